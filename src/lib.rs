@@ -1,4 +1,8 @@
 #![no_std]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#![cfg_attr(feature = "local_waker", feature(local_waker))]
+#![cfg_attr(feature = "coerce_unsized", feature(coerce_unsized, dispatch_from_dyn, unsize))]
+#![cfg_attr(all(test, feature = "coerce_unsized"), feature(arbitrary_self_types))]
 // Note: If you change this remember to update `README.md`. To do so run `cargo rdme`.
 //! `archery` is a rust library that offers a way to abstraction over
 //! [`Rc`](::alloc::rc::Rc) and
@@ -92,6 +96,44 @@
 //! To use it you need to enable the `triomphe` feature in `archery`. Use `ArcTK` as the pointer
 //! kind in [`SharedPointer`](crate::shared_pointer::SharedPointer).
 //!
+//! ## Biased reference counting
+//!
+//! With the `std` feature enabled you can use the `BiasedArcK` pointer kind. It is thread-safe,
+//! like `ArcK`, but the thread that creates a value clones and drops its pointers without atomic
+//! operations. Other threads use an atomic counter, which is merged with the one of the owner
+//! thread once the owner no longer holds any pointer. This makes `BiasedArcK` faster than `ArcK`
+//! when most pointers are cloned and dropped by the thread that created them.
+//!
+//! Since other threads cannot always tell whether they drop the last pointer, they sometimes hand
+//! it over to the owner thread, so `BiasedArcK` does not support `SharedPointer::into_inner()`.
+//!
+//! ```toml
+//! [dependencies]
+//! archery = { version = "<version>", features = ["std"] }
+//! ```
+//!
+//! ## Runtime-selected atomicity
+//!
+//! Also with the `std` feature, the `DynK` pointer kind lets you choose at runtime whether
+//! reference counts are updated atomically, so code that only learns at startup if it will run
+//! multithreaded does not need to be compiled for both `RcK` and `ArcK`. The mode is selected
+//! once for the whole process, before the first `DynK` pointer is created:
+//!
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
+//! use archery::*;
+//!
+//! DynK::set_mode(DynMode::Atomic).unwrap();
+//!
+//! let value: SharedPointer<_, DynK> = SharedPointer::new(1944);
+//! # }
+//! ```
+//!
+//! Since the mode is only known at runtime, `DynK` pointers cannot cross threads. In the atomic
+//! mode they can be converted to and from `ArcK` pointers, which can, through the token returned
+//! by `DynK::atomic()`.
+//!
 //! ## Serialization
 //!
 //! We support serialization through [serde](https://crates.io/crates/serde). To use it
@@ -101,11 +143,273 @@
 //! [dependencies]
 //! archery = { version = "<version>", features = ["serde"] }
 //! ```
-//! # Limitations
 //!
-//! Currently it is not possible to have unsized types inside a
-//! [`SharedPointer`](crate::shared_pointer::SharedPointer). As a workaround you can put the
-//! unsized type inside a [`Box`](::alloc::boxed::Box).
+//! By default a [`SharedPointer`](crate::shared_pointer::SharedPointer) is serialized as the value
+//! it points to:
+//!
+//! ```rust
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use archery::*;
+//! use serde::Deserialize;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     name: SharedPointer<String, ArcK>,
+//! }
+//!
+//! let config = Config { name: SharedPointer::new("archery".to_owned()) };
+//! let json = serde_json::to_string(&config).unwrap();
+//!
+//! assert_eq!(json, r#"{"name":"archery"}"#);
+//! # }
+//! ```
+//!
+//! A value shared by many pointers is therefore serialized many times. With the `std` feature also
+//! enabled, the [`serde::shared`](crate::shared_pointer::serde::shared) module serializes each shared value
+//! once, and the deserialized pointers share values the same way.
+//!
+//! For zero-copy deserialization enable the `rkyv` feature, which supports
+//! [rkyv](https://crates.io/crates/rkyv). A shared value is archived once, and the deserialized
+//! pointers share values the same way, for any pointer kind. See the
+//! [`rkyv`](crate::shared_pointer::rkyv) module for details.
+//!
+//! ## Fallible allocation
+//!
+//! [`SharedPointer::try_new()`](crate::shared_pointer::SharedPointer::try_new) returns an
+//! [`AllocError`](crate::AllocError) instead of aborting when memory allocation fails. It is
+//! available for the pointer kinds that implement
+//! [`SharedFallibleKind`](crate::shared_pointer::kind::SharedFallibleKind), such as `ArcTK` and
+//! `BiasedArcK`. `RcK` and `ArcK` rely on the unstable fallible allocation API of the standard
+//! library, so they only implement it with the `allocator_api` feature, which requires a nightly
+//! compiler:
+//!
+//! ```toml
+//! [dependencies]
+//! archery = { version = "<version>", features = ["allocator_api"] }
+//! ```
+//!
+//! ## Custom allocators
+//!
+//! With the `allocator_api` feature, which requires a nightly compiler, you can use the `RcAllocK`
+//! and `ArcAllocK` pointer kinds to allocate memory with a custom
+//! [`Allocator`](::core::alloc::Allocator):
+//!
+//! ```rust,ignore
+//! #![feature(allocator_api)]
+//!
+//! use archery::*;
+//!
+//! let value: SharedPointer<_, RcAllocK<MyAllocator>> = SharedPointer::new_in(1944, MyAllocator);
+//! ```
+//!
+//! Constructors that do not take an allocator, such as `SharedPointer::new()`, use the
+//! allocator’s [`Default`] implementation. There is no support for custom allocators on stable
+//! rust, since the `Rc` and `Arc` of the standard library only accept allocators through the
+//! unstable `Allocator` trait. The kind instance of these pointers holds their allocator, so
+//! they do not implement `SharedStatelessKind`, and cannot be converted into a bare pointer with
+//! `SharedPointer::into_raw()`.
+//!
+//! In particular there is no `allocator-api2` feature: the `Rc` and `Arc` of the standard library
+//! cannot use its allocators on stable rust either, so supporting it would mean shipping our own
+//! reference-counted pointers for `RcAllocK` and `ArcAllocK`, which we decided against for now.
+//!
+//! ## Unsized types
+//!
+//! A [`SharedPointer`](crate::shared_pointer::SharedPointer) can also hold unsized types, such as
+//! slices and string slices:
+//!
+//! ```rust
+//! use archery::*;
+//!
+//! let name: SharedPointer<str, RcK> = SharedPointer::from("António Variações");
+//! let years: SharedPointer<[u32], ArcK> = SharedPointer::from(vec![1944, 1984]);
+//!
+//! assert_eq!(&*name, "António Variações");
+//! assert_eq!(years.len(), 2);
+//! ```
+//!
+//! Trait objects can be obtained from a pointer to a concrete type with the
+//! [`coerce_shared_pointer!`](crate::coerce_shared_pointer) macro:
+//!
+//! ```rust
+//! use archery::*;
+//! use std::fmt::Display;
+//!
+//! let value: SharedPointer<i32, ArcK> = SharedPointer::new(1944);
+//! let value: SharedPointer<dyn Display, ArcK> = coerce_shared_pointer!(value);
+//!
+//! assert_eq!(value.to_string(), "1944");
+//! ```
+//!
+//! With the `coerce_unsized` feature, which requires a nightly compiler, the conversion is also
+//! an implicit coercion, and trait objects can be called through methods with a
+//! `self: SharedPointer<Self, P>` receiver for the pointer kinds that hold no state, such as `RcK`
+//! and `ArcK`:
+//!
+//! ```rust
+//! # #[cfg(feature = "coerce_unsized")]
+//! # {
+//! use archery::*;
+//! use std::fmt::Display;
+//!
+//! let value: SharedPointer<dyn Display, ArcK> = SharedPointer::new(1944);
+//!
+//! assert_eq!(value.to_string(), "1944");
+//! # }
+//! ```
+//!
+//! ## Static values
+//!
+//! A [`SharedPointer`](crate::shared_pointer::SharedPointer) can point to a value that lives
+//! forever, in which case cloning and dropping it never touches a reference count. Use
+//! [`StaticSharedPointer`](crate::shared_pointer::static_pointer::StaticSharedPointer) to declare
+//! such values in `static` items:
+//!
+//! ```rust
+//! use archery::*;
+//!
+//! static EMPTY: StaticSharedPointer<Vec<i32>> = StaticSharedPointer::new(Vec::new());
+//!
+//! let empty: SharedPointer<Vec<i32>, ArcK> = EMPTY.get();
+//!
+//! assert!(empty.is_empty());
+//! ```
+//!
+//! ## Interning
+//!
+//! With the `std` feature, an [`Interner`](crate::shared_pointer::interner::Interner) returns the
+//! same pointer for equal values, so they can be compared with
+//! [`SharedPointer::ptr_eq()`](crate::shared_pointer::SharedPointer::ptr_eq). Values are evicted
+//! once the interner holds their only pointer. For a thread-safe interner use
+//! [`SyncInterner`](crate::shared_pointer::interner::SyncInterner) with an atomic pointer kind.
+//!
+//! ## Interior mutability
+//!
+//! Kinds implementing [`SharedLockKind`](crate::shared_pointer::kind::SharedLockKind) have a
+//! matching lock type, so generic code can share mutable values without paying for locking with
+//! `RcK`: `SharedPointer<P::Lock<T>, P>` is an `Rc<RefCell<T>>` for `RcK`, and an
+//! `Arc<SyncLock<T>>` for `ArcK` and `ArcTK`. [`SyncLock`](crate::shared_pointer::lock::SyncLock)
+//! is a readers-writer lock, which uses the standard library’s `RwLock` with the `std` feature
+//! and a spin lock otherwise. All locks have the same API:
+//!
+//! ```rust
+//! use archery::*;
+//!
+//! fn push<P: SharedLockKind>(list: &SharedPointer<P::Lock<Vec<i32>>, P>, value: i32) {
+//!     list.lock().push(value);
+//! }
+//!
+//! let list: SharedPointer<_, RcK> = SharedPointer::new(SharedLock::new(Vec::new()));
+//!
+//! push(&list, 1944);
+//!
+//! assert_eq!(*list.read(), [1944]);
+//! ```
+//!
+//! ## Lazy values
+//!
+//! [`SharedOnce`](crate::shared_pointer::once::SharedOnce) and
+//! [`SharedLazy`](crate::shared_pointer::once::SharedLazy) hold a value that is initialized on
+//! first access and shared by all their clones. They use a `OnceCell` for `RcK`, and a
+//! thread-safe once-cell for atomic kinds, as selected by
+//! [`SharedOnceKind`](crate::shared_pointer::kind::SharedOnceKind):
+//!
+//! ```rust
+//! use archery::*;
+//!
+//! let squares: SharedLazy<Vec<u32>, _, ArcK> = SharedLazy::new(|| (0..8).map(|i| i * i).collect());
+//!
+//! assert_eq!(squares[3], 9);
+//! ```
+//!
+//! ## Swappable pointers
+//!
+//! A [`SyncSharedPointerCell`](crate::shared_pointer::cell::SyncSharedPointerCell) holds a
+//! `SharedPointer` of an atomic kind (`ArcK` or `ArcTK`) that can be loaded and replaced
+//! concurrently, which is useful for read-mostly values such as a configuration that can be
+//! reloaded. It is guarded by a read-write lock, so it is not lock-free.
+//! [`SharedPointerCell`](crate::shared_pointer::cell::SharedPointerCell) is its
+//! single-threaded counterpart, and both implement
+//! [`SharedCell`](crate::shared_pointer::cell::SharedCell):
+//!
+//! ```rust
+//! use archery::*;
+//!
+//! let config: SyncSharedPointerCell<String, ArcK> = SyncSharedPointerCell::from("v1".to_owned());
+//!
+//! config.rcu(|old| format!("{old}+"));
+//!
+//! assert_eq!(*config.load(), "v1+");
+//! ```
+//!
+//! ## Wakers
+//!
+//! Executors that store their tasks in a `SharedPointer` of an atomic kind can turn them into a
+//! [`Waker`](::core::task::Waker) by implementing
+//! [`SharedWake`](crate::shared_pointer::wake::SharedWake) for the task, without wrapping each
+//! task in a standard library `Arc`. Cloning and dropping the waker clones and drops the
+//! `SharedPointer`:
+//!
+//! ```rust
+//! use archery::*;
+//! use std::sync::atomic::AtomicBool;
+//! use std::sync::atomic::Ordering;
+//! use std::task::Waker;
+//!
+//! struct Task {
+//!     scheduled: AtomicBool,
+//! }
+//!
+//! impl SharedWake<ArcK> for Task {
+//!     fn wake(this: SharedPointer<Task, ArcK>) {
+//!         this.scheduled.store(true, Ordering::Release);
+//!     }
+//! }
+//!
+//! let task: SharedPointer<_, ArcK> = SharedPointer::new(Task { scheduled: AtomicBool::new(false) });
+//! let waker: Waker = SharedPointer::into_waker(SharedPointer::clone(&task));
+//!
+//! assert_eq!(SharedPointer::strong_count(&task), 2);
+//!
+//! waker.wake();
+//!
+//! assert!(task.scheduled.load(Ordering::Acquire));
+//! assert_eq!(SharedPointer::strong_count(&task), 1);
+//! ```
+//!
+//! With the `local_waker` feature, which requires a nightly compiler, tasks of any pointer kind,
+//! such as `RcK`, can be turned into a `LocalWaker` by implementing `SharedLocalWake`.
+//!
+//! ## Shared futures
+//!
+//! A [`SharedFuture`](crate::shared_pointer::future::SharedFuture) can be cloned and awaited by
+//! several consumers, which all get a clone of its output. Consumers are tracked without atomic
+//! operations for `RcK`, and with a [`SyncLock`](crate::shared_pointer::lock::SyncLock) for atomic
+//! kinds, so a `SharedFuture` of an atomic kind can be awaited from different threads:
+//!
+//! ```rust
+//! use archery::*;
+//! use std::pin::pin;
+//! use std::task::Context;
+//! use std::task::Poll;
+//! use std::task::Waker;
+//! use std::thread;
+//!
+//! let config: SharedFuture<_, ArcK> = SharedFuture::new(async { "v1".to_owned() });
+//! let config_clone = SharedFuture::clone(&config);
+//!
+//! thread::spawn(move || {
+//!     let mut cx = Context::from_waker(Waker::noop());
+//!
+//!     assert_eq!(pin!(config_clone).poll(&mut cx), Poll::Ready("v1".to_owned()));
+//! })
+//! .join()
+//! .unwrap();
+//!
+//! assert_eq!(SharedFuture::peek(&config).as_deref(), Some("v1"));
+//! ```
 //!
 //! # Alternative approaches
 //!
@@ -119,20 +423,74 @@
 
 extern crate alloc;
 
-#[cfg(test)]
-#[macro_use]
+#[cfg(any(test, feature = "std"))]
+#[cfg_attr(test, macro_use)]
 extern crate std;
 
 pub mod shared_pointer;
 
+pub use shared_pointer::future;
+
 pub use shared_pointer::SharedPointer;
+pub use shared_pointer::by_address::Address;
+pub use shared_pointer::by_address::ByAddress;
+pub use shared_pointer::cell::SharedCell;
+pub use shared_pointer::cell::SharedPointerCell;
+pub use shared_pointer::cell::SyncSharedPointerCell;
+pub use shared_pointer::future::SharedFuture;
+#[cfg(feature = "std")]
+pub use shared_pointer::interner::Interner;
+#[cfg(feature = "std")]
+pub use shared_pointer::interner::SyncInterner;
+pub use shared_pointer::lock::SharedLock;
+pub use shared_pointer::lock::SyncLock;
+pub use shared_pointer::once::SharedLazy;
+pub use shared_pointer::once::SharedOnce;
+pub use shared_pointer::once::SharedOnceCell;
+pub use shared_pointer::once::SyncOnceCell;
+pub use shared_pointer::static_pointer::StaticSharedPointer;
+pub use shared_pointer::thin::ThinSharedPointer;
+pub use shared_pointer::unique::UniqueSharedPointer;
+#[cfg(feature = "local_waker")]
+pub use shared_pointer::wake::SharedLocalWake;
+pub use shared_pointer::wake::SharedWake;
+pub use shared_pointer::weak::SharedWeak;
 
+pub use shared_pointer::kind::AllocError;
+#[cfg(feature = "allocator_api")]
+pub use shared_pointer::kind::SharedAllocatorKind;
+pub use shared_pointer::kind::SharedAtomicKind;
+pub use shared_pointer::kind::SharedFallibleKind;
+pub use shared_pointer::kind::SharedIntoInnerKind;
+pub use shared_pointer::kind::SharedLockKind;
+pub use shared_pointer::kind::SharedOnceKind;
 pub use shared_pointer::kind::SharedPointerKind;
+pub use shared_pointer::kind::SharedStatelessKind;
+pub use shared_pointer::kind::SharedThinKind;
+pub use shared_pointer::kind::SharedWeakKind;
 
+#[cfg(feature = "allocator_api")]
+#[doc(no_inline)]
+pub use shared_pointer::kind::ArcAllocK;
 #[doc(no_inline)]
 pub use shared_pointer::kind::ArcK;
 #[cfg(feature = "triomphe")]
 #[doc(no_inline)]
 pub use shared_pointer::kind::ArcTK;
+#[cfg(feature = "std")]
+#[doc(no_inline)]
+pub use shared_pointer::kind::BiasedArcK;
+#[cfg(feature = "std")]
+#[doc(no_inline)]
+pub use shared_pointer::kind::DynAtomic;
+#[cfg(feature = "std")]
+#[doc(no_inline)]
+pub use shared_pointer::kind::DynK;
+#[cfg(feature = "std")]
+#[doc(no_inline)]
+pub use shared_pointer::kind::DynMode;
+#[cfg(feature = "allocator_api")]
+#[doc(no_inline)]
+pub use shared_pointer::kind::RcAllocK;
 #[doc(no_inline)]
 pub use shared_pointer::kind::RcK;
@@ -0,0 +1,190 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::hash::Hash;
+use core::hash::Hasher;
+use core::ops::Deref;
+use core::ptr;
+
+/// Wrapper of a pointer whose [`Hash`], [`Eq`] and [`Ord`] implementations use the address the
+/// pointer points to, instead of the value.
+///
+/// This gives identity semantics to pointers such as [`SharedPointer`], which is what you want for
+/// memoization tables or sets of visited nodes: two pointers are equal if and only if they point
+/// to the same allocation, as with [`SharedPointer::ptr_eq()`]. This is also much faster than
+/// comparing or hashing large values.
+///
+/// Pointers of different kinds can be compared, and a map or set of `ByAddress` keys can be
+/// queried with an [`Address`], which is built from a plain reference.
+///
+/// Like [`SharedPointer::ptr_eq()`], the metadata of pointers to unsized types is ignored. Note
+/// that distinct values of zero-sized types may have the same address.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+/// use std::collections::HashSet;
+///
+/// let a: SharedPointer<_, RcK> = SharedPointer::new("António Variações");
+/// let b: SharedPointer<_, RcK> = SharedPointer::new("António Variações");
+///
+/// let mut visited: HashSet<ByAddress<SharedPointer<&str, RcK>>> = HashSet::new();
+///
+/// visited.insert(ByAddress(SharedPointer::clone(&a)));
+///
+/// assert!(visited.contains(&ByAddress(a)));
+/// assert!(!visited.contains(&ByAddress(SharedPointer::clone(&b))));
+/// assert!(!visited.contains(Address::from_ref(&*b)));
+/// ```
+///
+/// [`SharedPointer`]: crate::shared_pointer::SharedPointer
+/// [`SharedPointer::ptr_eq()`]: crate::shared_pointer::SharedPointer::ptr_eq
+#[derive(Clone, Copy, Default)]
+pub struct ByAddress<Ptr>(pub Ptr);
+
+impl<Ptr> ByAddress<Ptr> {
+    #[inline(always)]
+    pub fn into_inner(this: ByAddress<Ptr>) -> Ptr {
+        this.0
+    }
+}
+
+impl<Ptr: Deref> ByAddress<Ptr> {
+    #[inline(always)]
+    fn addr(&self) -> *const () {
+        ptr::from_ref::<Ptr::Target>(&self.0).cast::<()>()
+    }
+}
+
+impl<Ptr> Deref for ByAddress<Ptr> {
+    type Target = Ptr;
+
+    #[inline(always)]
+    fn deref(&self) -> &Ptr {
+        &self.0
+    }
+}
+
+impl<Ptr> From<Ptr> for ByAddress<Ptr> {
+    #[inline(always)]
+    fn from(ptr: Ptr) -> ByAddress<Ptr> {
+        ByAddress(ptr)
+    }
+}
+
+impl<Ptr: Deref> Borrow<Address<Ptr::Target>> for ByAddress<Ptr> {
+    #[inline(always)]
+    fn borrow(&self) -> &Address<Ptr::Target> {
+        Address::from_ref(&self.0)
+    }
+}
+
+impl<Ptr: Deref> Hash for ByAddress<Ptr> {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state);
+    }
+}
+
+impl<Ptr, PtrO> PartialEq<ByAddress<PtrO>> for ByAddress<Ptr>
+where
+    Ptr: Deref,
+    PtrO: Deref<Target = Ptr::Target>,
+{
+    #[inline(always)]
+    fn eq(&self, other: &ByAddress<PtrO>) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl<Ptr: Deref> Eq for ByAddress<Ptr> {}
+
+impl<Ptr, PtrO> PartialOrd<ByAddress<PtrO>> for ByAddress<Ptr>
+where
+    Ptr: Deref,
+    PtrO: Deref<Target = Ptr::Target>,
+{
+    #[inline(always)]
+    fn partial_cmp(&self, other: &ByAddress<PtrO>) -> Option<Ordering> {
+        Some(self.addr().cmp(&other.addr()))
+    }
+}
+
+impl<Ptr: Deref> Ord for ByAddress<Ptr> {
+    #[inline(always)]
+    fn cmp(&self, other: &ByAddress<Ptr>) -> Ordering {
+        self.addr().cmp(&other.addr())
+    }
+}
+
+impl<Ptr: Debug> Debug for ByAddress<Ptr> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+/// A value that is hashed, compared and ordered by its address, like a [`ByAddress`] pointing to
+/// it.
+///
+/// This is the type [`ByAddress`] keys borrow as, so a map or set of them can be queried with a
+/// plain reference to a value, turned into an `&Address` with [`Address::from_ref()`].
+#[repr(transparent)]
+pub struct Address<T: ?Sized>(T);
+
+impl<T: ?Sized> Address<T> {
+    #[inline(always)]
+    #[must_use]
+    pub fn from_ref(v: &T) -> &Address<T> {
+        // SAFETY: `Address<T>` is a transparent wrapper of `T`.
+        unsafe { &*(ptr::from_ref(v) as *const Address<T>) }
+    }
+
+    #[inline(always)]
+    fn addr(&self) -> *const () {
+        ptr::from_ref(self).cast::<()>()
+    }
+}
+
+impl<T: ?Sized> Hash for Address<T> {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state);
+    }
+}
+
+impl<T: ?Sized> PartialEq for Address<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Address<T>) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl<T: ?Sized> Eq for Address<T> {}
+
+impl<T: ?Sized> PartialOrd for Address<T> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Address<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized> Ord for Address<T> {
+    #[inline(always)]
+    fn cmp(&self, other: &Address<T>) -> Ordering {
+        self.addr().cmp(&other.addr())
+    }
+}
+
+impl<T: ?Sized> Debug for Address<T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.debug_tuple("Address").field(&self.addr()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,137 @@
+use super::*;
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::ArcK;
+use crate::shared_pointer::kind::RcK;
+use crate::shared_pointer::thin::ThinSharedPointer;
+use pretty_assertions::assert_eq;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::BuildHasher;
+use std::hash::BuildHasherDefault;
+use std::string::String;
+use std::string::ToString;
+
+fn hash<T: Hash + ?Sized>(v: &T) -> u64 {
+    BuildHasherDefault::<DefaultHasher>::default().hash_one(v)
+}
+
+#[test]
+fn test_eq() {
+    let a: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let b: SharedPointer<_, RcK> = SharedPointer::new(42);
+
+    assert_eq!(ByAddress(SharedPointer::clone(&a)), ByAddress(SharedPointer::clone(&a)));
+    assert_ne!(ByAddress(SharedPointer::clone(&a)), ByAddress(SharedPointer::clone(&b)));
+}
+
+#[test]
+fn test_eq_across_kinds() {
+    static VALUE: i32 = 42;
+
+    let a: SharedPointer<i32, RcK> = SharedPointer::from_static(&VALUE);
+    let b: SharedPointer<i32, ArcK> = SharedPointer::from_static(&VALUE);
+    let c: SharedPointer<i32, ArcK> = SharedPointer::new(42);
+
+    assert_eq!(ByAddress(a), ByAddress(SharedPointer::clone(&b)));
+    assert_ne!(ByAddress(b), ByAddress(c));
+}
+
+#[test]
+fn test_eq_unsized() {
+    let a: SharedPointer<str, RcK> = SharedPointer::from("hello");
+    let b: SharedPointer<str, RcK> = SharedPointer::from("hello");
+
+    assert_eq!(ByAddress(SharedPointer::clone(&a)), ByAddress(SharedPointer::clone(&a)));
+    assert_ne!(ByAddress(a), ByAddress(b));
+}
+
+#[test]
+fn test_ord() {
+    let a: SharedPointer<_, RcK> = SharedPointer::new(2);
+    let b: SharedPointer<_, RcK> = SharedPointer::new(1);
+
+    let by_address_a = ByAddress(SharedPointer::clone(&a));
+    let by_address_b = ByAddress(SharedPointer::clone(&b));
+
+    assert_eq!(
+        by_address_a.cmp(&by_address_b),
+        SharedPointer::as_ptr(&a).cmp(&SharedPointer::as_ptr(&b))
+    );
+    assert_eq!(by_address_a.cmp(&ByAddress(SharedPointer::clone(&a))), Ordering::Equal);
+    assert_eq!(
+        by_address_a.partial_cmp(&by_address_b),
+        Address::from_ref(&*a).partial_cmp(Address::from_ref(&*b))
+    );
+}
+
+#[test]
+fn test_hash() {
+    let a: SharedPointer<_, RcK> = SharedPointer::new(String::from("a"));
+
+    assert_eq!(hash(&ByAddress(SharedPointer::clone(&a))), hash(&ByAddress(&*a)));
+    assert_eq!(hash(&ByAddress(SharedPointer::clone(&a))), hash(Address::from_ref(&*a)));
+}
+
+#[test]
+fn test_hash_set() {
+    let a: SharedPointer<_, ArcK> = SharedPointer::new(vec![1, 2, 3]);
+    let b: SharedPointer<_, ArcK> = SharedPointer::new(vec![1, 2, 3]);
+    let mut set = HashSet::new();
+
+    assert!(set.insert(ByAddress(SharedPointer::clone(&a))));
+    assert!(!set.insert(ByAddress(SharedPointer::clone(&a))));
+    assert!(set.insert(ByAddress(SharedPointer::clone(&b))));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_borrowed_lookup() {
+    let a: SharedPointer<_, RcK> = SharedPointer::new("a".to_string());
+    let b: SharedPointer<_, RcK> = SharedPointer::new("a".to_string());
+    let mut map: HashMap<ByAddress<SharedPointer<String, RcK>>, i32> = HashMap::new();
+
+    map.insert(ByAddress(SharedPointer::clone(&a)), 1);
+
+    let value: &String = &a;
+
+    assert_eq!(map.get(Address::from_ref(value)), Some(&1));
+    assert_eq!(map.get(Address::from_ref(&*b)), None);
+
+    let mut set: BTreeSet<ByAddress<SharedPointer<String, RcK>>> = BTreeSet::new();
+
+    set.insert(ByAddress(SharedPointer::clone(&a)));
+    set.insert(ByAddress(SharedPointer::clone(&b)));
+
+    assert!(set.contains(Address::from_ref(&*a)));
+    assert!(set.contains(Address::from_ref(&*b)));
+    assert!(!set.contains(Address::from_ref(&"a".to_string())));
+}
+
+#[test]
+fn test_thin_shared_pointer() {
+    let a: ThinSharedPointer<i32, i32, RcK> =
+        ThinSharedPointer::from_header_and_iter(0, [1, 2].into_iter());
+    let b: ThinSharedPointer<i32, i32, RcK> =
+        ThinSharedPointer::from_header_and_iter(0, [1, 2].into_iter());
+
+    assert_eq!(ByAddress(a.clone()), ByAddress(a.clone()));
+    assert_ne!(ByAddress(a), ByAddress(b));
+}
+
+#[test]
+fn test_deref_into_inner() {
+    let a: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let by_address = ByAddress::from(SharedPointer::clone(&a));
+
+    assert_eq!(**by_address, 42);
+    assert!(SharedPointer::ptr_eq(&ByAddress::into_inner(by_address), &a));
+}
+
+#[test]
+fn test_debug() {
+    let a: SharedPointer<_, RcK> = SharedPointer::new(42);
+
+    assert_eq!(format!("{:?}", ByAddress(a)), "42");
+}
@@ -0,0 +1,350 @@
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::SharedAtomicKind;
+use crate::shared_pointer::kind::SharedPointerKind;
+use crate::shared_pointer::lock::SharedLock;
+use crate::shared_pointer::lock::SyncLock;
+use core::cell::RefCell;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::mem;
+
+/// Cell holding a [`SharedPointer`] that can be read and replaced through a shared reference.
+///
+/// This is implemented by [`SyncSharedPointerCell`], which can be shared between threads, and by
+/// [`SharedPointerCell`], which cannot, so code generic over the pointer kind can use either.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+///
+/// fn increment<C: SharedCell<i32, P>, P: SharedPointerKind>(counter: &C) -> i32 {
+///     *counter.rcu(|old| **old + 1)
+/// }
+///
+/// let counter: SharedPointerCell<i32, RcK> = SharedPointerCell::from(0);
+/// let sync_counter: SyncSharedPointerCell<i32, ArcK> = SyncSharedPointerCell::from(0);
+///
+/// increment(&counter);
+/// increment(&sync_counter);
+///
+/// assert_eq!(*counter.load(), 1);
+/// assert_eq!(*sync_counter.load(), 1);
+/// ```
+///
+/// [`SharedPointer`]: crate::shared_pointer::SharedPointer
+pub trait SharedCell<T: ?Sized, P: SharedPointerKind> {
+    /// Returns a clone of the current pointer.
+    fn load(&self) -> SharedPointer<T, P>;
+
+    /// Replaces the current pointer with `ptr`, dropping the previous one.
+    fn store(&self, ptr: SharedPointer<T, P>) {
+        drop(self.swap(ptr));
+    }
+
+    /// Replaces the current pointer with `ptr`, returning the previous one.
+    fn swap(&self, ptr: SharedPointer<T, P>) -> SharedPointer<T, P>;
+
+    /// Replaces the current pointer with `new` if it points to the same allocation as `current`
+    /// (see [`SharedPointer::ptr_eq()`]).
+    ///
+    /// Returns the previous pointer, so the replacement happened if and only if the returned
+    /// pointer points to the same allocation as `current`. Otherwise `new` is dropped.
+    ///
+    /// [`SharedPointer::ptr_eq()`]: crate::shared_pointer::SharedPointer::ptr_eq
+    fn compare_and_swap(
+        &self,
+        current: &SharedPointer<T, P>,
+        new: SharedPointer<T, P>,
+    ) -> SharedPointer<T, P>;
+
+    /// Read-copy-update: replaces the current pointer with the one computed by `f` from it,
+    /// returning the previous pointer.
+    ///
+    /// If the pointer is replaced by someone else while `f` runs, `f` is called again with the
+    /// new pointer, so it may be called more than once.
+    fn rcu<R, F>(&self, mut f: F) -> SharedPointer<T, P>
+    where
+        F: FnMut(&SharedPointer<T, P>) -> R,
+        R: Into<SharedPointer<T, P>>,
+    {
+        let mut current = self.load();
+
+        loop {
+            let previous = self.compare_and_swap(&current, f(&current).into());
+
+            if SharedPointer::ptr_eq(&previous, &current) {
+                return previous;
+            }
+
+            current = previous;
+        }
+    }
+}
+
+/// [`SharedPointer`] that can be loaded and replaced from any thread, for pointer kinds that are
+/// atomic, like [`ArcK`](crate::ArcK).
+///
+/// This is useful for values that are read often and replaced rarely, like a configuration that
+/// can be reloaded: readers [load](SharedCell::load) a pointer to the current snapshot and keep
+/// using it for as long as they need, unaffected by concurrent replacements.
+///
+/// This is not lock-free, unlike the `ArcSwap` of the
+/// [`arc-swap`](https://crates.io/crates/arc-swap) crate. Replacing the pointer never drops the
+/// value while a concurrent load is cloning the pointer to it because the pointer is guarded by
+/// a [`SyncLock`]: loads lock it for reading, so they do not wait
+/// for each other, and replacements lock it for writing. The lock is only held for as long as it
+/// takes to clone or swap the pointer, so no user code runs while it is held, and previous
+/// pointers are dropped after it is released.
+///
+/// Every load writes to the lock, besides updating the reference count, so concurrent loads
+/// contend on both. A replacement waits for the loads in progress, and the loads that start after
+/// it wait for the replacement. With the `std` feature waiting threads are parked by the
+/// [`std::sync::RwLock`]. Otherwise they spin, and a steady stream of loads can starve
+/// replacements.
+///
+/// The operations are provided by the [`SharedCell`] trait. For a single-threaded counterpart
+/// see [`SharedPointerCell`].
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+/// use std::thread;
+///
+/// let config: SyncSharedPointerCell<String, ArcK> = SyncSharedPointerCell::from("v1".to_owned());
+///
+/// thread::scope(|s| {
+///     s.spawn(|| config.store(SharedPointer::new("v2".to_owned())));
+///
+///     let snapshot = config.load();
+///
+///     assert!(*snapshot == "v1" || *snapshot == "v2");
+/// });
+///
+/// assert_eq!(*config.load(), "v2");
+/// ```
+///
+/// [`SharedPointer`]: crate::shared_pointer::SharedPointer
+pub struct SyncSharedPointerCell<T: ?Sized, P>
+where
+    P: SharedAtomicKind,
+{
+    ptr: SyncLock<SharedPointer<T, P>>,
+}
+
+impl<T: ?Sized, P> SyncSharedPointerCell<T, P>
+where
+    P: SharedAtomicKind,
+{
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(ptr: SharedPointer<T, P>) -> SyncSharedPointerCell<T, P> {
+        SyncSharedPointerCell { ptr: SyncLock::new(ptr) }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn into_inner(this: SyncSharedPointerCell<T, P>) -> SharedPointer<T, P> {
+        SharedLock::into_inner(this.ptr)
+    }
+
+    /// Returns a mutable reference to the pointer. No locking is needed since this borrows the
+    /// `SyncSharedPointerCell` mutably.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut SharedPointer<T, P> {
+        SharedLock::get_mut(&mut self.ptr)
+    }
+}
+
+impl<T: ?Sized, P> SharedCell<T, P> for SyncSharedPointerCell<T, P>
+where
+    P: SharedAtomicKind,
+{
+    #[inline(always)]
+    fn load(&self) -> SharedPointer<T, P> {
+        SharedPointer::clone(&self.ptr.read())
+    }
+
+    #[inline(always)]
+    fn swap(&self, ptr: SharedPointer<T, P>) -> SharedPointer<T, P> {
+        mem::replace(&mut *self.ptr.write(), ptr)
+    }
+
+    #[inline(always)]
+    fn compare_and_swap(
+        &self,
+        current: &SharedPointer<T, P>,
+        new: SharedPointer<T, P>,
+    ) -> SharedPointer<T, P> {
+        let mut ptr = self.ptr.write();
+
+        match SharedPointer::ptr_eq(&ptr, current) {
+            true => mem::replace(&mut *ptr, new),
+            // `new` is dropped when this returns, after the lock is released.
+            false => SharedPointer::clone(&ptr),
+        }
+    }
+}
+
+impl<T: ?Sized, P> Default for SyncSharedPointerCell<T, P>
+where
+    P: SharedAtomicKind,
+    SharedPointer<T, P>: Default,
+{
+    #[inline(always)]
+    fn default() -> SyncSharedPointerCell<T, P> {
+        SyncSharedPointerCell::new(SharedPointer::default())
+    }
+}
+
+impl<T: ?Sized, P> From<SharedPointer<T, P>> for SyncSharedPointerCell<T, P>
+where
+    P: SharedAtomicKind,
+{
+    #[inline(always)]
+    fn from(ptr: SharedPointer<T, P>) -> SyncSharedPointerCell<T, P> {
+        SyncSharedPointerCell::new(ptr)
+    }
+}
+
+impl<T, P> From<T> for SyncSharedPointerCell<T, P>
+where
+    P: SharedAtomicKind,
+{
+    #[inline(always)]
+    fn from(value: T) -> SyncSharedPointerCell<T, P> {
+        SyncSharedPointerCell::new(SharedPointer::new(value))
+    }
+}
+
+impl<T: ?Sized, P> Debug for SyncSharedPointerCell<T, P>
+where
+    T: Debug,
+    P: SharedAtomicKind,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SyncSharedPointerCell").field(&self.load()).finish()
+    }
+}
+
+/// Single-threaded counterpart of [`SyncSharedPointerCell`], which cannot be shared between
+/// threads but works with any pointer kind, like [`RcK`](crate::RcK).
+///
+/// The operations are provided by the [`SharedCell`] trait.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+///
+/// let cell: SharedPointerCell<i32, RcK> = SharedPointerCell::from(1);
+/// let previous = cell.swap(SharedPointer::new(2));
+///
+/// assert_eq!(*previous, 1);
+/// assert_eq!(*cell.load(), 2);
+/// ```
+pub struct SharedPointerCell<T: ?Sized, P>
+where
+    P: SharedPointerKind,
+{
+    /// A `RefCell` rather than an `UnsafeCell`, since cloning a pointer may run user code (like
+    /// the `Clone` implementation of a custom allocator), which could access the cell.
+    ptr: RefCell<SharedPointer<T, P>>,
+}
+
+impl<T: ?Sized, P> SharedPointerCell<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(ptr: SharedPointer<T, P>) -> SharedPointerCell<T, P> {
+        SharedPointerCell { ptr: RefCell::new(ptr) }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn into_inner(this: SharedPointerCell<T, P>) -> SharedPointer<T, P> {
+        this.ptr.into_inner()
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut SharedPointer<T, P> {
+        self.ptr.get_mut()
+    }
+}
+
+impl<T: ?Sized, P> SharedCell<T, P> for SharedPointerCell<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn load(&self) -> SharedPointer<T, P> {
+        SharedPointer::clone(&self.ptr.borrow())
+    }
+
+    #[inline(always)]
+    fn swap(&self, ptr: SharedPointer<T, P>) -> SharedPointer<T, P> {
+        self.ptr.replace(ptr)
+    }
+
+    #[inline(always)]
+    fn compare_and_swap(
+        &self,
+        current: &SharedPointer<T, P>,
+        new: SharedPointer<T, P>,
+    ) -> SharedPointer<T, P> {
+        let matches = SharedPointer::ptr_eq(&self.ptr.borrow(), current);
+
+        match matches {
+            true => self.ptr.replace(new),
+            false => self.load(),
+        }
+    }
+}
+
+impl<T: ?Sized, P> Default for SharedPointerCell<T, P>
+where
+    P: SharedPointerKind,
+    SharedPointer<T, P>: Default,
+{
+    #[inline(always)]
+    fn default() -> SharedPointerCell<T, P> {
+        SharedPointerCell::new(SharedPointer::default())
+    }
+}
+
+impl<T: ?Sized, P> From<SharedPointer<T, P>> for SharedPointerCell<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn from(ptr: SharedPointer<T, P>) -> SharedPointerCell<T, P> {
+        SharedPointerCell::new(ptr)
+    }
+}
+
+impl<T, P> From<T> for SharedPointerCell<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn from(value: T) -> SharedPointerCell<T, P> {
+        SharedPointerCell::new(SharedPointer::new(value))
+    }
+}
+
+impl<T: ?Sized, P> Debug for SharedPointerCell<T, P>
+where
+    T: Debug,
+    P: SharedPointerKind,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedPointerCell").field(&self.load()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test;
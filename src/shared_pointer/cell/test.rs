@@ -0,0 +1,237 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+#[cfg(feature = "triomphe")]
+use crate::shared_pointer::kind::ArcTK;
+use crate::shared_pointer::kind::RcK;
+use pretty_assertions::assert_eq;
+use static_assertions::assert_impl_all;
+use static_assertions::assert_not_impl_any;
+use std::cell::Cell;
+use std::format;
+use std::string::String;
+use std::thread;
+use std::vec;
+use std::vec::Vec;
+
+assert_impl_all!(SyncSharedPointerCell<i32, ArcK>: Send, Sync);
+assert_not_impl_any!(SyncSharedPointerCell<Cell<i32>, ArcK>: Send, Sync);
+assert_impl_all!(SharedPointerCell<i32, ArcK>: Send);
+assert_not_impl_any!(SharedPointerCell<i32, ArcK>: Sync);
+assert_not_impl_any!(SharedPointerCell<i32, RcK>: Send, Sync);
+
+fn check_load_store_swap<P: SharedPointerKind, C: SharedCell<i32, P>>(cell: &C) {
+    let one = cell.load();
+
+    assert_eq!(*one, 1);
+    assert!(SharedPointer::ptr_eq(&one, &cell.load()));
+
+    cell.store(SharedPointer::new(2));
+
+    assert_eq!(*cell.load(), 2);
+    assert_eq!(*one, 1);
+    assert_eq!(SharedPointer::strong_count(&one), 1);
+
+    let three = SharedPointer::new(3);
+    let two = cell.swap(SharedPointer::clone(&three));
+
+    assert_eq!(*two, 2);
+    assert_eq!(SharedPointer::strong_count(&two), 1);
+    assert!(SharedPointer::ptr_eq(&cell.load(), &three));
+}
+
+#[test]
+fn test_load_store_swap() {
+    check_load_store_swap(&SharedPointerCell::<i32, RcK>::from(1));
+    check_load_store_swap(&SharedPointerCell::<i32, ArcK>::from(1));
+    check_load_store_swap(&SyncSharedPointerCell::<i32, ArcK>::from(1));
+    #[cfg(feature = "triomphe")]
+    check_load_store_swap(&SyncSharedPointerCell::<i32, ArcTK>::from(1));
+}
+
+fn check_compare_and_swap<P: SharedPointerKind, C: SharedCell<i32, P>>(cell: &C) {
+    let one = cell.load();
+    let other_one = SharedPointer::new(1);
+
+    let previous = cell.compare_and_swap(&other_one, SharedPointer::new(2));
+
+    assert!(SharedPointer::ptr_eq(&previous, &one));
+    assert_eq!(*cell.load(), 1);
+
+    drop(previous);
+
+    let previous = cell.compare_and_swap(&one, SharedPointer::new(2));
+
+    assert!(SharedPointer::ptr_eq(&previous, &one));
+    assert_eq!(*cell.load(), 2);
+    assert_eq!(SharedPointer::strong_count(&one), 2);
+}
+
+#[test]
+fn test_compare_and_swap() {
+    check_compare_and_swap(&SharedPointerCell::<i32, RcK>::from(1));
+    check_compare_and_swap(&SyncSharedPointerCell::<i32, ArcK>::from(1));
+    #[cfg(feature = "triomphe")]
+    check_compare_and_swap(&SyncSharedPointerCell::<i32, ArcTK>::from(1));
+}
+
+fn check_rcu<P: SharedPointerKind, C: SharedCell<i32, P>>(cell: &C) {
+    let previous = cell.rcu(|old| **old + 1);
+
+    assert_eq!(*previous, 1);
+    assert_eq!(*cell.load(), 2);
+
+    let ptr = SharedPointer::new(10);
+    let previous = cell.rcu(|_| SharedPointer::clone(&ptr));
+
+    assert_eq!(*previous, 2);
+    assert!(SharedPointer::ptr_eq(&cell.load(), &ptr));
+}
+
+#[test]
+fn test_rcu() {
+    check_rcu(&SharedPointerCell::<i32, RcK>::from(1));
+    check_rcu(&SyncSharedPointerCell::<i32, ArcK>::from(1));
+}
+
+#[test]
+fn test_rcu_retries() {
+    let cell: SharedPointerCell<i32, RcK> = SharedPointerCell::from(1);
+    let calls = Cell::new(0);
+
+    let previous = cell.rcu(|old| {
+        calls.set(calls.get() + 1);
+
+        if calls.get() == 1 {
+            cell.store(SharedPointer::new(10));
+        }
+
+        **old + 1
+    });
+
+    assert_eq!(calls.get(), 2);
+    assert_eq!(*previous, 10);
+    assert_eq!(*cell.load(), 11);
+}
+
+#[test]
+fn test_unsized() {
+    let cell: SyncSharedPointerCell<str, ArcK> =
+        SyncSharedPointerCell::new(SharedPointer::from("a"));
+
+    cell.store(SharedPointer::from("bc"));
+
+    assert_eq!(&*cell.load(), "bc");
+
+    let cell: SharedPointerCell<[i32], RcK> = SharedPointerCell::new(SharedPointer::from(vec![1]));
+
+    assert_eq!(*cell.swap(SharedPointer::from(vec![2, 3])), [1]);
+    assert_eq!(*cell.load(), [2, 3]);
+}
+
+#[test]
+fn test_static() {
+    static VALUE: i32 = 42;
+
+    let cell: SyncSharedPointerCell<i32, ArcK> =
+        SyncSharedPointerCell::new(SharedPointer::from_static(&VALUE));
+    let ptr = cell.load();
+
+    assert!(SharedPointer::is_static(&ptr));
+    assert!(SharedPointer::ptr_eq(&cell.compare_and_swap(&ptr, SharedPointer::new(0)), &ptr));
+    assert_eq!(*cell.load(), 0);
+}
+
+#[test]
+fn test_into_inner_get_mut() {
+    let mut cell: SyncSharedPointerCell<i32, ArcK> = SyncSharedPointerCell::from(1);
+
+    *cell.get_mut() = SharedPointer::new(2);
+
+    assert_eq!(*SyncSharedPointerCell::into_inner(cell), 2);
+
+    let mut cell: SharedPointerCell<i32, RcK> = SharedPointerCell::from(1);
+
+    *cell.get_mut() = SharedPointer::new(2);
+
+    assert_eq!(*SharedPointerCell::into_inner(cell), 2);
+}
+
+#[test]
+fn test_default() {
+    let cell: SyncSharedPointerCell<i32, ArcK> = SyncSharedPointerCell::default();
+
+    assert_eq!(*cell.load(), 0);
+
+    let cell: SharedPointerCell<String, RcK> = SharedPointerCell::default();
+
+    assert_eq!(*cell.load(), "");
+}
+
+#[test]
+fn test_debug() {
+    let cell: SyncSharedPointerCell<i32, ArcK> = SyncSharedPointerCell::from(42);
+
+    assert_eq!(format!("{:?}", cell), "SyncSharedPointerCell(42)");
+
+    let cell: SharedPointerCell<&str, RcK> = SharedPointerCell::from("a");
+
+    assert_eq!(format!("{:?}", cell), r#"SharedPointerCell("a")"#);
+}
+
+#[test]
+fn test_concurrent_load_store() {
+    /// Value that checks it is still alive when read.
+    struct Value {
+        alive: bool,
+        data: Vec<usize>,
+    }
+
+    impl Drop for Value {
+        fn drop(&mut self) {
+            self.alive = false;
+        }
+    }
+
+    let cell: SyncSharedPointerCell<Value, ArcK> =
+        SyncSharedPointerCell::from(Value { alive: true, data: vec![0; 16] });
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..10_000 {
+                    let value = cell.load();
+
+                    assert!(value.alive);
+                    assert_eq!(value.data.len(), 16);
+                }
+            });
+        }
+
+        for _ in 0..2 {
+            s.spawn(|| {
+                for i in 0..1_000 {
+                    cell.store(SharedPointer::new(Value { alive: true, data: vec![i; 16] }));
+                }
+            });
+        }
+    });
+
+    assert!(cell.load().alive);
+}
+
+#[test]
+fn test_concurrent_rcu() {
+    let cell: SyncSharedPointerCell<usize, ArcK> = SyncSharedPointerCell::from(0);
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..1_000 {
+                    cell.rcu(|old| **old + 1);
+                }
+            });
+        }
+    });
+
+    assert_eq!(*cell.load(), 4_000);
+}
@@ -0,0 +1,367 @@
+//! Futures whose output is shared by several consumers.
+//!
+//! A [`SharedFuture`] can be cloned and awaited by any number of consumers, which all get a clone
+//! of the output of the future it wraps. The future is only polled once per wake up, by whichever
+//! consumer polls it, and its output is kept until the last `SharedFuture` is dropped.
+
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::ArcK;
+use crate::shared_pointer::kind::SharedLockKind;
+use crate::shared_pointer::lock::SharedLock;
+use crate::shared_pointer::lock::SyncLock;
+use crate::shared_pointer::wake::SharedWake;
+use alloc::vec::Vec;
+use core::cell::UnsafeCell;
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::Ordering;
+use core::task::Context;
+use core::task::Poll;
+use core::task::Waker;
+
+/// State of a [`SharedFuture`], protected by the lock of the pointer kind.
+struct State<T> {
+    output: Option<T>,
+    /// Whether a consumer is polling the future, in which case it has exclusive access to it.
+    polling: bool,
+    /// Whether the future panicked while being polled.
+    poisoned: bool,
+    /// Wakers of the consumers waiting for the output, indexed by their key.
+    wakers: Vec<Option<Waker>>,
+    /// Keys that are free to be reused.
+    free_keys: Vec<usize>,
+}
+
+impl<T> State<T> {
+    fn new() -> State<T> {
+        State {
+            output: None,
+            polling: false,
+            poisoned: false,
+            wakers: Vec::new(),
+            free_keys: Vec::new(),
+        }
+    }
+
+    /// Registers the waker of a consumer, assigning it a key if it does not have one yet. Returns
+    /// the key if the registered waker changed, in which case the notifier must be updated.
+    fn register(&mut self, key: &mut Option<usize>, waker: &Waker) -> Option<usize> {
+        match *key {
+            Some(k) => match &mut self.wakers[k] {
+                Some(w) if w.will_wake(waker) => None,
+                slot => {
+                    *slot = Some(waker.clone());
+                    Some(k)
+                }
+            },
+            None => {
+                let k = match self.free_keys.pop() {
+                    Some(k) => {
+                        self.wakers[k] = Some(waker.clone());
+                        k
+                    }
+                    None => {
+                        self.wakers.push(Some(waker.clone()));
+                        self.wakers.len() - 1
+                    }
+                };
+
+                *key = Some(k);
+
+                Some(k)
+            }
+        }
+    }
+
+    fn unregister(&mut self, key: usize) {
+        self.wakers[key] = None;
+        self.free_keys.push(key);
+    }
+}
+
+/// Waker of the inner future, which wakes every consumer waiting for the output.
+///
+/// A [`Waker`] must be `Send` and `Sync`, so this is always atomically reference counted, and it
+/// holds copies of the wakers in `State::wakers` behind a [`SyncLock`]. Those are only updated
+/// when a consumer registers a different waker, or is dropped.
+struct Notifier {
+    /// Whether the future was woken since a consumer last started polling it.
+    woken: AtomicBool,
+    wakers: SyncLock<Vec<Option<Waker>>>,
+}
+
+impl Notifier {
+    fn set(&self, key: usize, waker: Option<Waker>) {
+        let mut wakers = self.wakers.lock();
+
+        if key >= wakers.len() {
+            wakers.resize(key + 1, None);
+        }
+
+        wakers[key] = waker;
+    }
+
+    /// Wakes every consumer. The wakers are woken after the lock is released, since waking runs
+    /// arbitrary code.
+    fn wake_all(&self) {
+        let wakers: Vec<Waker> = self.wakers.read().iter().flatten().cloned().collect();
+
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    /// Wakes every consumer for the last time, once the future is done.
+    fn wake_all_and_clear(&self) {
+        let wakers = mem::take(&mut *self.wakers.lock());
+
+        wakers.into_iter().flatten().for_each(Waker::wake);
+    }
+}
+
+impl SharedWake<ArcK> for Notifier {
+    fn wake(this: SharedPointer<Notifier, ArcK>) {
+        Notifier::wake_by_ref(&this);
+    }
+
+    fn wake_by_ref(this: &SharedPointer<Notifier, ArcK>) {
+        this.woken.store(true, Ordering::Release);
+        this.wake_all();
+    }
+}
+
+struct Inner<F: Future, P: SharedLockKind> {
+    /// Only accessed by the consumer that set `State::polling`.
+    future: UnsafeCell<Option<F>>,
+    state: P::Lock<State<F::Output>>,
+    notifier: SharedPointer<Notifier, ArcK>,
+    /// Waker of `notifier`, with which the inner future is polled.
+    waker: Waker,
+}
+
+// SAFETY: The future is only accessed by one thread at a time, since only the consumer that sets
+//         `State::polling` (while holding the lock) accesses it, so it only needs to be `Send`.
+unsafe impl<F, P> Sync for Inner<F, P>
+where
+    F: Future + Send,
+    P: SharedLockKind,
+    P::Lock<State<F::Output>>: Sync,
+{
+}
+
+/// Resets the state if polling the future panics, so that consumers do not wait forever.
+struct PollGuard<'a, F: Future, P: SharedLockKind> {
+    inner: &'a Inner<F, P>,
+}
+
+impl<F: Future, P: SharedLockKind> Drop for PollGuard<'_, F, P> {
+    fn drop(&mut self) {
+        {
+            let mut state = self.inner.state.lock();
+
+            state.polling = false;
+            state.poisoned = true;
+        }
+
+        self.inner.notifier.wake_all_and_clear();
+    }
+}
+
+/// Future that can be cloned and awaited by several consumers, which all get a clone of the
+/// output of the future it wraps. This is similar to the `Shared` future of the
+/// [`futures`](https://crates.io/crates/futures) crate, but the inner future is stored in a
+/// [`SharedPointer`] of kind `P`.
+///
+/// The bookkeeping of the consumers is protected by the lock of `P`, as defined by
+/// [`SharedLockKind`], so it is a `RefCell` for [`RcK`](crate::RcK), and a
+/// [`SyncLock`](crate::SyncLock) for [`ArcK`](crate::ArcK) or `ArcTK`, so that consumers can run
+/// on different threads.
+///
+/// The inner future is polled by the consumer being polled, but with a waker of its own, which
+/// wakes every consumer waiting for the output. This way the future makes progress as long as
+/// any consumer is polled, even if the consumer that polled it last is never polled again. That
+/// waker can be used from any thread, whatever `P` is, so it is atomically reference counted and
+/// keeps its own copy of the wakers of the consumers behind a `SyncLock`. The copy is only
+/// updated when a consumer polls with a different waker than the last time, or is dropped, so
+/// polling with the same waker takes no atomic lock for `RcK`.
+///
+/// # Panics
+///
+/// If the inner future panics, the panic is propagated to the consumer polling it, and polling
+/// any of the consumers afterwards panics.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+/// use std::pin::pin;
+/// use std::task::Context;
+/// use std::task::Poll;
+/// use std::task::Waker;
+///
+/// let future: SharedFuture<_, RcK> = SharedFuture::new(async { "done".to_owned() });
+/// let future_clone = SharedFuture::clone(&future);
+///
+/// let mut cx = Context::from_waker(Waker::noop());
+///
+/// assert_eq!(pin!(future).poll(&mut cx), Poll::Ready("done".to_owned()));
+/// assert_eq!(SharedFuture::peek(&future_clone).as_deref(), Some("done"));
+/// assert_eq!(pin!(future_clone).poll(&mut cx), Poll::Ready("done".to_owned()));
+/// ```
+pub struct SharedFuture<F: Future, P: SharedLockKind> {
+    inner: SharedPointer<Inner<F, P>, P>,
+    /// Key of the waker of this consumer, once it is registered.
+    key: Option<usize>,
+}
+
+impl<F: Future, P: SharedLockKind> SharedFuture<F, P> {
+    #[must_use]
+    pub fn new(future: F) -> SharedFuture<F, P> {
+        let notifier: SharedPointer<Notifier, ArcK> = SharedPointer::new(Notifier {
+            woken: AtomicBool::new(false),
+            wakers: SyncLock::new(Vec::new()),
+        });
+        let inner = Inner {
+            future: UnsafeCell::new(Some(future)),
+            state: SharedLock::new(State::new()),
+            waker: SharedPointer::into_waker(SharedPointer::clone(&notifier)),
+            notifier,
+        };
+
+        SharedFuture { inner: SharedPointer::new(inner), key: None }
+    }
+
+    /// Returns `true` if the two `SharedFuture`s wrap the same future.
+    #[inline(always)]
+    #[must_use]
+    pub fn ptr_eq(this: &SharedFuture<F, P>, other: &SharedFuture<F, P>) -> bool {
+        SharedPointer::ptr_eq(&this.inner, &other.inner)
+    }
+}
+
+impl<F, P> SharedFuture<F, P>
+where
+    F: Future,
+    F::Output: Clone,
+    P: SharedLockKind,
+{
+    /// Returns the output of the future, if it is available.
+    #[must_use]
+    pub fn peek(this: &SharedFuture<F, P>) -> Option<F::Output> {
+        this.inner.state.read().output.clone()
+    }
+}
+
+impl<F: Future, P: SharedLockKind> Clone for SharedFuture<F, P> {
+    #[inline(always)]
+    fn clone(&self) -> SharedFuture<F, P> {
+        SharedFuture { inner: SharedPointer::clone(&self.inner), key: None }
+    }
+}
+
+impl<F, P> Future for SharedFuture<F, P>
+where
+    F: Future,
+    F::Output: Clone,
+    P: SharedLockKind,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = self.get_mut();
+        let inner: &Inner<F, P> = &this.inner;
+
+        {
+            let mut state = inner.state.lock();
+
+            if let Some(output) = &state.output {
+                return Poll::Ready(output.clone());
+            }
+
+            assert!(!state.poisoned, "inner future of `SharedFuture` panicked");
+
+            // Registered while holding the lock, so that we are woken if the consumer polling the
+            // future sets the output after we checked it.
+            if let Some(key) = state.register(&mut this.key, cx.waker()) {
+                inner.notifier.set(key, Some(cx.waker().clone()));
+            }
+
+            if state.polling {
+                // We are woken along with every other consumer once the future wakes.
+                return Poll::Pending;
+            }
+
+            state.polling = true;
+            inner.notifier.woken.store(false, Ordering::Release);
+        }
+
+        let guard = PollGuard { inner };
+
+        // SAFETY: We set `State::polling`, so we have exclusive access to the future. The future
+        //         is pinned, since it lives in the shared pointer and is never moved out of it.
+        let future = unsafe { Pin::new_unchecked(&mut *inner.future.get()) };
+        let poll = future
+            .as_pin_mut()
+            .expect("the future is only dropped when done")
+            .poll(&mut Context::from_waker(&inner.waker));
+
+        if poll.is_ready() {
+            // Drop the future before taking the lock, since dropping it runs arbitrary code.
+            // SAFETY: We still have exclusive access to the future.
+            unsafe { *inner.future.get() = None };
+        }
+
+        mem::forget(guard);
+
+        let poll = {
+            let mut state = inner.state.lock();
+
+            state.polling = false;
+
+            match poll {
+                Poll::Ready(output) => {
+                    state.output = Some(output.clone());
+                    state.wakers = Vec::new();
+                    state.free_keys = Vec::new();
+
+                    Poll::Ready(output)
+                }
+                Poll::Pending => Poll::Pending,
+            }
+        };
+
+        if poll.is_ready() {
+            inner.notifier.wake_all_and_clear();
+        }
+
+        poll
+    }
+}
+
+impl<F: Future, P: SharedLockKind> Drop for SharedFuture<F, P> {
+    fn drop(&mut self) {
+        let Some(key) = self.key else {
+            return;
+        };
+
+        {
+            let mut state = self.inner.state.lock();
+
+            if state.output.is_some() || state.poisoned {
+                return;
+            }
+
+            state.unregister(key);
+            self.inner.notifier.set(key, None);
+        }
+
+        // The future was woken and nobody polled it since. We may be the consumer that was
+        // supposed to, so another consumer needs to.
+        if self.inner.notifier.woken.load(Ordering::Acquire) {
+            self.inner.notifier.wake_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,365 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+#[cfg(feature = "triomphe")]
+use crate::shared_pointer::kind::ArcTK;
+use crate::shared_pointer::kind::RcK;
+use crate::shared_pointer::kind::SharedAtomicKind;
+use crate::shared_pointer::wake::SharedWake;
+use core::future;
+use pretty_assertions::assert_eq;
+use static_assertions::assert_impl_all;
+use static_assertions::assert_not_impl_any;
+use std::boxed::Box;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::string::String;
+use std::string::ToString;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::thread::Thread;
+use std::vec::Vec;
+
+assert_impl_all!(SharedFuture<future::Ready<i32>, ArcK>: Send, Sync, Unpin);
+assert_not_impl_any!(SharedFuture<future::Ready<i32>, RcK>: Send, Sync);
+assert_not_impl_any!(SharedFuture<future::Ready<Cell<i32>>, ArcK>: Send, Sync);
+
+/// Waker that counts how many times it was woken.
+#[derive(Default)]
+struct CountingWaker {
+    wakes: AtomicUsize,
+}
+
+impl CountingWaker {
+    fn wakes(&self) -> usize {
+        self.wakes.load(Ordering::Relaxed)
+    }
+}
+
+impl<P: SharedAtomicKind> SharedWake<P> for CountingWaker {
+    fn wake(this: SharedPointer<CountingWaker, P>) {
+        CountingWaker::wake_by_ref(&this);
+    }
+
+    fn wake_by_ref(this: &SharedPointer<CountingWaker, P>) {
+        this.wakes.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn counting_waker() -> (SharedPointer<CountingWaker, ArcK>, Waker) {
+    let counter: SharedPointer<CountingWaker, ArcK> = SharedPointer::default();
+    let waker = SharedPointer::into_waker(SharedPointer::clone(&counter));
+
+    (counter, waker)
+}
+
+fn poll<F: Future + Unpin>(future: &mut F, waker: &Waker) -> Poll<F::Output> {
+    Pin::new(future).poll(&mut Context::from_waker(waker))
+}
+
+/// Future that is ready once it is signaled.
+#[derive(Clone, Default)]
+struct Signal {
+    state: SharedPointer<Mutex<(bool, Option<Waker>)>, ArcK>,
+}
+
+impl Signal {
+    fn signal(&self) {
+        let waker = {
+            let mut state = self.state.lock().unwrap();
+
+            state.0 = true;
+            state.1.take()
+        };
+
+        waker.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl Future for Signal {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+
+        match state.0 {
+            true => Poll::Ready(()),
+            false => {
+                state.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn check_ready<P: SharedLockKind>() {
+    let polls = Cell::new(0);
+    let mut future: SharedFuture<_, P> = SharedFuture::new(future::poll_fn(|_| {
+        polls.set(polls.get() + 1);
+        Poll::Ready("a".to_string())
+    }));
+    let mut future_clone = SharedFuture::clone(&future);
+
+    assert!(SharedFuture::ptr_eq(&future, &future_clone));
+    assert_eq!(SharedFuture::peek(&future), None);
+
+    assert_eq!(poll(&mut future, Waker::noop()), Poll::Ready("a".to_string()));
+    assert_eq!(SharedFuture::peek(&future_clone).as_deref(), Some("a"));
+    assert_eq!(poll(&mut future_clone, Waker::noop()), Poll::Ready("a".to_string()));
+    assert_eq!(poll(&mut future, Waker::noop()), Poll::Ready("a".to_string()));
+
+    assert_eq!(polls.get(), 1);
+}
+
+#[test]
+fn test_ready() {
+    check_ready::<RcK>();
+    check_ready::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_ready::<ArcTK>();
+}
+
+fn check_wakes_all_consumers<P: SharedLockKind>() {
+    let signal = Signal::default();
+    let mut future_a: SharedFuture<_, P> = SharedFuture::new(Signal::clone(&signal));
+    let mut future_b = SharedFuture::clone(&future_a);
+    let (counter_a, waker_a) = counting_waker();
+    let (counter_b, waker_b) = counting_waker();
+
+    assert_eq!(poll(&mut future_a, &waker_a), Poll::Pending);
+    assert_eq!(poll(&mut future_b, &waker_b), Poll::Pending);
+
+    signal.signal();
+
+    assert_eq!(counter_a.wakes(), 1);
+    assert_eq!(counter_b.wakes(), 1);
+
+    assert_eq!(poll(&mut future_b, &waker_b), Poll::Ready(()));
+
+    // Consumers are woken once more when the output is available.
+    assert_eq!(counter_a.wakes(), 2);
+
+    assert_eq!(poll(&mut future_a, &waker_a), Poll::Ready(()));
+}
+
+#[test]
+fn test_wakes_all_consumers() {
+    check_wakes_all_consumers::<RcK>();
+    check_wakes_all_consumers::<ArcK>();
+}
+
+fn check_first_poller_never_polled_again<P: SharedLockKind>() {
+    let signal = Signal::default();
+    let state = SharedPointer::clone(&signal.state);
+    // Only keeps the waker of its first poll, like futures that do not update their waker.
+    let mut future_a: SharedFuture<_, P> = SharedFuture::new(future::poll_fn(move |cx| {
+        let mut state = state.lock().unwrap();
+
+        match state.0 {
+            true => Poll::Ready(()),
+            false => {
+                state.1.get_or_insert_with(|| cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }));
+    let mut future_b = SharedFuture::clone(&future_a);
+    let (counter_a, waker_a) = counting_waker();
+    let (counter_b, waker_b) = counting_waker();
+
+    assert_eq!(poll(&mut future_a, &waker_a), Poll::Pending);
+    assert_eq!(poll(&mut future_b, &waker_b), Poll::Pending);
+
+    signal.signal();
+
+    assert_eq!(counter_a.wakes(), 1);
+    assert_eq!(counter_b.wakes(), 1);
+    assert_eq!(poll(&mut future_b, &waker_b), Poll::Ready(()));
+}
+
+#[test]
+fn test_first_poller_never_polled_again() {
+    check_first_poller_never_polled_again::<RcK>();
+    check_first_poller_never_polled_again::<ArcK>();
+}
+
+#[test]
+fn test_drop_woken_consumer_wakes_consumers() {
+    type Hook = RefCell<Option<Box<dyn FnOnce()>>>;
+
+    let hook: SharedPointer<Hook, RcK> = SharedPointer::default();
+    // Wakes itself and then runs the hook, as if another consumer was polled concurrently.
+    let mut future_a: SharedFuture<_, RcK> = SharedFuture::new(future::poll_fn({
+        let hook = SharedPointer::clone(&hook);
+
+        move |cx| {
+            cx.waker().wake_by_ref();
+
+            let hook = hook.borrow_mut().take();
+
+            if let Some(hook) = hook {
+                hook();
+            }
+
+            Poll::<()>::Pending
+        }
+    }));
+    let future_b: SharedPointer<RefCell<_>, RcK> =
+        SharedPointer::new(RefCell::new(SharedFuture::clone(&future_a)));
+    let future_c = SharedFuture::clone(&future_a);
+    let (counter_a, waker_a) = counting_waker();
+    let (counter_b, waker_b) = counting_waker();
+
+    *hook.borrow_mut() = Some(Box::new({
+        let future_b = SharedPointer::clone(&future_b);
+        let waker_b = waker_b.clone();
+
+        move || assert_eq!(poll(&mut *future_b.borrow_mut(), &waker_b), Poll::Pending)
+    }));
+
+    // `future_b` registers after the future woke, while `future_a` is polling it.
+    assert_eq!(poll(&mut future_a, &waker_a), Poll::Pending);
+    assert_eq!(counter_a.wakes(), 1);
+    assert_eq!(counter_b.wakes(), 0);
+
+    // `future_c` never registered, so it owes no poll.
+    drop(future_c);
+
+    assert_eq!(counter_b.wakes(), 0);
+
+    // `future_a` was woken to poll the future, so it passes that on when dropped.
+    drop(future_a);
+
+    assert_eq!(counter_b.wakes(), 1);
+    assert_eq!(poll(&mut *future_b.borrow_mut(), &waker_b), Poll::Pending);
+}
+
+#[test]
+fn test_drop_unwoken_consumer() {
+    let signal = Signal::default();
+    let mut future_a: SharedFuture<_, RcK> = SharedFuture::new(Signal::clone(&signal));
+    let mut future_b = SharedFuture::clone(&future_a);
+    let (counter_a, waker_a) = counting_waker();
+    let (counter_b, waker_b) = counting_waker();
+
+    assert_eq!(poll(&mut future_a, &waker_a), Poll::Pending);
+    assert_eq!(poll(&mut future_b, &waker_b), Poll::Pending);
+
+    drop(future_a);
+
+    assert_eq!(counter_b.wakes(), 0);
+
+    signal.signal();
+
+    assert_eq!(counter_a.wakes(), 0);
+    assert_eq!(counter_b.wakes(), 1);
+    assert_eq!(poll(&mut future_b, &waker_b), Poll::Ready(()));
+}
+
+#[test]
+fn test_drop_output() {
+    let output: SharedPointer<i32, RcK> = SharedPointer::new(42);
+    let mut future: SharedFuture<_, RcK> = SharedFuture::new(future::ready(output.clone()));
+    let future_clone = SharedFuture::clone(&future);
+
+    assert_eq!(poll(&mut future, Waker::noop()), Poll::Ready(output.clone()));
+    assert_eq!(SharedPointer::strong_count(&output), 2);
+
+    drop(future);
+
+    assert_eq!(SharedPointer::strong_count(&output), 2);
+
+    drop(future_clone);
+
+    assert_eq!(SharedPointer::strong_count(&output), 1);
+}
+
+#[test]
+fn test_inner_future_panics() {
+    let polls = Cell::new(0);
+    let mut future_a: SharedFuture<_, RcK> = SharedFuture::new(future::poll_fn(|_| -> Poll<i32> {
+        polls.set(polls.get() + 1);
+
+        assert_ne!(polls.get(), 2, "inner panic");
+
+        Poll::Pending
+    }));
+    let mut future_b = SharedFuture::clone(&future_a);
+    let (counter_a, waker_a) = counting_waker();
+
+    assert_eq!(poll(&mut future_a, &waker_a), Poll::Pending);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| poll(&mut future_b, Waker::noop())));
+
+    assert!(result.is_err());
+    assert_eq!(counter_a.wakes(), 1);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| poll(&mut future_a, &waker_a)));
+
+    assert_eq!(
+        result.unwrap_err().downcast_ref::<&str>(),
+        Some(&"inner future of `SharedFuture` panicked")
+    );
+    assert_eq!(polls.get(), 2);
+}
+
+/// Waker that unparks a thread.
+struct Unparker {
+    thread: Thread,
+}
+
+impl SharedWake<ArcK> for Unparker {
+    fn wake(this: SharedPointer<Unparker, ArcK>) {
+        this.thread.unpark();
+    }
+}
+
+fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+    let waker = SharedPointer::<_, ArcK>::into_waker(SharedPointer::new(Unparker {
+        thread: thread::current(),
+    }));
+
+    loop {
+        match poll(&mut future, &waker) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+fn check_threads<P: SharedLockKind + SharedAtomicKind>()
+where
+    P::Lock<State<String>>: Send + Sync,
+{
+    let signal = Signal::default();
+    let future: SharedFuture<_, P> = SharedFuture::new(future::poll_fn(|cx| {
+        Pin::new(&mut Signal::clone(&signal)).poll(cx).map(|()| String::from("done"))
+    }));
+
+    let outputs: Vec<String> = thread::scope(|s| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let future = SharedFuture::clone(&future);
+
+                s.spawn(move || block_on(future))
+            })
+            .collect();
+
+        signal.signal();
+
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    assert_eq!(outputs.len(), 8);
+    assert!(outputs.iter().all(|output| output == "done"));
+}
+
+#[test]
+fn test_threads() {
+    check_threads::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_threads::<ArcTK>();
+}
@@ -0,0 +1,348 @@
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::SharedPointerKind;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::hash::BuildHasher;
+use core::hash::Hash;
+use std::collections::HashSet;
+use std::hash::RandomState;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::sync::TryLockError;
+
+/// Minimum number of entries the interner holds before evicting unused entries.
+const MIN_PURGE_THRESHOLD: usize = 32;
+
+/// Hash-consing table that returns a canonical [`SharedPointer`] for each distinct value.
+///
+/// Interning a value equal to one that is already interned returns a clone of the pointer
+/// returned the first time, so interned values can be compared with
+/// [`SharedPointer::ptr_eq()`] instead of by value.
+///
+/// An entry is evicted once the interner holds the only pointer to its value (its
+/// [strong count](SharedPointer::strong_count) is one). Eviction happens in
+/// [`Interner::purge()`], which is called automatically each time the number of entries doubles
+/// since the last purge, so interning has an amortized constant cost. Entries of
+/// [static values](SharedPointer::from_static) are never evicted.
+///
+/// This requires the `std` feature. For a thread-safe interner see [`SyncInterner`].
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+///
+/// let mut interner: Interner<String, RcK> = Interner::new();
+///
+/// let a = interner.intern("António Variações".to_owned());
+/// let b = interner.intern("António Variações".to_owned());
+///
+/// assert!(SharedPointer::ptr_eq(&a, &b));
+/// ```
+///
+/// [`SharedPointer`]: crate::shared_pointer::SharedPointer
+/// [`SharedPointer::ptr_eq()`]: crate::shared_pointer::SharedPointer::ptr_eq
+pub struct Interner<T: ?Sized, P, S = RandomState>
+where
+    P: SharedPointerKind,
+{
+    set: HashSet<SharedPointer<T, P>, S>,
+    purge_threshold: usize,
+}
+
+impl<T: ?Sized, P> Interner<T, P>
+where
+    T: Hash + Eq,
+    P: SharedPointerKind,
+{
+    #[must_use]
+    pub fn new() -> Interner<T, P> {
+        Interner::with_hasher(RandomState::new())
+    }
+}
+
+impl<T: ?Sized, P, S> Interner<T, P, S>
+where
+    T: Hash + Eq,
+    P: SharedPointerKind,
+    S: BuildHasher,
+{
+    #[must_use]
+    pub fn with_hasher(hasher: S) -> Interner<T, P, S> {
+        Interner { set: HashSet::with_hasher(hasher), purge_threshold: MIN_PURGE_THRESHOLD }
+    }
+
+    /// Returns the canonical pointer to a value equal to `value`, interning `value` if there is
+    /// none.
+    pub fn intern(&mut self, value: T) -> SharedPointer<T, P>
+    where
+        T: Sized,
+    {
+        match self.set.get(&value) {
+            Some(ptr) => SharedPointer::clone(ptr),
+            None => self.insert(SharedPointer::new(value)),
+        }
+    }
+
+    /// Returns the canonical pointer to a value equal to `value`, interning a pointer created
+    /// from `value` if there is none. This is useful for unsized types, such as `str`, since a
+    /// new allocation is only created if the value is not already interned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    ///
+    /// let mut interner: Interner<str, ArcK> = Interner::new();
+    ///
+    /// let a = interner.intern_ref("António Variações");
+    /// let b = interner.intern_ref("António Variações");
+    ///
+    /// assert!(SharedPointer::ptr_eq(&a, &b));
+    /// ```
+    pub fn intern_ref<'a>(&mut self, value: &'a T) -> SharedPointer<T, P>
+    where
+        SharedPointer<T, P>: From<&'a T>,
+    {
+        match self.set.get(value) {
+            Some(ptr) => SharedPointer::clone(ptr),
+            None => self.insert(SharedPointer::from(value)),
+        }
+    }
+
+    /// Returns the canonical pointer to a value equal to the one `ptr` points to, interning `ptr`
+    /// if there is none.
+    pub fn intern_shared(&mut self, ptr: SharedPointer<T, P>) -> SharedPointer<T, P> {
+        match self.set.get(&*ptr) {
+            Some(canonical) => SharedPointer::clone(canonical),
+            None => self.insert(ptr),
+        }
+    }
+
+    fn insert(&mut self, ptr: SharedPointer<T, P>) -> SharedPointer<T, P> {
+        if self.set.len() >= self.purge_threshold {
+            self.purge();
+            self.purge_threshold = MIN_PURGE_THRESHOLD.max(2 * self.set.len());
+        }
+
+        self.set.insert(SharedPointer::clone(&ptr));
+
+        ptr
+    }
+
+    /// Returns the canonical pointer to a value equal to `value`, if there is one.
+    #[must_use]
+    pub fn get(&self, value: &T) -> Option<SharedPointer<T, P>> {
+        self.set.get(value).cloned()
+    }
+
+    /// Returns whether `ptr` is the canonical pointer to its value, i.e. if it points to the same
+    /// allocation as the pointer interned for its value.
+    #[must_use]
+    pub fn contains(&self, ptr: &SharedPointer<T, P>) -> bool {
+        self.set.get(&**ptr).is_some_and(|canonical| SharedPointer::ptr_eq(canonical, ptr))
+    }
+
+    /// Evicts the entries whose value is only referenced by the interner.
+    pub fn purge(&mut self) {
+        self.set
+            .retain(|ptr| SharedPointer::is_static(ptr) || SharedPointer::strong_count(ptr) > 1);
+    }
+
+    /// Evicts all entries. Pointers previously returned by the interner stay valid, but are no
+    /// longer canonical.
+    pub fn clear(&mut self) {
+        self.set.clear();
+    }
+
+    /// Returns the number of entries, including entries that have not been evicted yet although
+    /// only the interner references their value.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+}
+
+impl<T: ?Sized, P, S> Default for Interner<T, P, S>
+where
+    T: Hash + Eq,
+    P: SharedPointerKind,
+    S: BuildHasher + Default,
+{
+    fn default() -> Interner<T, P, S> {
+        Interner::with_hasher(S::default())
+    }
+}
+
+impl<T: ?Sized, P, S> Debug for Interner<T, P, S>
+where
+    T: Debug,
+    P: SharedPointerKind,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.set.iter()).finish()
+    }
+}
+
+/// Thread-safe version of [`Interner`], which can be shared between threads if the pointer kind
+/// `P` is atomic, such as [`ArcK`](crate::ArcK).
+///
+/// All operations lock a mutex that guards an [`Interner`], so they take `&self`.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+/// use std::thread;
+///
+/// let interner: SyncInterner<u32, ArcK> = SyncInterner::new();
+///
+/// let (a, b) = thread::scope(|s| {
+///     let a = s.spawn(|| interner.intern(1944));
+///     let b = s.spawn(|| interner.intern(1944));
+///
+///     (a.join().unwrap(), b.join().unwrap())
+/// });
+///
+/// assert!(SharedPointer::ptr_eq(&a, &b));
+/// ```
+pub struct SyncInterner<T: ?Sized, P, S = RandomState>
+where
+    P: SharedPointerKind,
+{
+    interner: Mutex<Interner<T, P, S>>,
+}
+
+impl<T: ?Sized, P> SyncInterner<T, P>
+where
+    T: Hash + Eq,
+    P: SharedPointerKind,
+{
+    #[must_use]
+    pub fn new() -> SyncInterner<T, P> {
+        SyncInterner::with_hasher(RandomState::new())
+    }
+}
+
+impl<T: ?Sized, P, S> SyncInterner<T, P, S>
+where
+    T: Hash + Eq,
+    P: SharedPointerKind,
+    S: BuildHasher,
+{
+    #[must_use]
+    pub fn with_hasher(hasher: S) -> SyncInterner<T, P, S> {
+        SyncInterner { interner: Mutex::new(Interner::with_hasher(hasher)) }
+    }
+
+    /// Locks the interner. A panic while the lock was held (which can only come from the `Hash`
+    /// or `Eq` implementations of `T`) leaves the interner in a consistent state, so poisoning
+    /// is ignored.
+    fn lock(&self) -> MutexGuard<'_, Interner<T, P, S>> {
+        self.interner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// See [`Interner::intern()`].
+    pub fn intern(&self, value: T) -> SharedPointer<T, P>
+    where
+        T: Sized,
+    {
+        self.lock().intern(value)
+    }
+
+    /// See [`Interner::intern_ref()`].
+    pub fn intern_ref<'a>(&self, value: &'a T) -> SharedPointer<T, P>
+    where
+        SharedPointer<T, P>: From<&'a T>,
+    {
+        self.lock().intern_ref(value)
+    }
+
+    /// See [`Interner::intern_shared()`].
+    pub fn intern_shared(&self, ptr: SharedPointer<T, P>) -> SharedPointer<T, P> {
+        self.lock().intern_shared(ptr)
+    }
+
+    /// See [`Interner::get()`].
+    #[must_use]
+    pub fn get(&self, value: &T) -> Option<SharedPointer<T, P>> {
+        self.lock().get(value)
+    }
+
+    /// See [`Interner::contains()`].
+    #[must_use]
+    pub fn contains(&self, ptr: &SharedPointer<T, P>) -> bool {
+        self.lock().contains(ptr)
+    }
+
+    /// See [`Interner::purge()`].
+    pub fn purge(&self) {
+        self.lock().purge();
+    }
+
+    /// See [`Interner::clear()`].
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// See [`Interner::len()`].
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Consumes the `SyncInterner`, returning the underlying [`Interner`].
+    #[must_use]
+    pub fn into_inner(self) -> Interner<T, P, S> {
+        self.interner.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: ?Sized, P, S> Default for SyncInterner<T, P, S>
+where
+    T: Hash + Eq,
+    P: SharedPointerKind,
+    S: BuildHasher + Default,
+{
+    fn default() -> SyncInterner<T, P, S> {
+        SyncInterner::with_hasher(S::default())
+    }
+}
+
+impl<T: ?Sized, P, S> From<Interner<T, P, S>> for SyncInterner<T, P, S>
+where
+    P: SharedPointerKind,
+{
+    fn from(interner: Interner<T, P, S>) -> SyncInterner<T, P, S> {
+        SyncInterner { interner: Mutex::new(interner) }
+    }
+}
+
+impl<T: ?Sized, P, S> Debug for SyncInterner<T, P, S>
+where
+    T: Debug,
+    P: SharedPointerKind,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.interner.try_lock() {
+            Ok(interner) => Debug::fmt(&*interner, f),
+            Err(TryLockError::Poisoned(err)) => Debug::fmt(&*err.into_inner(), f),
+            Err(TryLockError::WouldBlock) => f.write_str("SyncInterner { <locked> }"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,197 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+#[cfg(feature = "triomphe")]
+use crate::shared_pointer::kind::ArcTK;
+use crate::shared_pointer::kind::RcK;
+use pretty_assertions::assert_eq;
+use static_assertions::assert_impl_all;
+use static_assertions::assert_not_impl_any;
+use std::format;
+use std::string::String;
+use std::string::ToString;
+use std::thread;
+use std::vec::Vec;
+
+assert_impl_all!(SyncInterner<i32, ArcK>: Send, Sync);
+assert_not_impl_any!(SyncInterner<i32, RcK>: Send, Sync);
+assert_not_impl_any!(Interner<i32, RcK>: Send, Sync);
+
+fn check_intern<P: SharedPointerKind>() {
+    let mut interner: Interner<String, P> = Interner::new();
+
+    let a = interner.intern("a".to_string());
+    let a_again = interner.intern("a".to_string());
+    let b = interner.intern("b".to_string());
+
+    assert!(SharedPointer::ptr_eq(&a, &a_again));
+    assert!(!SharedPointer::ptr_eq(&a, &b));
+    assert_eq!(*a, "a");
+    assert_eq!(*b, "b");
+    assert_eq!(interner.len(), 2);
+    assert_eq!(SharedPointer::strong_count(&a), 3);
+}
+
+#[test]
+fn test_intern() {
+    check_intern::<RcK>();
+    check_intern::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_intern::<ArcTK>();
+}
+
+#[test]
+fn test_intern_ref() {
+    let mut interner: Interner<str, RcK> = Interner::new();
+
+    let a = interner.intern_ref("a");
+    let a_again = interner.intern_ref("a");
+    let b = interner.intern_ref("b");
+
+    assert!(SharedPointer::ptr_eq(&a, &a_again));
+    assert!(!SharedPointer::ptr_eq(&a, &b));
+    assert_eq!(&*a, "a");
+
+    let mut interner: Interner<[i32], ArcK> = Interner::new();
+
+    let a = interner.intern_ref(&[1, 2]);
+    let a_again = interner.intern_ref(&[1, 2]);
+
+    assert!(SharedPointer::ptr_eq(&a, &a_again));
+}
+
+#[test]
+fn test_intern_shared() {
+    let mut interner: Interner<str, RcK> = Interner::new();
+
+    let a: SharedPointer<str, RcK> = SharedPointer::from("a");
+    let canonical = interner.intern_shared(SharedPointer::clone(&a));
+
+    assert!(SharedPointer::ptr_eq(&a, &canonical));
+
+    let other: SharedPointer<str, RcK> = SharedPointer::from("a");
+    let canonical = interner.intern_shared(other);
+
+    assert!(SharedPointer::ptr_eq(&a, &canonical));
+    assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn test_get_contains() {
+    let mut interner: Interner<i32, RcK> = Interner::new();
+
+    assert!(interner.get(&1).is_none());
+
+    let a = interner.intern(1);
+
+    assert!(SharedPointer::ptr_eq(&interner.get(&1).unwrap(), &a));
+    assert!(interner.contains(&a));
+    assert!(!interner.contains(&SharedPointer::new(1)));
+    assert!(!interner.contains(&SharedPointer::new(2)));
+}
+
+#[test]
+fn test_purge() {
+    let mut interner: Interner<i32, RcK> = Interner::new();
+
+    let a = interner.intern(1);
+    let b = interner.intern(2);
+
+    drop(b);
+
+    assert_eq!(interner.len(), 2);
+
+    interner.purge();
+
+    assert_eq!(interner.len(), 1);
+    assert!(interner.contains(&a));
+    assert!(interner.get(&2).is_none());
+    assert_eq!(SharedPointer::strong_count(&a), 2);
+
+    drop(a);
+    interner.purge();
+
+    assert!(interner.is_empty());
+}
+
+#[test]
+fn test_purge_static() {
+    static VALUE: i32 = 42;
+
+    let mut interner: Interner<i32, ArcK> = Interner::new();
+
+    drop(interner.intern_shared(SharedPointer::from_static(&VALUE)));
+    interner.purge();
+
+    let ptr = interner.intern(42);
+
+    assert!(SharedPointer::is_static(&ptr));
+}
+
+#[test]
+fn test_automatic_purge() {
+    let mut interner: Interner<usize, RcK> = Interner::new();
+    let kept: Vec<SharedPointer<usize, RcK>> = (0..10).map(|i| interner.intern(i)).collect();
+
+    for i in 10..10_000 {
+        drop(interner.intern(i));
+
+        assert!(interner.len() <= 2 * MIN_PURGE_THRESHOLD.max(2 * kept.len()));
+    }
+
+    for ptr in &kept {
+        assert!(interner.contains(ptr));
+    }
+}
+
+#[test]
+fn test_clear() {
+    let mut interner: Interner<i32, RcK> = Interner::new();
+
+    let a = interner.intern(1);
+
+    interner.clear();
+
+    assert!(interner.is_empty());
+    assert!(!interner.contains(&a));
+    assert_eq!(*a, 1);
+    assert!(!SharedPointer::ptr_eq(&a, &interner.intern(1)));
+}
+
+#[test]
+fn test_debug() {
+    let mut interner: Interner<i32, RcK> = Interner::new();
+    let _a = interner.intern(1);
+
+    assert_eq!(format!("{:?}", interner), "{1}");
+
+    let interner: SyncInterner<i32, RcK> = SyncInterner::from(interner);
+
+    assert_eq!(format!("{:?}", interner), "{1}");
+}
+
+#[test]
+fn test_sync_interner() {
+    let interner: SyncInterner<usize, ArcK> = SyncInterner::new();
+
+    let ptrs: Vec<Vec<SharedPointer<usize, ArcK>>> = thread::scope(|s| {
+        let handles: Vec<_> =
+            (0..4).map(|_| s.spawn(|| (0..100).map(|i| interner.intern(i)).collect())).collect();
+
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    for thread_ptrs in &ptrs[1..] {
+        for (a, b) in thread_ptrs.iter().zip(&ptrs[0]) {
+            assert!(SharedPointer::ptr_eq(a, b));
+        }
+    }
+
+    assert_eq!(interner.len(), 100);
+    assert!(interner.contains(&ptrs[0][0]));
+
+    drop(ptrs);
+    interner.purge();
+
+    assert!(interner.is_empty());
+    assert!(interner.into_inner().is_empty());
+}
@@ -0,0 +1,35 @@
+use core::fmt;
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+/// The error returned by the fallible constructors, such as
+/// [`SharedPointer::try_new()`](crate::shared_pointer::SharedPointer::try_new), when memory
+/// allocation fails.
+///
+/// This mirrors [`core::alloc::AllocError`], which is not yet stable.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AllocError;
+
+impl Display for AllocError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl core::error::Error for AllocError {}
+
+#[cfg(feature = "allocator_api")]
+impl From<core::alloc::AllocError> for AllocError {
+    #[inline(always)]
+    fn from(_: core::alloc::AllocError) -> AllocError {
+        AllocError
+    }
+}
+
+#[cfg(feature = "triomphe")]
+impl From<triomphe::AllocError> for AllocError {
+    #[inline(always)]
+    fn from(_: triomphe::AllocError) -> AllocError {
+        AllocError
+    }
+}
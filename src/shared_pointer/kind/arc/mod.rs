@@ -1,163 +1,290 @@
+use crate::shared_pointer::SharedPointer;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::AllocError;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::SharedAllocatorKind;
+use crate::shared_pointer::kind::SharedAtomicKind;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::SharedFallibleKind;
+use crate::shared_pointer::kind::SharedIntoInnerKind;
+use crate::shared_pointer::kind::SharedLockKind;
+use crate::shared_pointer::kind::SharedOnceKind;
 use crate::shared_pointer::kind::SharedPointerKind;
-use crate::shared_pointer::kind::erased_ptr::ErasedPtr;
+use crate::shared_pointer::kind::SharedStatelessKind;
+use crate::shared_pointer::kind::SharedThinKind;
+use crate::shared_pointer::kind::SharedWeakKind;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::move_from_box;
+use crate::shared_pointer::lock::SyncLock;
+use crate::shared_pointer::once::SyncOnceCell;
+use crate::shared_pointer::thin;
+use crate::shared_pointer::thin::HeaderSlice;
+use crate::shared_pointer::thin::HeaderSliceUnit;
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::Global;
 use alloc::boxed::Box;
 use alloc::sync::Arc;
+use alloc::sync::Weak;
+use alloc::vec::Vec;
 use core::fmt;
 use core::fmt::Debug;
 use core::fmt::Formatter;
-use core::mem::ManuallyDrop;
+#[cfg(feature = "coerce_unsized")]
+use core::marker::Unsize;
+use core::mem::MaybeUninit;
+#[cfg(feature = "coerce_unsized")]
+use core::ops::DispatchFromDyn;
+use core::ptr::NonNull;
 
 /// [Type constructors](https://en.wikipedia.org/wiki/Type_constructor) for
 /// [`Arc`] pointers.
+#[derive(Default)]
 pub struct ArcK {
-    /// A pointer previously obtained from [`Arc::into_raw()`] for the `T` this instance was
-    /// constructed with, and round-tripped through [`Arc::from_raw()`] on every operation. This
-    /// avoids relying on the layout of `Arc<T>` (which is not part of Rust's stable ABI) to
-    /// perform the type erasure.
-    ///
-    /// The referenced [`Arc`] allocation is kept alive by this pointer (which owns one strong
-    /// reference) until [`SharedPointerKind::drop()`] is called.
-    inner: ErasedPtr,
+    _private: (),
 }
 
-// SAFETY: `ArcK` is a type-erased handle to an `Arc<T>` for some `T`. `Arc<T>` is `Send + Sync`
-// when `T: Send + Sync`. `ArcK` itself is unconditionally `Send + Sync` because it has no
-// knowledge of `T`; the safe wrapper `SharedPointer<T, ArcK>` gates its own `Send`/`Sync` impls
-// on `T: Send + Sync` (see the "Wrapping in a safe API" section on `SharedPointerKind`).
-unsafe impl Send for ArcK {}
-unsafe impl Sync for ArcK {}
+unsafe impl SharedPointerKind for ArcK {
+    type Pointer<T: ?Sized> = Arc<T>;
+
+    #[inline(always)]
+    fn new<T>(v: T) -> Arc<T> {
+        Arc::new(v)
+    }
+
+    #[inline(always)]
+    fn from_box<T>(v: Box<T>) -> Arc<T> {
+        Arc::from(v)
+    }
+
+    #[inline(always)]
+    fn new_uninit<T>() -> Arc<MaybeUninit<T>> {
+        Arc::new_uninit()
+    }
 
-impl ArcK {
     #[inline(always)]
-    fn new_from_inner<T>(arc: Arc<T>) -> ArcK {
-        ArcK { inner: ErasedPtr::new(Arc::into_raw(arc)) }
+    fn from_slice<T: Clone>(v: &[T]) -> Arc<[T]> {
+        Arc::from(v)
     }
 
-    /// Reconstructs a non-owning view of the inner [`Arc<T>`].
-    ///
-    /// The returned [`ManuallyDrop`] must not be unwrapped: dropping the inner [`Arc`] would
-    /// decrement a refcount that this instance still logically owns.
-    ///
-    /// # Safety
-    ///
-    /// `Self` must have been constructed with the same `T`.
     #[inline(always)]
-    unsafe fn as_inner<T>(&self) -> ManuallyDrop<Arc<T>> {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Arc::into_raw::<T>` and points to a live `Arc<T>` allocation. Wrapping the
-        // reconstructed `Arc` in `ManuallyDrop` prevents it from decrementing the strong count
-        // when this local goes out of scope.
-        ManuallyDrop::new(unsafe { Arc::from_raw(self.inner.cast::<T>()) })
+    fn from_vec<T>(v: Vec<T>) -> Arc<[T]> {
+        Arc::from(v)
     }
 
-    /// Takes ownership of the inner [`Arc<T>`], consuming `self`.
-    ///
-    /// # Safety
-    ///
-    /// `Self` must have been constructed with the same `T`.
     #[inline(always)]
-    unsafe fn take_inner<T>(self) -> Arc<T> {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Arc::into_raw::<T>`. `self` is consumed by value and `ArcK` has no `Drop` impl, so
-        // the ownership of the strong reference transfers cleanly to the returned `Arc<T>`.
-        unsafe { Arc::from_raw(self.inner.cast::<T>()) }
+    fn from_str(v: &str) -> Arc<str> {
+        Arc::from(v)
+    }
+
+    #[inline(always)]
+    fn into_raw<T: ?Sized>(ptr: Arc<T>) -> (NonNull<T>, ArcK) {
+        // SAFETY: `Arc::into_raw()` never returns a null pointer.
+        (unsafe { NonNull::new_unchecked(Arc::into_raw(ptr).cast_mut()) }, ArcK::default())
+    }
+
+    #[inline(always)]
+    unsafe fn from_raw<T: ?Sized>(ptr: NonNull<T>, _: ArcK) -> Arc<T> {
+        // SAFETY: By the caller's guarantee, `ptr` was returned by `Arc::into_raw()`, possibly
+        // after a cast that `Arc::from_raw()` accepts, and owns a strong reference.
+        unsafe { Arc::from_raw(ptr.as_ptr()) }
+    }
+
+    #[inline(always)]
+    fn clone<T: ?Sized>(ptr: &Arc<T>) -> Arc<T> {
+        Arc::clone(ptr)
+    }
+
+    #[inline(always)]
+    fn strong_count<T: ?Sized>(ptr: &Arc<T>) -> usize {
+        Arc::strong_count(ptr)
+    }
+
+    #[inline(always)]
+    fn get_mut<T: ?Sized>(ptr: &mut Arc<T>) -> Option<&mut T> {
+        Arc::get_mut(ptr)
+    }
+
+    #[inline(always)]
+    fn make_mut<T: Clone>(ptr: &mut Arc<T>) -> &mut T {
+        Arc::make_mut(ptr)
+    }
+
+    #[inline(always)]
+    fn make_mut_with<T>(ptr: &mut Arc<T>, f: impl FnOnce(&T) -> T) -> &mut T {
+        if Arc::get_mut(ptr).is_none() {
+            *ptr = Arc::new(f(ptr));
+        }
+
+        // SAFETY: `ptr` is either the only reference to its allocation, as checked by
+        // `Arc::get_mut()`, or a new allocation.
+        unsafe { Arc::get_mut(ptr).unwrap_unchecked() }
+    }
+
+    #[inline(always)]
+    fn try_unwrap<T>(ptr: Arc<T>) -> Result<T, Arc<T>> {
+        Arc::try_unwrap(ptr)
     }
 }
 
-unsafe impl SharedPointerKind for ArcK {
+#[cfg(feature = "allocator_api")]
+unsafe impl SharedFallibleKind for ArcK {
+    #[inline(always)]
+    fn try_new<T>(v: T) -> Result<Arc<T>, AllocError> {
+        Ok(Arc::try_new(v)?)
+    }
+
     #[inline(always)]
-    fn new<T>(v: T) -> ArcK {
-        ArcK::new_from_inner(Arc::new(v))
+    fn try_from_box<T>(v: Box<T>) -> Result<Arc<T>, AllocError> {
+        let mut arc: Arc<MaybeUninit<T>> = Arc::try_new_uninit()?;
+
+        // SAFETY: `arc` was just created, so it is the only reference to its value.
+        move_from_box(v, unsafe { Arc::get_mut(&mut arc).unwrap_unchecked() });
+
+        // SAFETY: The value was initialized by `move_from_box()`.
+        Ok(unsafe { arc.assume_init() })
     }
+}
 
+unsafe impl SharedIntoInnerKind for ArcK {
     #[inline(always)]
-    fn from_box<T>(v: Box<T>) -> ArcK {
-        ArcK::new_from_inner::<T>(Arc::from(v))
+    fn into_inner<T>(ptr: Arc<T>) -> Option<T> {
+        Arc::into_inner(ptr)
     }
+}
+
+impl SharedLockKind for ArcK {
+    type Lock<T> = SyncLock<T>;
+}
+
+impl SharedOnceKind for ArcK {
+    type OnceCell<T> = SyncOnceCell<T>;
+}
+
+unsafe impl SharedStatelessKind for ArcK {}
+
+unsafe impl SharedAtomicKind for ArcK {}
+
+unsafe impl SharedWeakKind for ArcK {
+    type Weak<T: ?Sized> = Weak<T>;
 
     #[inline(always)]
-    unsafe fn as_ptr<T>(&self) -> *const T {
-        // SAFETY: The type-parameter invariant is forwarded to `ErasedPtr::cast`.
-        unsafe { self.inner.cast::<T>() }
+    fn new_cyclic<T>(f: impl FnOnce(&Weak<T>) -> T) -> Arc<T> {
+        Arc::new_cyclic(f)
     }
 
     #[inline(always)]
-    unsafe fn deref<T>(&self) -> &T {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Arc::into_raw::<T>`, so it points to a valid `T` inside an allocation that is kept
-        // alive by `self`. The returned reference is tied to the lifetime of `&self`.
-        unsafe { &*self.inner.cast::<T>() }
+    fn downgrade<T: ?Sized>(ptr: &Arc<T>) -> Weak<T> {
+        Arc::downgrade(ptr)
     }
 
     #[inline(always)]
-    unsafe fn try_unwrap<T>(self) -> Result<T, ArcK> {
-        // SAFETY: The type-parameter invariant is forwarded to `take_inner`.
-        let arc: Arc<T> = unsafe { self.take_inner::<T>() };
+    fn weak_count<T: ?Sized>(ptr: &Arc<T>) -> usize {
+        Arc::weak_count(ptr)
+    }
 
-        Arc::try_unwrap(arc).map_err(ArcK::new_from_inner)
+    #[inline(always)]
+    fn upgrade<T: ?Sized>(weak: &Weak<T>) -> Option<Arc<T>> {
+        weak.upgrade()
     }
 
     #[inline(always)]
-    unsafe fn get_mut<T>(&mut self) -> Option<&mut T> {
-        // SAFETY: The type-parameter invariant is forwarded to `map_owned`; `Arc::from_raw` and
-        // `Arc::into_raw` are an inverse pair for `Arc<T>`.
-        let ret: Option<*mut T> = unsafe {
-            self.inner.map_owned::<T, Arc<T>, _>(Arc::from_raw, Arc::as_ptr, |arc| {
-                Arc::get_mut(arc).map(core::ptr::from_mut)
-            })
-        };
+    fn weak_into_raw<T: ?Sized>(weak: Weak<T>) -> (NonNull<T>, ArcK) {
+        // SAFETY: `Weak::into_raw()` never returns a null pointer.
+        (unsafe { NonNull::new_unchecked(Weak::into_raw(weak).cast_mut()) }, ArcK::default())
+    }
 
-        // SAFETY: If `Arc::get_mut` returned `Some`, no other strong or weak reference existed
-        // at the time of the call, so we have exclusive access to the `T`. The allocation is
-        // kept alive for at least as long as `self` because `self.inner` still owns a strong
-        // reference. The returned reference's lifetime is bound to `&mut self`, so no other
-        // access through `self` can occur while it is live.
-        ret.map(|p| unsafe { &mut *p })
+    #[inline(always)]
+    unsafe fn weak_from_raw<T: ?Sized>(ptr: NonNull<T>, _: ArcK) -> Weak<T> {
+        // SAFETY: By the caller's guarantee, `ptr` was returned by `Weak::into_raw()`, possibly
+        // after a cast that `Weak::from_raw()` accepts, and owns a weak reference.
+        unsafe { Weak::from_raw(ptr.as_ptr()) }
     }
 
     #[inline(always)]
-    unsafe fn make_mut<T: Clone>(&mut self) -> &mut T {
-        // SAFETY: The type-parameter invariant is forwarded to `map_owned`; `Arc::from_raw` and
-        // `Arc::into_raw` are an inverse pair for `Arc<T>`.
-        let ret: *mut T = unsafe {
-            self.inner.map_owned::<T, Arc<T>, _>(Arc::from_raw, Arc::as_ptr, |arc| {
-                core::ptr::from_mut(Arc::make_mut(arc))
-            })
-        };
+    fn weak_strong_count<T: ?Sized>(weak: &Weak<T>) -> usize {
+        weak.strong_count()
+    }
 
-        // SAFETY: `Arc::make_mut` guarantees exclusive access to the (possibly freshly cloned)
-        // `T`. The allocation is kept alive by `self.inner`. The returned reference's lifetime
-        // is bound to `&mut self`, so no other access through `self` can occur while it is
-        // live.
-        unsafe { &mut *ret }
+    #[inline(always)]
+    fn weak_weak_count<T: ?Sized>(weak: &Weak<T>) -> usize {
+        weak.weak_count()
     }
 
     #[inline(always)]
-    unsafe fn strong_count<T>(&self) -> usize {
-        // SAFETY: The type-parameter invariant is forwarded to `as_inner`.
-        let arc: ManuallyDrop<Arc<T>> = unsafe { self.as_inner::<T>() };
+    fn weak_clone<T: ?Sized>(weak: &Weak<T>) -> Weak<T> {
+        Weak::clone(weak)
+    }
+}
 
-        Arc::strong_count(&*arc)
+unsafe impl SharedThinKind for ArcK {
+    #[inline(always)]
+    fn from_header_and_iter<H, T, I>(header: H, items: I) -> Arc<HeaderSlice<H, T>>
+    where
+        I: ExactSizeIterator<Item = T>,
+    {
+        let length = items.len();
+        // `Arc` cannot allocate a custom dynamically-sized type, so it allocates a slice of units
+        // with the same layout instead, and the value is written in place.
+        let mut units =
+            Arc::<[HeaderSliceUnit<H, T>]>::new_uninit_slice(thin::unit_count::<H, T>(length));
+        let mem = Arc::get_mut(&mut units).expect("a new `Arc` is unique").as_mut_ptr().cast();
+
+        // SAFETY: `mem` points to the units, which are not shared.
+        unsafe { thin::write_header_and_iter(mem, header, items) };
+
+        // SAFETY: The units hold a valid `HeaderSlice<H, T>`, and `Arc<[HeaderSliceUnit<H, T>]>`
+        // has the same layout as `Arc<HeaderSlice<H, T>>`.
+        unsafe { Arc::from_raw(thin::header_slice_ptr(Arc::into_raw(units).cast(), length)) }
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+unsafe impl SharedAllocatorKind for ArcK {
+    type Allocator = Global;
+
+    #[inline(always)]
+    fn new_in<T>(v: T, _alloc: Global) -> Arc<T> {
+        Arc::new(v)
     }
 
     #[inline(always)]
-    unsafe fn clone<T>(&self) -> ArcK {
-        // SAFETY: The type-parameter invariant is forwarded to `as_inner`.
-        let arc: ManuallyDrop<Arc<T>> = unsafe { self.as_inner::<T>() };
+    fn try_new_in<T>(v: T, _alloc: Global) -> Result<Arc<T>, AllocError> {
+        ArcK::try_new(v)
+    }
 
-        ArcK::new_from_inner(Arc::clone(&*arc))
+    #[inline(always)]
+    fn from_box_in<T: ?Sized>(v: Box<T>) -> Arc<T> {
+        Arc::from(v)
     }
 
     #[inline(always)]
-    unsafe fn drop<T>(&mut self) {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Arc::into_raw::<T>`. Reconstructing the `Arc<T>` and letting it drop decrements the
-        // strong count matching the initial `Arc::into_raw`. The caller guarantees this is the
-        // last use of `self`.
-        drop(unsafe { Arc::from_raw(self.inner.cast::<T>()) });
+    fn allocator(&self) -> &Global {
+        &Global
     }
 }
 
+impl<T: ?Sized> From<Arc<T>> for SharedPointer<T, ArcK> {
+    #[inline(always)]
+    fn from(v: Arc<T>) -> SharedPointer<T, ArcK> {
+        SharedPointer::from_inner_pointer(v)
+    }
+}
+
+impl<T: ?Sized> From<SharedPointer<T, ArcK>> for Arc<T> {
+    #[inline(always)]
+    fn from(v: SharedPointer<T, ArcK>) -> Arc<T> {
+        SharedPointer::into_inner_pointer(v)
+    }
+}
+
+#[cfg(feature = "coerce_unsized")]
+impl<T, U> DispatchFromDyn<SharedPointer<U, ArcK>> for SharedPointer<T, ArcK>
+where
+    T: Unsize<U> + ?Sized,
+    U: ?Sized,
+{
+}
+
 impl Debug for ArcK {
     #[inline(always)]
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
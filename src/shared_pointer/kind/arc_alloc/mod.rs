@@ -0,0 +1,226 @@
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::AllocError;
+use crate::shared_pointer::kind::SharedAllocatorKind;
+use crate::shared_pointer::kind::SharedFallibleKind;
+use crate::shared_pointer::kind::SharedIntoInnerKind;
+use crate::shared_pointer::kind::SharedLockKind;
+use crate::shared_pointer::kind::SharedOnceKind;
+use crate::shared_pointer::kind::SharedPointerKind;
+use crate::shared_pointer::kind::move_from_box;
+use crate::shared_pointer::lock::SyncLock;
+use crate::shared_pointer::once::SyncOnceCell;
+use alloc::alloc::Global;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::alloc::Allocator;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+/// [Type constructors](https://en.wikipedia.org/wiki/Type_constructor) for
+/// [`Arc`] pointers whose memory is allocated by the allocator `A`.
+///
+/// Constructors that do not take an allocator, such as
+/// [`SharedPointer::new()`](crate::shared_pointer::SharedPointer::new), use
+/// [`A::default()`](Default::default). Use
+/// [`SharedPointer::new_in()`](crate::shared_pointer::SharedPointer::new_in) to allocate with a
+/// specific allocator.
+#[derive(Default)]
+pub struct ArcAllocK<A = Global> {
+    /// The allocator of the [`Arc`] allocation.
+    alloc: A,
+}
+
+impl<A: Allocator> ArcAllocK<A> {
+    #[inline(always)]
+    fn new_from_alloc(alloc: A) -> ArcAllocK<A> {
+        ArcAllocK { alloc }
+    }
+}
+
+unsafe impl<A: Allocator + Clone + Default> SharedPointerKind for ArcAllocK<A> {
+    type Pointer<T: ?Sized> = Arc<T, A>;
+
+    #[inline(always)]
+    fn new<T>(v: T) -> Arc<T, A> {
+        Arc::new_in(v, A::default())
+    }
+
+    #[inline(always)]
+    fn from_box<T>(v: Box<T>) -> Arc<T, A> {
+        ArcAllocK::new(*v)
+    }
+
+    #[inline(always)]
+    fn new_uninit<T>() -> Arc<MaybeUninit<T>, A> {
+        Arc::new_uninit_in(A::default())
+    }
+
+    #[inline(always)]
+    fn from_slice<T: Clone>(v: &[T]) -> Arc<[T], A> {
+        let mut vec: Vec<T, A> = Vec::with_capacity_in(v.len(), A::default());
+
+        vec.extend_from_slice(v);
+
+        Arc::from(vec)
+    }
+
+    #[inline(always)]
+    fn from_vec<T>(v: Vec<T>) -> Arc<[T], A> {
+        let mut vec: Vec<T, A> = Vec::with_capacity_in(v.len(), A::default());
+
+        vec.extend(v);
+
+        Arc::from(vec)
+    }
+
+    #[inline(always)]
+    fn from_str(v: &str) -> Arc<str, A> {
+        let mut vec: Vec<u8, A> = Vec::with_capacity_in(v.len(), A::default());
+
+        vec.extend_from_slice(v.as_bytes());
+
+        let (ptr, alloc) = Arc::into_raw_with_allocator(Arc::<[u8], A>::from(vec));
+
+        // SAFETY: `str` has the same layout as `[u8]`, and the bytes were copied from a `str`,
+        // so they are valid UTF-8.
+        unsafe { Arc::from_raw_in(ptr as *const str, alloc) }
+    }
+
+    #[inline(always)]
+    fn into_raw<T: ?Sized>(ptr: Arc<T, A>) -> (NonNull<T>, ArcAllocK<A>) {
+        let (ptr, alloc) = Arc::into_raw_with_allocator(ptr);
+
+        // SAFETY: `Arc::into_raw_with_allocator()` never returns a null pointer.
+        (unsafe { NonNull::new_unchecked(ptr.cast_mut()) }, ArcAllocK::new_from_alloc(alloc))
+    }
+
+    #[inline(always)]
+    unsafe fn from_raw<T: ?Sized>(ptr: NonNull<T>, kind: ArcAllocK<A>) -> Arc<T, A> {
+        // SAFETY: By the caller's guarantee, `ptr` was returned by
+        // `Arc::into_raw_with_allocator()`, possibly after a cast that `Arc::from_raw_in()`
+        // accepts, and owns a strong reference. `kind` was returned with it, so `kind.alloc` is
+        // the allocator that owns its memory.
+        unsafe { Arc::from_raw_in(ptr.as_ptr(), kind.alloc) }
+    }
+
+    #[inline(always)]
+    fn clone<T: ?Sized>(ptr: &Arc<T, A>) -> Arc<T, A> {
+        Arc::clone(ptr)
+    }
+
+    #[inline(always)]
+    fn strong_count<T: ?Sized>(ptr: &Arc<T, A>) -> usize {
+        Arc::strong_count(ptr)
+    }
+
+    #[inline(always)]
+    fn get_mut<T: ?Sized>(ptr: &mut Arc<T, A>) -> Option<&mut T> {
+        Arc::get_mut(ptr)
+    }
+
+    #[inline(always)]
+    fn make_mut<T: Clone>(ptr: &mut Arc<T, A>) -> &mut T {
+        Arc::make_mut(ptr)
+    }
+
+    #[inline(always)]
+    fn make_mut_with<T>(ptr: &mut Arc<T, A>, f: impl FnOnce(&T) -> T) -> &mut T {
+        if Arc::get_mut(ptr).is_none() {
+            *ptr = Arc::new_in(f(ptr), Arc::allocator(ptr).clone());
+        }
+
+        // SAFETY: `ptr` is either the only reference to its allocation, as checked by
+        // `Arc::get_mut()`, or a new allocation.
+        unsafe { Arc::get_mut(ptr).unwrap_unchecked() }
+    }
+
+    #[inline(always)]
+    fn try_unwrap<T>(ptr: Arc<T, A>) -> Result<T, Arc<T, A>> {
+        Arc::try_unwrap(ptr)
+    }
+}
+
+unsafe impl<A: Allocator + Clone + Default> SharedFallibleKind for ArcAllocK<A> {
+    #[inline(always)]
+    fn try_new<T>(v: T) -> Result<Arc<T, A>, AllocError> {
+        ArcAllocK::try_new_in(v, A::default())
+    }
+
+    #[inline(always)]
+    fn try_from_box<T>(v: Box<T>) -> Result<Arc<T, A>, AllocError> {
+        let mut arc: Arc<MaybeUninit<T>, A> = Arc::try_new_uninit_in(A::default())?;
+
+        // SAFETY: `arc` was just created, so it is the only reference to its value.
+        move_from_box(v, unsafe { Arc::get_mut(&mut arc).unwrap_unchecked() });
+
+        // SAFETY: The value was initialized by `move_from_box()`.
+        Ok(unsafe { arc.assume_init() })
+    }
+}
+
+unsafe impl<A: Allocator + Clone + Default> SharedIntoInnerKind for ArcAllocK<A> {
+    #[inline(always)]
+    fn into_inner<T>(ptr: Arc<T, A>) -> Option<T> {
+        Arc::into_inner(ptr)
+    }
+}
+
+unsafe impl<A: Allocator + Clone + Default> SharedAllocatorKind for ArcAllocK<A> {
+    type Allocator = A;
+
+    #[inline(always)]
+    fn new_in<T>(v: T, alloc: A) -> Arc<T, A> {
+        Arc::new_in(v, alloc)
+    }
+
+    #[inline(always)]
+    fn try_new_in<T>(v: T, alloc: A) -> Result<Arc<T, A>, AllocError> {
+        Ok(Arc::try_new_in(v, alloc)?)
+    }
+
+    #[inline(always)]
+    fn from_box_in<T: ?Sized>(v: Box<T, A>) -> Arc<T, A> {
+        Arc::from(v)
+    }
+
+    #[inline(always)]
+    fn allocator(&self) -> &A {
+        &self.alloc
+    }
+}
+
+impl<A: Allocator + Clone + Default> SharedLockKind for ArcAllocK<A> {
+    type Lock<T> = SyncLock<T>;
+}
+
+impl<A: Allocator + Clone + Default> SharedOnceKind for ArcAllocK<A> {
+    type OnceCell<T> = SyncOnceCell<T>;
+}
+
+impl<T: ?Sized, A: Allocator + Clone + Default> From<Arc<T, A>> for SharedPointer<T, ArcAllocK<A>> {
+    #[inline(always)]
+    fn from(v: Arc<T, A>) -> SharedPointer<T, ArcAllocK<A>> {
+        SharedPointer::from_inner_pointer(v)
+    }
+}
+
+impl<T: ?Sized, A: Allocator + Clone + Default> From<SharedPointer<T, ArcAllocK<A>>> for Arc<T, A> {
+    #[inline(always)]
+    fn from(v: SharedPointer<T, ArcAllocK<A>>) -> Arc<T, A> {
+        SharedPointer::into_inner_pointer(v)
+    }
+}
+
+impl<A> Debug for ArcAllocK<A> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("ArcAllocK")
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -1,161 +1,239 @@
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::AllocError;
+use crate::shared_pointer::kind::SharedAtomicKind;
+use crate::shared_pointer::kind::SharedFallibleKind;
+use crate::shared_pointer::kind::SharedIntoInnerKind;
+use crate::shared_pointer::kind::SharedLockKind;
+use crate::shared_pointer::kind::SharedOnceKind;
 use crate::shared_pointer::kind::SharedPointerKind;
-use crate::shared_pointer::kind::erased_ptr::ErasedPtr;
+use crate::shared_pointer::kind::SharedStatelessKind;
+use crate::shared_pointer::kind::SharedThinKind;
+use crate::shared_pointer::kind::move_from_box;
+use crate::shared_pointer::lock::SyncLock;
+use crate::shared_pointer::once::SyncOnceCell;
+use crate::shared_pointer::thin::HeaderSlice;
+use crate::shared_pointer::thin::ThinSharedPointer;
+use crate::shared_pointer::unique::UniqueSharedPointer;
 use alloc::boxed::Box;
+use alloc::vec::Vec;
 use core::fmt;
 use core::fmt::Debug;
 use core::fmt::Formatter;
-use core::mem::ManuallyDrop;
+#[cfg(feature = "coerce_unsized")]
+use core::marker::Unsize;
+use core::mem::MaybeUninit;
+#[cfg(feature = "coerce_unsized")]
+use core::ops::DispatchFromDyn;
+use core::ptr::NonNull;
 use triomphe::Arc;
+use triomphe::HeaderWithLength;
+use triomphe::ThinArc;
+use triomphe::UniqueArc;
 
 /// [Type constructors](https://en.wikipedia.org/wiki/Type_constructor) for
 /// [`triomphe::Arc`](triomphe::Arc) pointers.
+#[derive(Default)]
 pub struct ArcTK {
-    /// A pointer previously obtained from [`Arc::into_raw()`] for the `T` this instance was
-    /// constructed with, and round-tripped through [`Arc::from_raw()`] on every operation.
-    /// This avoids relying on the layout of `Arc<T>` (which is not part of Rust's stable ABI)
-    /// to perform the type erasure.
-    ///
-    /// The referenced [`Arc`] allocation is kept alive by this pointer (which owns one strong
-    /// reference) until [`SharedPointerKind::drop()`] is called.
-    inner: ErasedPtr,
+    _private: (),
 }
 
-// SAFETY: `ArcTK` is a type-erased handle to a `triomphe::Arc<T>` for some `T`.
-// `triomphe::Arc<T>` is `Send + Sync` when `T: Send + Sync`. `ArcTK` itself is unconditionally
-// `Send + Sync` because it has no knowledge of `T`; the safe wrapper `SharedPointer<T, ArcTK>`
-// gates its own `Send`/`Sync` impls on `T: Send + Sync` (see the "Wrapping in a safe API"
-// section on `SharedPointerKind`).
-unsafe impl Send for ArcTK {}
-unsafe impl Sync for ArcTK {}
-
-impl ArcTK {
-    #[inline(always)]
-    fn new_from_inner<T>(arc: Arc<T>) -> ArcTK {
-        ArcTK { inner: ErasedPtr::new(Arc::into_raw(arc)) }
-    }
-
-    /// Reconstructs a non-owning view of the inner [`Arc<T>`].
-    ///
-    /// The returned [`ManuallyDrop`] must not be unwrapped: dropping the inner [`Arc`] would
-    /// decrement a refcount that this instance still logically owns.
-    ///
-    /// # Safety
-    ///
-    /// `Self` must have been constructed with the same `T`.
-    #[inline(always)]
-    unsafe fn as_inner<T>(&self) -> ManuallyDrop<Arc<T>> {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Arc::into_raw::<T>` and points to a live `Arc<T>` allocation. Wrapping the
-        // reconstructed `Arc` in `ManuallyDrop` prevents it from decrementing the strong count
-        // when this local goes out of scope.
-        ManuallyDrop::new(unsafe { Arc::from_raw(self.inner.cast::<T>()) })
-    }
-
-    /// Takes ownership of the inner [`Arc<T>`], consuming `self`.
-    ///
-    /// # Safety
-    ///
-    /// `Self` must have been constructed with the same `T`.
-    #[inline(always)]
-    unsafe fn take_inner<T>(self) -> Arc<T> {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Arc::into_raw::<T>`. `self` is consumed by value and `ArcTK` has no `Drop` impl, so
-        // the ownership of the strong reference transfers cleanly to the returned `Arc<T>`.
-        unsafe { Arc::from_raw(self.inner.cast::<T>()) }
+unsafe impl SharedPointerKind for ArcTK {
+    type Pointer<T: ?Sized> = Arc<T>;
+
+    #[inline(always)]
+    fn new<T>(v: T) -> Arc<T> {
+        Arc::new(v)
     }
-}
 
-unsafe impl SharedPointerKind for ArcTK {
     #[inline(always)]
-    fn new<T>(v: T) -> ArcTK {
-        ArcTK::new_from_inner(Arc::new(v))
+    fn from_box<T>(v: Box<T>) -> Arc<T> {
+        Arc::from(v)
     }
 
     #[inline(always)]
-    fn from_box<T>(v: Box<T>) -> ArcTK {
-        ArcTK::new_from_inner::<T>(Arc::from(v))
+    fn new_uninit<T>() -> Arc<MaybeUninit<T>> {
+        Arc::new_uninit()
     }
 
     #[inline(always)]
-    unsafe fn as_ptr<T>(&self) -> *const T {
-        // SAFETY: The type-parameter invariant is forwarded to `ErasedPtr::cast`.
-        unsafe { self.inner.cast::<T>() }
+    fn from_slice<T: Clone>(v: &[T]) -> Arc<[T]> {
+        v.iter().cloned().collect::<Arc<[T]>>()
     }
 
     #[inline(always)]
-    unsafe fn deref<T>(&self) -> &T {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Arc::into_raw::<T>`, so it points to a valid `T` inside an allocation that is kept
-        // alive by `self`. The returned reference is tied to the lifetime of `&self`.
-        unsafe { &*self.inner.cast::<T>() }
+    fn from_vec<T>(v: Vec<T>) -> Arc<[T]> {
+        Arc::from(v)
     }
 
     #[inline(always)]
-    unsafe fn try_unwrap<T>(self) -> Result<T, ArcTK> {
-        // SAFETY: The type-parameter invariant is forwarded to `take_inner`.
-        let arc: Arc<T> = unsafe { self.take_inner::<T>() };
+    fn from_str(v: &str) -> Arc<str> {
+        Arc::from(v)
+    }
 
-        Arc::try_unwrap(arc).map_err(ArcTK::new_from_inner)
+    #[inline(always)]
+    fn into_raw<T: ?Sized>(ptr: Arc<T>) -> (NonNull<T>, ArcTK) {
+        // SAFETY: `Arc::into_raw()` never returns a null pointer.
+        (unsafe { NonNull::new_unchecked(Arc::into_raw(ptr).cast_mut()) }, ArcTK::default())
     }
 
     #[inline(always)]
-    unsafe fn get_mut<T>(&mut self) -> Option<&mut T> {
-        // SAFETY: The type-parameter invariant is forwarded to `map_owned`; `Arc::from_raw` and
-        // `Arc::into_raw` are an inverse pair for `Arc<T>`.
-        let ret: Option<*mut T> = unsafe {
-            self.inner.map_owned::<T, Arc<T>, _>(Arc::from_raw, Arc::as_ptr, |arc| {
-                Arc::get_mut(arc).map(core::ptr::from_mut)
-            })
-        };
+    unsafe fn from_raw<T: ?Sized>(ptr: NonNull<T>, _: ArcTK) -> Arc<T> {
+        // SAFETY: By the caller's guarantee, `ptr` was returned by `Arc::into_raw()`, possibly
+        // after a cast that `Arc::from_raw()` accepts, and owns a strong reference.
+        unsafe { Arc::from_raw(ptr.as_ptr()) }
+    }
 
-        // SAFETY: If `Arc::get_mut` returned `Some`, no other strong or weak reference existed
-        // at the time of the call, so we have exclusive access to the `T`. The allocation is
-        // kept alive for at least as long as `self` because `self.inner` still owns a strong
-        // reference. The returned reference's lifetime is bound to `&mut self`, so no other
-        // access through `self` can occur while it is live.
-        ret.map(|p| unsafe { &mut *p })
+    #[inline(always)]
+    fn clone<T: ?Sized>(ptr: &Arc<T>) -> Arc<T> {
+        Arc::clone(ptr)
     }
 
     #[inline(always)]
-    unsafe fn make_mut<T: Clone>(&mut self) -> &mut T {
-        // SAFETY: The type-parameter invariant is forwarded to `map_owned`; `Arc::from_raw` and
-        // `Arc::into_raw` are an inverse pair for `Arc<T>`.
-        let ret: *mut T = unsafe {
-            self.inner.map_owned::<T, Arc<T>, _>(Arc::from_raw, Arc::as_ptr, |arc| {
-                core::ptr::from_mut(Arc::make_mut(arc))
-            })
-        };
+    fn strong_count<T: ?Sized>(ptr: &Arc<T>) -> usize {
+        Arc::count(ptr)
+    }
 
-        // SAFETY: `Arc::make_mut` guarantees exclusive access to the (possibly freshly cloned)
-        // `T`. The allocation is kept alive by `self.inner`. The returned reference's lifetime
-        // is bound to `&mut self`, so no other access through `self` can occur while it is
-        // live.
-        unsafe { &mut *ret }
+    #[inline(always)]
+    fn get_mut<T: ?Sized>(ptr: &mut Arc<T>) -> Option<&mut T> {
+        Arc::get_mut(ptr)
     }
 
     #[inline(always)]
-    unsafe fn strong_count<T>(&self) -> usize {
-        // SAFETY: The type-parameter invariant is forwarded to `as_inner`.
-        let arc: ManuallyDrop<Arc<T>> = unsafe { self.as_inner::<T>() };
+    fn make_mut<T: Clone>(ptr: &mut Arc<T>) -> &mut T {
+        Arc::make_mut(ptr)
+    }
 
-        Arc::count(&*arc)
+    #[inline(always)]
+    fn make_mut_with<T>(ptr: &mut Arc<T>, f: impl FnOnce(&T) -> T) -> &mut T {
+        if Arc::get_mut(ptr).is_none() {
+            *ptr = Arc::new(f(ptr));
+        }
+
+        // SAFETY: `ptr` is either the only reference to its allocation, as checked by
+        // `Arc::get_mut()`, or a new allocation.
+        unsafe { Arc::get_mut(ptr).unwrap_unchecked() }
     }
 
     #[inline(always)]
-    unsafe fn clone<T>(&self) -> ArcTK {
-        // SAFETY: The type-parameter invariant is forwarded to `as_inner`.
-        let arc: ManuallyDrop<Arc<T>> = unsafe { self.as_inner::<T>() };
+    fn try_unwrap<T>(ptr: Arc<T>) -> Result<T, Arc<T>> {
+        Arc::try_unwrap(ptr)
+    }
+}
 
-        ArcTK::new_from_inner(Arc::clone(&*arc))
+unsafe impl SharedFallibleKind for ArcTK {
+    #[inline(always)]
+    fn try_new<T>(v: T) -> Result<Arc<T>, AllocError> {
+        Ok(Arc::try_new(v)?)
     }
 
     #[inline(always)]
-    unsafe fn drop<T>(&mut self) {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Arc::into_raw::<T>`. Reconstructing the `Arc<T>` and letting it drop decrements the
-        // strong count matching the initial `Arc::into_raw`. The caller guarantees this is the
-        // last use of `self`.
-        drop(unsafe { Arc::from_raw(self.inner.cast::<T>()) });
+    fn try_from_box<T>(v: Box<T>) -> Result<Arc<T>, AllocError> {
+        let mut arc: UniqueArc<MaybeUninit<T>> = UniqueArc::try_new_uninit()?;
+
+        move_from_box(v, &mut arc);
+
+        // SAFETY: The value was initialized by `move_from_box()`.
+        Ok(UniqueArc::shareable(unsafe { UniqueArc::assume_init(arc) }))
+    }
+}
+
+unsafe impl SharedIntoInnerKind for ArcTK {
+    #[inline(always)]
+    fn into_inner<T>(ptr: Arc<T>) -> Option<T> {
+        Arc::into_unique(ptr).map(UniqueArc::into_inner)
+    }
+}
+
+impl SharedLockKind for ArcTK {
+    type Lock<T> = SyncLock<T>;
+}
+
+impl SharedOnceKind for ArcTK {
+    type OnceCell<T> = SyncOnceCell<T>;
+}
+
+unsafe impl SharedStatelessKind for ArcTK {}
+
+unsafe impl SharedAtomicKind for ArcTK {}
+
+unsafe impl SharedThinKind for ArcTK {
+    #[inline(always)]
+    fn from_header_and_iter<H, T, I>(header: H, items: I) -> Arc<HeaderSlice<H, T>>
+    where
+        I: ExactSizeIterator<Item = T>,
+    {
+        let header = HeaderWithLength::new(header, items.len());
+        let arc = Arc::from_header_and_iter(header, items);
+
+        // SAFETY: `HeaderSlice<H, T>` has the same layout as `triomphe::HeaderSlice<
+        // HeaderWithLength<H>, [T]>`, so the allocation can be used as an
+        // `Arc<HeaderSlice<H, T>>`.
+        unsafe { Arc::from_raw(Arc::into_raw(arc) as *const HeaderSlice<H, T>) }
+    }
+}
+
+impl<T: ?Sized> From<Arc<T>> for SharedPointer<T, ArcTK> {
+    #[inline(always)]
+    fn from(v: Arc<T>) -> SharedPointer<T, ArcTK> {
+        SharedPointer::from_inner_pointer(v)
+    }
+}
+
+impl<T: ?Sized> From<SharedPointer<T, ArcTK>> for Arc<T> {
+    #[inline(always)]
+    fn from(v: SharedPointer<T, ArcTK>) -> Arc<T> {
+        SharedPointer::into_inner_pointer(v)
+    }
+}
+
+#[cfg(feature = "coerce_unsized")]
+impl<T, U> DispatchFromDyn<SharedPointer<U, ArcTK>> for SharedPointer<T, ArcTK>
+where
+    T: Unsize<U> + ?Sized,
+    U: ?Sized,
+{
+}
+
+impl<T: ?Sized> From<UniqueArc<T>> for UniqueSharedPointer<T, ArcTK> {
+    #[inline(always)]
+    fn from(v: UniqueArc<T>) -> UniqueSharedPointer<T, ArcTK> {
+        // SAFETY: A `UniqueArc` is the only reference to its value.
+        unsafe { UniqueSharedPointer::new_from_inner(UniqueArc::shareable(v)) }
+    }
+}
+
+impl<T: ?Sized> From<UniqueSharedPointer<T, ArcTK>> for UniqueArc<T> {
+    #[inline(always)]
+    fn from(v: UniqueSharedPointer<T, ArcTK>) -> UniqueArc<T> {
+        let arc: Arc<T> = SharedPointer::into_inner_pointer(UniqueSharedPointer::into_shared(v));
+
+        match UniqueArc::try_from(arc) {
+            Ok(unique) => unique,
+            Err(_) => unreachable!("a `UniqueSharedPointer` is the only reference to its value"),
+        }
+    }
+}
+
+impl<H, T> From<ThinArc<H, T>> for ThinSharedPointer<H, T, ArcTK> {
+    #[inline(always)]
+    fn from(v: ThinArc<H, T>) -> ThinSharedPointer<H, T, ArcTK> {
+        let raw = Arc::into_raw(Arc::from_thin(v)) as *const HeaderSlice<H, T>;
+
+        // SAFETY: `HeaderSlice<H, T>` has the same layout as `triomphe::HeaderSlice<
+        // HeaderWithLength<H>, [T]>`, and the strong reference of `raw` is given away.
+        ThinSharedPointer::from(unsafe { SharedPointer::<_, ArcTK>::from_raw(raw) })
+    }
+}
+
+impl<H, T> From<ThinSharedPointer<H, T, ArcTK>> for ThinArc<H, T> {
+    #[inline(always)]
+    fn from(v: ThinSharedPointer<H, T, ArcTK>) -> ThinArc<H, T> {
+        let raw = SharedPointer::into_raw(ThinSharedPointer::into_shared(v))
+            as *const triomphe::HeaderSlice<HeaderWithLength<H>, [T]>;
+
+        // SAFETY: `HeaderSlice<H, T>` has the same layout as `triomphe::HeaderSlice<
+        // HeaderWithLength<H>, [T]>`, and its length is the one of the slice.
+        Arc::into_thin(unsafe { Arc::from_raw(raw) })
     }
 }
 
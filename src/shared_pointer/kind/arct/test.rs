@@ -8,183 +8,190 @@ type PointerKind = ArcTK;
 
 assert_impl_all!(ArcTK: Send, Sync);
 
-#[test]
-fn test_from_box_t() {
-    let mut ptr = PointerKind::from_box(Box::new(42));
-
-    unsafe {
-        assert_eq!(ptr.deref::<i32>(), &42);
+mod static_check_arct_does_not_support_weak {
+    use crate::shared_pointer::kind::{ArcTK, SharedWeakKind};
+    use static_assertions::*;
 
-        ptr.drop::<i32>();
-    }
+    assert_not_impl_any!(ArcTK: SharedWeakKind);
 }
 
 #[test]
-fn test_as_ptr() {
-    let mut x = PointerKind::new::<&'static str>("hello");
+fn test_from_box_t() {
+    let ptr = PointerKind::from_box(Box::new(42));
 
-    unsafe {
-        let mut y = PointerKind::clone::<&'static str>(&x);
-        let x_ptr: *const &'static str = PointerKind::as_ptr(&x);
+    assert_eq!(*ptr, 42);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
 
-        assert_eq!(x_ptr, PointerKind::as_ptr(&y));
-        assert_eq!(*x_ptr, "hello");
+#[test]
+fn test_try_new() {
+    let ptr = PointerKind::try_new::<i32>(42).unwrap();
 
-        x.drop::<&'static str>();
-        y.drop::<&'static str>();
-    }
+    assert_eq!(*ptr, 42);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
 }
 
 #[test]
-fn test_deref() {
-    let mut ptr_42 = PointerKind::new::<i32>(42);
-    let mut ptr_box_dyn_hello = PointerKind::new::<Box<dyn ToString>>(Box::new("hello"));
+fn test_try_from_box() {
+    let ptr = PointerKind::try_from_box::<i32>(Box::new(42)).unwrap();
 
-    unsafe {
-        assert_eq!(ptr_42.deref::<i32>(), &42);
-        assert_eq!(ptr_box_dyn_hello.deref::<Box<dyn ToString>>().to_string(), "hello");
-
-        ptr_42.drop::<i32>();
-        ptr_box_dyn_hello.drop::<Box<dyn ToString>>();
-    }
+    assert_eq!(*ptr, 42);
 }
 
 #[test]
-fn test_try_unwrap() {
-    let ptr = PointerKind::new::<i32>(42);
+fn test_new_uninit_assume_init() {
+    let mut ptr = PointerKind::new_uninit::<i32>();
 
-    unsafe {
-        assert_eq!(ptr.try_unwrap::<i32>().unwrap(), 42);
-    }
-
-    let ptr = PointerKind::new::<i32>(42);
+    PointerKind::get_mut(&mut ptr).unwrap().write(42);
 
-    unsafe {
-        let ptr_clone = ptr.clone::<i32>();
+    // SAFETY: The value was just initialized.
+    let ptr = unsafe { ptr.assume_init() };
 
-        let mut ptr_clone = ptr_clone.try_unwrap::<i32>().unwrap_err();
-        let mut ptr = ptr.try_unwrap::<i32>().unwrap_err();
+    assert_eq!(*ptr, 42);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
 
-        assert_eq!(ptr.deref::<i32>(), &42);
-        assert_eq!(ptr_clone.deref::<i32>(), &42);
+#[test]
+fn test_from_slice() {
+    let ptr = PointerKind::from_slice::<i32>(&[1, 2, 3]);
 
-        ptr.drop::<i32>();
-        ptr_clone.drop::<i32>();
-    }
+    assert_eq!(*ptr, [1, 2, 3]);
 }
 
 #[test]
-fn test_get_mut() {
-    let mut ptr = PointerKind::new::<i32>(42);
+fn test_from_vec() {
+    let ptr = PointerKind::from_vec::<i32>(vec![1, 2, 3]);
 
-    unsafe {
-        assert_eq!(ptr.deref::<i32>(), &42);
+    assert_eq!(*ptr, [1, 2, 3]);
+}
 
-        *ptr.get_mut::<i32>().unwrap() += 1;
+#[test]
+fn test_from_str() {
+    let ptr = PointerKind::from_str("hello from test_from_str");
 
-        assert_eq!(ptr.deref::<i32>(), &43);
+    assert_eq!(&*ptr, "hello from test_from_str");
+}
 
-        let mut ptr_clone = ptr.clone::<i32>();
+#[test]
+fn test_unsized() {
+    let mut ptr = PointerKind::from_vec::<i32>(vec![1, 2, 3]);
 
-        assert_eq!(ptr.get_mut::<i32>(), None);
-        assert_eq!(ptr_clone.get_mut::<i32>(), None);
+    PointerKind::get_mut(&mut ptr).unwrap()[0] = 0;
 
-        ptr.drop::<i32>();
+    let ptr_clone = PointerKind::clone(&ptr);
 
-        *ptr_clone.get_mut::<i32>().unwrap() += 1;
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+    assert_eq!(PointerKind::get_mut(&mut ptr), None);
+    assert_eq!(NonNull::from(&*ptr), NonNull::from(&*ptr_clone));
+    assert_eq!(*ptr_clone, [0, 2, 3]);
 
-        assert_eq!(ptr_clone.deref::<i32>(), &44);
+    drop(ptr);
 
-        ptr_clone.drop::<i32>();
-    }
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
 }
 
 #[test]
-fn test_make_mut() {
-    let mut ptr = PointerKind::new::<i32>(42);
-
-    unsafe {
-        assert_eq!(ptr.deref::<i32>(), &42);
+fn test_into_raw_from_raw() {
+    let ptr = PointerKind::from_str("hello from test_into_raw_from_raw");
+    let (raw, kind) = PointerKind::into_raw(ptr);
 
-        *ptr.make_mut::<i32>() += 1;
+    // SAFETY: `raw` points to a live value owned by the strong reference we just released.
+    assert_eq!(unsafe { raw.as_ref() }, "hello from test_into_raw_from_raw");
 
-        assert_eq!(ptr.deref::<i32>(), &43);
+    // SAFETY: `raw` and `kind` were returned by `into_raw()`.
+    let ptr = unsafe { PointerKind::from_raw(raw, kind) };
 
-        // Clone to force make_mut to clone the data.
-        let mut ptr_clone = ptr.clone::<i32>();
+    assert_eq!(NonNull::from(&*ptr), raw);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
 
-        assert_eq!(ptr_clone.deref::<i32>(), &43);
+#[test]
+fn test_into_raw_from_raw_unsize() {
+    let ptr = PointerKind::new::<i32>(42);
+    let (raw, kind) = PointerKind::into_raw(ptr);
+    let raw: NonNull<dyn ToString> = raw;
 
-        *ptr_clone.make_mut::<i32>() += 1;
+    // SAFETY: `raw` was returned by `into_raw()` and then unsized.
+    let ptr = unsafe { PointerKind::from_raw(raw, kind) };
 
-        assert_eq!(ptr.deref::<i32>(), &43);
-        assert_eq!(ptr_clone.deref::<i32>(), &44);
+    assert_eq!(ptr.to_string(), "42");
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
 
-        *ptr.make_mut::<i32>() *= 2;
+#[test]
+fn test_try_unwrap() {
+    let ptr = PointerKind::new::<i32>(42);
 
-        assert_eq!(ptr.deref::<i32>(), &(2 * 43));
-        assert_eq!(ptr_clone.deref::<i32>(), &44);
+    assert_eq!(PointerKind::try_unwrap(ptr).unwrap(), 42);
 
-        ptr.drop::<i32>();
+    let ptr = PointerKind::new::<i32>(42);
+    let ptr_clone = PointerKind::clone(&ptr);
 
-        assert_eq!(ptr_clone.deref::<i32>(), &44);
+    let ptr_clone = PointerKind::try_unwrap(ptr_clone).unwrap_err();
+    let ptr = PointerKind::try_unwrap(ptr).unwrap_err();
 
-        ptr_clone.drop::<i32>();
-    }
+    assert_eq!(*ptr, 42);
+    assert_eq!(*ptr_clone, 42);
 }
 
 #[test]
-fn test_strong_count() {
+fn test_get_mut() {
     let mut ptr = PointerKind::new::<i32>(42);
 
-    unsafe {
-        assert_eq!(ptr.strong_count::<i32>(), 1);
+    *PointerKind::get_mut(&mut ptr).unwrap() += 1;
 
-        let mut ptr_clone = ptr.clone::<i32>();
+    assert_eq!(*ptr, 43);
 
-        assert_eq!(ptr.strong_count::<i32>(), 2);
-        assert_eq!(ptr_clone.strong_count::<i32>(), 2);
+    let mut ptr_clone = PointerKind::clone(&ptr);
 
-        ptr.drop::<i32>();
+    assert_eq!(PointerKind::get_mut(&mut ptr), None);
+    assert_eq!(PointerKind::get_mut(&mut ptr_clone), None);
 
-        assert_eq!(ptr_clone.strong_count::<i32>(), 1);
+    drop(ptr);
 
-        ptr_clone.drop::<i32>();
-    }
+    *PointerKind::get_mut(&mut ptr_clone).unwrap() += 1;
+
+    assert_eq!(*ptr_clone, 44);
 }
 
 #[test]
-fn test_clone() {
-    let mut ptr = PointerKind::new::<Cell<i32>>(Cell::new(42));
+fn test_make_mut() {
+    let mut ptr = PointerKind::new::<i32>(42);
 
-    unsafe {
-        let mut ptr_clone = ptr.clone::<Cell<i32>>();
+    *PointerKind::make_mut(&mut ptr) += 1;
 
-        assert_eq!(ptr.deref::<Cell<i32>>().get(), 42);
-        assert_eq!(ptr_clone.deref::<Cell<i32>>().get(), 42);
+    assert_eq!(*ptr, 43);
 
-        ptr_clone.deref::<Cell<i32>>().set(3);
+    // Clone to force make_mut to clone the data.
+    let mut ptr_clone = PointerKind::clone(&ptr);
 
-        assert_eq!(ptr.deref::<Cell<i32>>().get(), 3);
-        assert_eq!(ptr_clone.deref::<Cell<i32>>().get(), 3);
+    *PointerKind::make_mut(&mut ptr_clone) += 1;
 
-        ptr.drop::<Cell<i32>>();
+    assert_eq!(*ptr, 43);
+    assert_eq!(*ptr_clone, 44);
 
-        assert_eq!(ptr_clone.deref::<Cell<i32>>().get(), 3);
+    *PointerKind::make_mut(&mut ptr) *= 2;
 
-        ptr_clone.drop::<Cell<i32>>();
-    }
+    assert_eq!(*ptr, 2 * 43);
+    assert_eq!(*ptr_clone, 44);
 }
 
 #[test]
-fn test_debug() {
+fn test_make_mut_with() {
     let mut ptr = PointerKind::new::<i32>(42);
 
-    assert_eq!(format!("{:?}", ptr), "ArcTK");
+    *PointerKind::make_mut_with(&mut ptr, |_| unreachable!()) += 1;
 
-    unsafe {
-        ptr.drop::<i32>();
-    }
+    assert_eq!(*ptr, 43);
+
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    *PointerKind::make_mut_with(&mut ptr_clone, |v| v * 10) += 1;
+
+    assert_eq!(*ptr, 43);
+    assert_eq!(*ptr_clone, 431);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
 }
 
 #[test]
@@ -200,26 +207,82 @@ fn test_make_mut_panic_safety() {
         }
     }
 
-    let mut ptr = PointerKind::new::<PanicOnClone>(PanicOnClone(42));
+    let ptr = PointerKind::new::<PanicOnClone>(PanicOnClone(42));
+    let mut ptr_clone = PointerKind::clone(&ptr);
 
-    unsafe {
-        let mut ptr_clone = ptr.clone::<PanicOnClone>();
+    // Trigger `make_mut` on a shared handle so it must clone via `T::clone` (which panics).
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        PointerKind::make_mut(&mut ptr_clone);
+    }));
 
-        assert_eq!(ptr.strong_count::<PanicOnClone>(), 2);
+    assert!(result.is_err(), "make_mut should have unwound");
 
-        // Trigger `make_mut` on a shared handle so it must clone via `T::clone` (which panics).
-        let result = catch_unwind(AssertUnwindSafe(|| {
-            ptr_clone.make_mut::<PanicOnClone>();
-        }));
+    // A panic in `T::clone` must not desync the strong count: both handles must still own
+    // their strong reference.
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 2);
+}
 
-        assert!(result.is_err(), "make_mut should have unwound");
+#[test]
+fn test_into_inner() {
+    let ptr = PointerKind::new::<i32>(42);
+    let ptr_clone = PointerKind::clone(&ptr);
 
-        // A panic in `T::clone` must not desync the strong count: both handles must still own
-        // their strong reference.
-        assert_eq!(ptr.strong_count::<PanicOnClone>(), 2);
-        assert_eq!(ptr_clone.strong_count::<PanicOnClone>(), 2);
+    assert_eq!(PointerKind::into_inner(ptr), None);
+    assert_eq!(PointerKind::into_inner(ptr_clone), Some(42));
+}
 
-        ptr.drop::<PanicOnClone>();
-        ptr_clone.drop::<PanicOnClone>();
-    }
+#[test]
+fn test_clone() {
+    let ptr = PointerKind::new::<Cell<i32>>(Cell::new(42));
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+
+    ptr_clone.set(3);
+
+    assert_eq!(ptr.get(), 3);
+
+    drop(ptr);
+
+    assert_eq!(ptr_clone.get(), 3);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(format!("{:?}", PointerKind::default()), "ArcTK");
+}
+
+#[test]
+fn test_from_header_and_iter() {
+    let ptr = PointerKind::from_header_and_iter::<i32, i32, _>(42, [1, 2, 3].into_iter());
+
+    assert_eq!(*ptr.header(), 42);
+    assert_eq!(ptr.slice(), [1, 2, 3]);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(ptr_clone.slice(), [1, 2, 3]);
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+}
+
+#[test]
+fn test_from_into_thin_arc() {
+    let thin_arc: ThinArc<i32, i32> = ThinArc::from_header_and_iter(42, [1, 2, 3].into_iter());
+    let raw = thin_arc.ptr();
+    let ptr: ThinSharedPointer<i32, i32, ArcTK> = ThinSharedPointer::from(thin_arc);
+
+    assert_eq!(*ptr.header(), 42);
+    assert_eq!(ptr.slice(), [1, 2, 3]);
+    assert_eq!(ThinSharedPointer::strong_count(&ptr), 1);
+
+    let ptr_clone = ThinSharedPointer::clone(&ptr);
+    let thin_arc: ThinArc<i32, i32> = ThinArc::from(ptr);
+
+    assert_eq!(thin_arc.ptr(), raw);
+    assert_eq!(thin_arc.header.header, 42);
+    assert_eq!(thin_arc.slice, [1, 2, 3]);
+    assert_eq!(ThinSharedPointer::strong_count(&ptr_clone), 2);
 }
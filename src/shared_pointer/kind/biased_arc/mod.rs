@@ -0,0 +1,190 @@
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::AllocError;
+use crate::shared_pointer::kind::SharedFallibleKind;
+use crate::shared_pointer::kind::SharedLockKind;
+use crate::shared_pointer::kind::SharedOnceKind;
+use crate::shared_pointer::kind::SharedPointerKind;
+use crate::shared_pointer::kind::SharedStatelessKind;
+use crate::shared_pointer::lock::SyncLock;
+use crate::shared_pointer::once::SyncOnceCell;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+#[cfg(feature = "coerce_unsized")]
+use core::marker::Unsize;
+use core::mem::MaybeUninit;
+#[cfg(feature = "coerce_unsized")]
+use core::ops::DispatchFromDyn;
+use core::ptr::NonNull;
+
+mod pointer;
+mod thread;
+
+pub use pointer::BiasedArc;
+
+/// [Type constructors](https://en.wikipedia.org/wiki/Type_constructor) for
+/// [`BiasedArc`] pointers.
+///
+/// This is a thread-safe alternative to [`ArcK`](super::ArcK) for values that are mostly cloned
+/// and dropped by the thread that created them, which can do so without atomic operations. See
+/// [`BiasedArc`] for the details.
+#[derive(Default)]
+pub struct BiasedArcK {
+    _private: (),
+}
+
+impl BiasedArcK {
+    /// Releases the references that other threads handed over to the current thread, dropping
+    /// the values that are no longer referenced.
+    ///
+    /// When a thread drops what might be the last reference to a value created by another
+    /// thread, that reference is handed over to the thread that created the value. This happens
+    /// automatically whenever that thread creates a new [`BiasedArc`], drops its last reference to
+    /// one, and when it exits, but a long-lived thread that stops creating and dropping pointers
+    /// can call this to release memory earlier.
+    #[inline(always)]
+    pub fn release_deferred() {
+        thread::release_pending();
+    }
+}
+
+unsafe impl SharedPointerKind for BiasedArcK {
+    type Pointer<T: ?Sized> = BiasedArc<T>;
+
+    #[inline(always)]
+    fn new<T>(v: T) -> BiasedArc<T> {
+        BiasedArc::new(v)
+    }
+
+    #[inline(always)]
+    fn from_box<T>(v: Box<T>) -> BiasedArc<T> {
+        BiasedArc::from(v)
+    }
+
+    #[inline(always)]
+    fn new_uninit<T>() -> BiasedArc<MaybeUninit<T>> {
+        BiasedArc::new_uninit()
+    }
+
+    #[inline(always)]
+    fn from_slice<T: Clone>(v: &[T]) -> BiasedArc<[T]> {
+        BiasedArc::from(v)
+    }
+
+    #[inline(always)]
+    fn from_vec<T>(v: Vec<T>) -> BiasedArc<[T]> {
+        BiasedArc::from(v)
+    }
+
+    #[inline(always)]
+    fn from_str(v: &str) -> BiasedArc<str> {
+        BiasedArc::from(v)
+    }
+
+    #[inline(always)]
+    fn into_raw<T: ?Sized>(ptr: BiasedArc<T>) -> (NonNull<T>, BiasedArcK) {
+        // SAFETY: `BiasedArc::into_raw()` never returns a null pointer.
+        let ptr = unsafe { NonNull::new_unchecked(BiasedArc::into_raw(ptr).cast_mut()) };
+
+        (ptr, BiasedArcK::default())
+    }
+
+    #[inline(always)]
+    unsafe fn from_raw<T: ?Sized>(ptr: NonNull<T>, _: BiasedArcK) -> BiasedArc<T> {
+        // SAFETY: By the caller's guarantee, `ptr` was returned by `BiasedArc::into_raw()`,
+        // possibly after a cast that `BiasedArc::from_raw()` accepts, and owns a reference.
+        unsafe { BiasedArc::from_raw(ptr.as_ptr()) }
+    }
+
+    #[inline(always)]
+    fn clone<T: ?Sized>(ptr: &BiasedArc<T>) -> BiasedArc<T> {
+        BiasedArc::clone(ptr)
+    }
+
+    #[inline(always)]
+    fn strong_count<T: ?Sized>(ptr: &BiasedArc<T>) -> usize {
+        BiasedArc::strong_count(ptr)
+    }
+
+    #[inline(always)]
+    fn get_mut<T: ?Sized>(ptr: &mut BiasedArc<T>) -> Option<&mut T> {
+        BiasedArc::get_mut(ptr)
+    }
+
+    #[inline(always)]
+    fn make_mut<T: Clone>(ptr: &mut BiasedArc<T>) -> &mut T {
+        BiasedArc::make_mut(ptr)
+    }
+
+    #[inline(always)]
+    fn make_mut_with<T>(ptr: &mut BiasedArc<T>, f: impl FnOnce(&T) -> T) -> &mut T {
+        if BiasedArc::get_mut(ptr).is_none() {
+            *ptr = BiasedArc::new(f(ptr));
+        }
+
+        // SAFETY: `ptr` is either the only reference to its allocation, as checked by
+        // `BiasedArc::get_mut()`, or a new allocation.
+        unsafe { BiasedArc::get_mut(ptr).unwrap_unchecked() }
+    }
+
+    #[inline(always)]
+    fn try_unwrap<T>(ptr: BiasedArc<T>) -> Result<T, BiasedArc<T>> {
+        BiasedArc::try_unwrap(ptr)
+    }
+}
+
+unsafe impl SharedFallibleKind for BiasedArcK {
+    #[inline(always)]
+    fn try_new<T>(v: T) -> Result<BiasedArc<T>, AllocError> {
+        BiasedArc::try_new(v)
+    }
+
+    #[inline(always)]
+    fn try_from_box<T>(v: Box<T>) -> Result<BiasedArc<T>, AllocError> {
+        BiasedArc::try_from_box(v)
+    }
+}
+
+impl SharedLockKind for BiasedArcK {
+    type Lock<T> = SyncLock<T>;
+}
+
+impl SharedOnceKind for BiasedArcK {
+    type OnceCell<T> = SyncOnceCell<T>;
+}
+
+unsafe impl SharedStatelessKind for BiasedArcK {}
+
+impl<T: ?Sized> From<BiasedArc<T>> for SharedPointer<T, BiasedArcK> {
+    #[inline(always)]
+    fn from(v: BiasedArc<T>) -> SharedPointer<T, BiasedArcK> {
+        SharedPointer::from_inner_pointer(v)
+    }
+}
+
+impl<T: ?Sized> From<SharedPointer<T, BiasedArcK>> for BiasedArc<T> {
+    #[inline(always)]
+    fn from(v: SharedPointer<T, BiasedArcK>) -> BiasedArc<T> {
+        SharedPointer::into_inner_pointer(v)
+    }
+}
+
+#[cfg(feature = "coerce_unsized")]
+impl<T, U> DispatchFromDyn<SharedPointer<U, BiasedArcK>> for SharedPointer<T, BiasedArcK>
+where
+    T: Unsize<U> + ?Sized,
+    U: ?Sized,
+{
+}
+
+impl Debug for BiasedArcK {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("BiasedArcK")
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,686 @@
+use super::thread;
+use super::thread::Deferred;
+use crate::shared_pointer::kind::AllocError;
+use crate::shared_pointer::kind::move_from_box;
+use alloc::alloc::handle_alloc_error;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+use core::mem;
+use core::mem::ManuallyDrop;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+use core::ptr;
+use core::ptr::NonNull;
+use core::sync::atomic;
+use core::sync::atomic::AtomicIsize;
+use core::sync::atomic::Ordering;
+
+/// Flag of [`Header::shared`] set once the biased count was merged into it.
+const MERGED: isize = 0b01;
+/// Flag of [`Header::shared`] set once a reference was handed over to the owner thread.
+const QUEUED: isize = 0b10;
+const SHARED_SHIFT: u32 = 2;
+/// A reference in [`Header::shared`].
+const SHARED_ONE: isize = 1 << SHARED_SHIFT;
+
+/// Value of [`Header::biased`] once it was merged into [`Header::shared`].
+const BIASED_MERGED: isize = isize::MIN;
+
+/// Maximum number of references, as in `std::sync::Arc`. This leaves plenty of room for the
+/// counts to temporarily go over it before we abort.
+const MAX_REFCOUNT: isize = isize::MAX >> (SHARED_SHIFT + 1);
+
+/// The reference counts of a [`BiasedArc`], stored right before its value.
+///
+/// The number of references is the sum of the biased and the shared counts. The biased count is
+/// only modified by the owner thread, so it does not need atomic operations. The shared count is
+/// modified by every other thread, and it can become negative if they release references that
+/// were counted by the owner. When the owner thread drops its last reference (its biased count
+/// reaches zero) it merges the biased count into the shared count, after which every thread uses
+/// the shared count.
+///
+/// A thread that is not the owner cannot know whether it is releasing the last reference before
+/// the counts are merged. If the shared count is zero, it hands its reference over to the owner
+/// thread instead, which will merge the counts (see the [`thread`] module).
+struct Header {
+    /// Id of the thread that created the allocation, or [`thread::NO_OWNER`].
+    owner: usize,
+    /// Only accessed by the owner thread, or by any thread once the owner exited.
+    biased: Cell<isize>,
+    /// The shared count, shifted by [`SHARED_SHIFT`], with the [`MERGED`] and [`QUEUED`] flags.
+    shared: AtomicIsize,
+}
+
+impl Header {
+    fn new(owner: usize) -> Header {
+        match owner {
+            thread::NO_OWNER => Header {
+                owner,
+                biased: Cell::new(BIASED_MERGED),
+                shared: AtomicIsize::new(SHARED_ONE | MERGED),
+            },
+            _ => Header { owner, biased: Cell::new(1), shared: AtomicIsize::new(0) },
+        }
+    }
+
+    /// Returns the biased count if the current thread is the owner and the counts are not
+    /// merged.
+    #[inline(always)]
+    fn biased(&self) -> Option<isize> {
+        match self.owner == thread::current_id() {
+            true => Some(self.biased.get()).filter(|&biased| biased != BIASED_MERGED),
+            false => None,
+        }
+    }
+
+    /// Merges the biased count into the shared count, releasing `released` references. Returns
+    /// whether no references are left.
+    ///
+    /// This must only be called once, either by the owner thread or after it exited.
+    fn merge(&self, released: isize) -> bool {
+        let biased = self.biased.replace(BIASED_MERGED);
+        let delta = ((biased - released) << SHARED_SHIFT) | MERGED;
+        let shared = self.shared.fetch_add(delta, Ordering::AcqRel) + delta;
+
+        shared >> SHARED_SHIFT == 0
+    }
+
+    /// Releases a reference counted in the shared count. Returns whether no references are left,
+    /// or `None` if the caller must hand the reference over to the owner thread.
+    #[inline(always)]
+    fn release_shared(&self) -> Option<bool> {
+        let mut shared = self.shared.load(Ordering::Relaxed);
+
+        if shared & MERGED != 0 {
+            shared = self.shared.fetch_sub(SHARED_ONE, Ordering::Release);
+        } else {
+            loop {
+                // If the shared count is zero we cannot know if the owner thread still holds
+                // references.
+                let new = match shared {
+                    0 => QUEUED,
+                    _ => shared - SHARED_ONE,
+                };
+
+                match self.shared.compare_exchange_weak(
+                    shared,
+                    new,
+                    Ordering::Release,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) if shared == 0 => return None,
+                    Ok(_) => break,
+                    Err(actual) => shared = actual,
+                }
+            }
+        }
+
+        match shared & MERGED != 0 && shared >> SHARED_SHIFT == 1 {
+            true => {
+                // Synchronize with the release of every other reference.
+                atomic::fence(Ordering::Acquire);
+
+                Some(true)
+            }
+            false => Some(false),
+        }
+    }
+}
+
+/// Computes the layout of the allocation of a value with layout `value` and the offset of the
+/// value in it.
+fn allocation_layout(value: Layout) -> (Layout, usize) {
+    let (layout, offset) =
+        Layout::new::<Header>().extend(value).unwrap_or_else(|_| panic!("allocation too large"));
+
+    (layout.pad_to_align(), offset)
+}
+
+/// Allocates memory for a value with layout `value` and initializes the header. Returns a pointer
+/// to where the value must be written.
+fn allocate(value: Layout) -> Result<NonNull<u8>, AllocError> {
+    let (layout, offset) = allocation_layout(value);
+
+    // SAFETY: The layout has a non-zero size, since it includes the header.
+    let base = NonNull::new(unsafe { alloc::alloc::alloc(layout) }).ok_or(AllocError)?;
+
+    // SAFETY: `base` is valid for writes of the allocation layout, which starts with a `Header`.
+    unsafe { base.cast::<Header>().write(Header::new(thread::register_current())) };
+
+    // SAFETY: `offset` is within the allocation.
+    Ok(unsafe { base.add(offset) })
+}
+
+/// Like [`allocate()`], but aborts on allocation failure.
+fn allocate_or_abort(value: Layout) -> NonNull<u8> {
+    allocate(value).unwrap_or_else(|AllocError| handle_alloc_error(allocation_layout(value).0))
+}
+
+/// A thread-safe reference-counting pointer that uses
+/// [biased reference counting](https://dl.acm.org/doi/10.1145/3243176.3243195). This is the
+/// pointer type of [`BiasedArcK`](super::BiasedArcK).
+///
+/// The thread that creates a `BiasedArc` owns it: while it clones and drops it there are no
+/// atomic operations involved. Other threads use atomic operations, as with
+/// [`Arc`](alloc::sync::Arc). Once the owner thread drops all the references it counted, every
+/// thread uses atomic operations.
+///
+/// Other threads cannot tell if they drop the last reference while the owner thread still counts
+/// references. In that case the reference is handed over to the owner thread, which releases it
+/// when it next creates a `BiasedArc`, when it drops its last reference to any `BiasedArc` it
+/// created, when it calls
+/// [`BiasedArcK::release_deferred()`](super::BiasedArcK::release_deferred), or when it exits. The
+/// value is then dropped by the owner thread. Until then the memory of the value is held, so an
+/// owner thread that keeps references but never does any of the above holds it indefinitely. For
+/// the same reason other threads can only get mutable access to the value
+/// ([`BiasedArc::get_mut()`]) once the owner thread dropped its references, and
+/// [`BiasedArc::into_inner()`] can return `None` for every reference.
+pub struct BiasedArc<T: ?Sized> {
+    ptr: NonNull<T>,
+    _phantom_t: PhantomData<T>,
+}
+
+// SAFETY: The reference counts are thread-safe, so this is as `Send` and `Sync` as `Arc<T>`.
+unsafe impl<T: Send + Sync + ?Sized> Send for BiasedArc<T> {}
+unsafe impl<T: Send + Sync + ?Sized> Sync for BiasedArc<T> {}
+
+impl<T> BiasedArc<T> {
+    #[inline(always)]
+    pub fn new(v: T) -> BiasedArc<T> {
+        let ptr: NonNull<T> = allocate_or_abort(Layout::new::<T>()).cast::<T>();
+
+        // SAFETY: `ptr` is valid for writes of `T`.
+        unsafe { ptr.write(v) };
+
+        BiasedArc::from_inner(ptr)
+    }
+
+    /// Like [`BiasedArc::new()`], but returns an [`AllocError`] instead of aborting if the
+    /// memory allocation fails.
+    #[inline(always)]
+    pub fn try_new(v: T) -> Result<BiasedArc<T>, AllocError> {
+        let ptr: NonNull<T> = allocate(Layout::new::<T>())?.cast::<T>();
+
+        // SAFETY: `ptr` is valid for writes of `T`.
+        unsafe { ptr.write(v) };
+
+        Ok(BiasedArc::from_inner(ptr))
+    }
+
+    /// Like [`BiasedArc::try_new()`], but moves the value from a box, without going through the
+    /// stack.
+    #[inline(always)]
+    pub fn try_from_box(v: Box<T>) -> Result<BiasedArc<T>, AllocError> {
+        let mut ptr: NonNull<MaybeUninit<T>> =
+            allocate(Layout::new::<T>())?.cast::<MaybeUninit<T>>();
+
+        // SAFETY: `ptr` is valid for writes of `T`, and nothing else points to it yet.
+        move_from_box(v, unsafe { ptr.as_mut() });
+
+        Ok(BiasedArc::from_inner(ptr.cast::<T>()))
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn new_uninit() -> BiasedArc<MaybeUninit<T>> {
+        BiasedArc::from_inner(allocate_or_abort(Layout::new::<T>()).cast::<MaybeUninit<T>>())
+    }
+
+    #[inline(always)]
+    pub fn try_unwrap(this: BiasedArc<T>) -> Result<T, BiasedArc<T>> {
+        if !this.is_unique() {
+            return Err(this);
+        }
+
+        let this = ManuallyDrop::new(this);
+
+        // SAFETY: This is the only reference, so we can move the value out and free the
+        // allocation.
+        unsafe {
+            let v: T = this.ptr.read();
+
+            BiasedArc::deallocate(this.ptr);
+
+            Ok(v)
+        }
+    }
+
+    /// Returns the value if this is the last reference to it. Otherwise, releases the reference
+    /// and returns `None`.
+    ///
+    /// Note that if other threads drop the last reference while the owner thread still counts
+    /// references, the value is dropped by the owner thread instead, so all calls can return
+    /// `None`. This is why [`BiasedArcK`](super::BiasedArcK) does not implement
+    /// [`SharedIntoInnerKind`](crate::shared_pointer::kind::SharedIntoInnerKind).
+    #[inline(always)]
+    #[must_use]
+    pub fn into_inner(this: BiasedArc<T>) -> Option<T> {
+        let this = ManuallyDrop::new(this);
+
+        match this.release() {
+            // SAFETY: We released the last reference, so we can move the value out and free the
+            // allocation.
+            true => unsafe {
+                let v: T = this.ptr.read();
+
+                BiasedArc::deallocate(this.ptr);
+
+                Some(v)
+            },
+            false => None,
+        }
+    }
+}
+
+impl<T: Clone> BiasedArc<T> {
+    #[inline(always)]
+    pub fn make_mut(this: &mut BiasedArc<T>) -> &mut T {
+        if !this.is_unique() {
+            *this = BiasedArc::new(T::clone(this));
+        }
+
+        // SAFETY: This is the only reference.
+        unsafe { this.ptr.as_mut() }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn unwrap_or_clone(this: BiasedArc<T>) -> T {
+        BiasedArc::try_unwrap(this).unwrap_or_else(|this| T::clone(&this))
+    }
+}
+
+impl<T> BiasedArc<MaybeUninit<T>> {
+    /// # Safety
+    ///
+    /// The value must be initialized.
+    #[inline(always)]
+    #[must_use]
+    pub unsafe fn assume_init(this: BiasedArc<MaybeUninit<T>>) -> BiasedArc<T> {
+        let this = ManuallyDrop::new(this);
+
+        BiasedArc::from_inner(this.ptr.cast::<T>())
+    }
+}
+
+impl<T: ?Sized> BiasedArc<T> {
+    #[inline(always)]
+    fn from_inner(ptr: NonNull<T>) -> BiasedArc<T> {
+        BiasedArc { ptr, _phantom_t: PhantomData }
+    }
+
+    #[inline(always)]
+    fn header(&self) -> &Header {
+        // SAFETY: The value is alive while `self` is.
+        unsafe { BiasedArc::header_of(self.ptr) }
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must point to the value of a `BiasedArc` allocation that was not yet dropped.
+    #[inline(always)]
+    unsafe fn header_of<'a>(ptr: NonNull<T>) -> &'a Header {
+        // SAFETY: The value is valid, so we can get its layout, which determines its offset in
+        // the allocation.
+        let (_, offset) = allocation_layout(Layout::for_value(unsafe { ptr.as_ref() }));
+
+        // SAFETY: The header is at the start of the allocation.
+        unsafe { ptr.cast::<u8>().sub(offset).cast::<Header>().as_ref() }
+    }
+
+    /// Frees the allocation, without dropping the value.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to the value of a `BiasedArc` allocation with no references left, and
+    /// the value must still be valid.
+    unsafe fn deallocate(ptr: NonNull<T>) {
+        // SAFETY: The value is still valid.
+        let (layout, offset) = allocation_layout(Layout::for_value(unsafe { ptr.as_ref() }));
+
+        // SAFETY: The allocation starts `offset` bytes before the value and was allocated with
+        // `layout`.
+        unsafe { alloc::alloc::dealloc(ptr.cast::<u8>().sub(offset).as_ptr(), layout) };
+    }
+
+    /// Drops the value and frees the allocation.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to the value of a `BiasedArc` allocation with no references left.
+    #[inline(never)]
+    unsafe fn drop_slow(ptr: NonNull<T>) {
+        /// Frees the allocation even if dropping the value panics.
+        struct Guard<T: ?Sized> {
+            ptr: NonNull<T>,
+            layout: Layout,
+            offset: usize,
+        }
+
+        impl<T: ?Sized> Drop for Guard<T> {
+            fn drop(&mut self) {
+                // SAFETY: See `BiasedArc::deallocate()`.
+                unsafe {
+                    alloc::alloc::dealloc(
+                        self.ptr.cast::<u8>().sub(self.offset).as_ptr(),
+                        self.layout,
+                    );
+                }
+            }
+        }
+
+        // SAFETY: The value is still valid.
+        let (layout, offset) = allocation_layout(Layout::for_value(unsafe { ptr.as_ref() }));
+        let _guard = Guard { ptr, layout, offset };
+
+        // SAFETY: There are no references left.
+        unsafe { ptr.drop_in_place() };
+    }
+
+    /// Returns whether this is the only reference.
+    #[inline(always)]
+    fn is_unique(&self) -> bool {
+        let header = self.header();
+        let shared = header.shared.load(Ordering::Acquire);
+
+        match header.biased() {
+            Some(biased) => biased + (shared >> SHARED_SHIFT) == 1,
+            None => shared & MERGED != 0 && shared >> SHARED_SHIFT == 1,
+        }
+    }
+
+    /// Releases this reference, without dropping the value. Returns whether this was the last
+    /// reference, in which case the caller must drop the value (or move it out) and free the
+    /// allocation.
+    #[inline(always)]
+    fn release(&self) -> bool {
+        let header = self.header();
+
+        match header.biased() {
+            Some(1) => {
+                // The owner thread lets go of the allocation, releasing this reference.
+                let last: bool = header.merge(1);
+
+                // This is also when the owner releases the references other threads handed over
+                // to it, so that they are not held until it creates a new allocation. This must
+                // happen after the merge, since it can drop values that hold references to this
+                // allocation.
+                thread::release_pending();
+
+                last
+            }
+            Some(biased) => {
+                header.biased.set(biased - 1);
+
+                false
+            }
+            None => match header.release_shared() {
+                Some(last) => last,
+                None => {
+                    thread::defer(
+                        header.owner,
+                        Deferred::new(self.ptr, BiasedArc::<T>::release_deferred),
+                    );
+
+                    false
+                }
+            },
+        }
+    }
+
+    /// Releases a reference handed over to the owner thread.
+    ///
+    /// # Safety
+    ///
+    /// `deferred` must have been created by [`BiasedArc::release()`], and this must run in the
+    /// owner thread or after it exited.
+    unsafe fn release_deferred(deferred: &Deferred) {
+        // SAFETY: By the caller's guarantee, `deferred` points to the value of a `BiasedArc`
+        // allocation of `T`, with a reference we now own.
+        let ptr: NonNull<T> = unsafe { deferred.ptr::<T>() };
+        // SAFETY: The deferred reference keeps the value alive.
+        let header: &Header = unsafe { BiasedArc::header_of(ptr) };
+
+        // Either we are the owner thread or it exited, so we can access the biased count.
+        let last: bool = match header.biased.get() {
+            BIASED_MERGED => header.release_shared() == Some(true),
+            _ => header.merge(1),
+        };
+
+        if last {
+            // SAFETY: We released the last reference.
+            unsafe { BiasedArc::drop_slow(ptr) };
+        }
+    }
+
+    #[inline(always)]
+    #[must_use = "losing the pointer will leak memory"]
+    pub fn into_raw(this: BiasedArc<T>) -> *const T {
+        let this = ManuallyDrop::new(this);
+
+        this.ptr.as_ptr().cast_const()
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [`BiasedArc::into_raw()`], possibly after an unsizing
+    /// coercion, and its reference must not have been released.
+    #[inline(always)]
+    pub unsafe fn from_raw(ptr: *const T) -> BiasedArc<T> {
+        // SAFETY: By the caller's guarantee, `ptr` is not null.
+        BiasedArc::from_inner(unsafe { NonNull::new_unchecked(ptr.cast_mut()) })
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [`BiasedArc::into_raw()`] and its allocation must be
+    /// alive.
+    #[inline(always)]
+    pub unsafe fn increment_strong_count(ptr: *const T) {
+        // SAFETY: By the caller's guarantee, the reference we make up is alive, and we do not
+        // release it.
+        let this = ManuallyDrop::new(unsafe { BiasedArc::from_raw(ptr) });
+
+        mem::forget(BiasedArc::clone(&this));
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [`BiasedArc::into_raw()`] and the reference being
+    /// released must be alive.
+    #[inline(always)]
+    pub unsafe fn decrement_strong_count(ptr: *const T) {
+        // SAFETY: By the caller's guarantee, the reference we take is alive.
+        drop(unsafe { BiasedArc::from_raw(ptr) });
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn as_ptr(this: &BiasedArc<T>) -> *const T {
+        this.ptr.as_ptr().cast_const()
+    }
+
+    #[inline(always)]
+    pub fn get_mut(this: &mut BiasedArc<T>) -> Option<&mut T> {
+        match this.is_unique() {
+            // SAFETY: This is the only reference.
+            true => Some(unsafe { this.ptr.as_mut() }),
+            false => None,
+        }
+    }
+
+    /// Gets the number of references to the value.
+    ///
+    /// Threads other than the owner cannot see the references counted by the owner thread until
+    /// it drops them, so for them this is only a lower bound.
+    #[inline(always)]
+    #[must_use]
+    pub fn strong_count(this: &BiasedArc<T>) -> usize {
+        let header = this.header();
+        let shared = header.shared.load(Ordering::Acquire);
+        let count: isize = match header.biased() {
+            Some(biased) => biased + (shared >> SHARED_SHIFT),
+            // The owner still counts at least one reference if the counts are not merged.
+            None if shared & MERGED == 0 => (shared >> SHARED_SHIFT) + 1,
+            None => shared >> SHARED_SHIFT,
+        };
+
+        // This reference is always counted.
+        count.max(1).unsigned_abs()
+    }
+}
+
+impl<T: ?Sized> Clone for BiasedArc<T> {
+    #[inline(always)]
+    fn clone(&self) -> BiasedArc<T> {
+        let header = self.header();
+
+        let count: isize = match header.biased() {
+            Some(biased) => {
+                header.biased.set(biased + 1);
+
+                biased
+            }
+            None => header.shared.fetch_add(SHARED_ONE, Ordering::Relaxed) >> SHARED_SHIFT,
+        };
+
+        if count > MAX_REFCOUNT {
+            std::process::abort();
+        }
+
+        BiasedArc::from_inner(self.ptr)
+    }
+}
+
+impl<T: ?Sized> Drop for BiasedArc<T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        if self.release() {
+            // SAFETY: We released the last reference.
+            unsafe { BiasedArc::drop_slow(self.ptr) };
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for BiasedArc<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // SAFETY: The value is alive while `self` is.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> From<T> for BiasedArc<T> {
+    #[inline(always)]
+    fn from(v: T) -> BiasedArc<T> {
+        BiasedArc::new(v)
+    }
+}
+
+impl<T> From<Box<T>> for BiasedArc<T> {
+    #[inline(always)]
+    fn from(v: Box<T>) -> BiasedArc<T> {
+        let mut ptr: NonNull<MaybeUninit<T>> =
+            allocate_or_abort(Layout::new::<T>()).cast::<MaybeUninit<T>>();
+
+        // SAFETY: `ptr` is valid for writes of `T`, and nothing else points to it yet.
+        move_from_box(v, unsafe { ptr.as_mut() });
+
+        BiasedArc::from_inner(ptr.cast::<T>())
+    }
+}
+
+impl<T: Clone> From<&[T]> for BiasedArc<[T]> {
+    fn from(v: &[T]) -> BiasedArc<[T]> {
+        /// Drops the elements cloned so far and frees the allocation if a clone panics.
+        struct Guard<T> {
+            data: NonNull<T>,
+            len: usize,
+            layout: Layout,
+        }
+
+        impl<T> Drop for Guard<T> {
+            fn drop(&mut self) {
+                let (layout, offset) = allocation_layout(self.layout);
+
+                // SAFETY: The first `len` elements were initialized, and the allocation starts
+                // `offset` bytes before them.
+                unsafe {
+                    NonNull::slice_from_raw_parts(self.data, self.len).drop_in_place();
+                    alloc::alloc::dealloc(self.data.cast::<u8>().sub(offset).as_ptr(), layout);
+                }
+            }
+        }
+
+        let layout = Layout::array::<T>(v.len()).unwrap_or_else(|_| panic!("capacity overflow"));
+        let data: NonNull<T> = allocate_or_abort(layout).cast::<T>();
+        let mut guard = Guard { data, len: 0, layout };
+
+        for (i, item) in v.iter().enumerate() {
+            // SAFETY: The allocation has room for `v.len()` elements.
+            unsafe { data.add(i).write(item.clone()) };
+
+            guard.len += 1;
+        }
+
+        mem::forget(guard);
+
+        BiasedArc::from_inner(NonNull::slice_from_raw_parts(data, v.len()))
+    }
+}
+
+impl<T> From<Vec<T>> for BiasedArc<[T]> {
+    fn from(mut v: Vec<T>) -> BiasedArc<[T]> {
+        let layout = Layout::array::<T>(v.len()).unwrap_or_else(|_| panic!("capacity overflow"));
+        let data: NonNull<T> = allocate_or_abort(layout).cast::<T>();
+        let len = v.len();
+
+        // SAFETY: The allocation has room for `len` elements, which we move out of `v`.
+        unsafe {
+            ptr::copy_nonoverlapping(v.as_ptr(), data.as_ptr(), len);
+            v.set_len(0);
+        }
+
+        BiasedArc::from_inner(NonNull::slice_from_raw_parts(data, len))
+    }
+}
+
+impl From<&str> for BiasedArc<str> {
+    #[inline(always)]
+    fn from(v: &str) -> BiasedArc<str> {
+        let bytes = BiasedArc::<[u8]>::into_raw(BiasedArc::from(v.as_bytes()));
+
+        // SAFETY: The bytes are valid UTF-8, and `str` has the same layout as `[u8]`.
+        unsafe { BiasedArc::from_raw(bytes as *const str) }
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for BiasedArc<T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for BiasedArc<T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> fmt::Pointer for BiasedArc<T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        fmt::Pointer::fmt(&self.ptr, f)
+    }
+}
@@ -0,0 +1,489 @@
+use super::*;
+use crate::shared_pointer::kind::SharedIntoInnerKind;
+use pretty_assertions::assert_eq;
+use static_assertions::assert_impl_all;
+use static_assertions::assert_not_impl_any;
+use std::cell::Cell;
+use std::string::ToString;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::vec::Vec;
+
+type PointerKind = BiasedArcK;
+
+assert_impl_all!(BiasedArcK: Send, Sync);
+// Another thread may hand the last reference over to the owner thread, so `into_inner()` cannot
+// guarantee that one of the calls returns the value.
+assert_not_impl_any!(BiasedArcK: SharedIntoInnerKind);
+assert_impl_all!(SharedPointer<i32, BiasedArcK>: Send, Sync);
+
+/// Counts how many times it was dropped.
+struct DropCounter(Arc<AtomicUsize>);
+
+impl DropCounter {
+    fn new() -> (DropCounter, Arc<AtomicUsize>) {
+        let drops = Arc::new(AtomicUsize::new(0));
+
+        (DropCounter(Arc::clone(&drops)), drops)
+    }
+}
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_from_box_t() {
+    let ptr = PointerKind::from_box(Box::new(42));
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
+
+#[test]
+fn test_from_box_drops_once() {
+    let (value, drops) = DropCounter::new();
+
+    drop(PointerKind::from_box(Box::new(value)));
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+    let (value, drops) = DropCounter::new();
+
+    drop(PointerKind::try_from_box(Box::new(value)).unwrap());
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_try_new() {
+    let ptr = PointerKind::try_new::<i32>(42).unwrap();
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
+
+#[test]
+fn test_try_from_box() {
+    let ptr = PointerKind::try_from_box::<i32>(Box::new(42)).unwrap();
+
+    assert_eq!(*ptr, 42);
+}
+
+#[test]
+fn test_new_uninit_assume_init() {
+    let mut ptr = PointerKind::new_uninit::<i32>();
+
+    PointerKind::get_mut(&mut ptr).unwrap().write(42);
+
+    // SAFETY: The value was just initialized.
+    let ptr = unsafe { BiasedArc::assume_init(ptr) };
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
+
+#[test]
+fn test_from_slice() {
+    let ptr = PointerKind::from_slice::<i32>(&[1, 2, 3]);
+
+    assert_eq!(*ptr, [1, 2, 3]);
+}
+
+#[test]
+fn test_from_vec() {
+    let ptr = PointerKind::from_vec::<i32>(vec![1, 2, 3]);
+
+    assert_eq!(*ptr, [1, 2, 3]);
+}
+
+#[test]
+fn test_from_str() {
+    let ptr = PointerKind::from_str("hello from test_from_str");
+
+    assert_eq!(&*ptr, "hello from test_from_str");
+}
+
+#[test]
+fn test_unsized() {
+    let mut ptr = PointerKind::from_vec::<i32>(vec![1, 2, 3]);
+
+    PointerKind::get_mut(&mut ptr).unwrap()[0] = 0;
+
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+    assert_eq!(PointerKind::get_mut(&mut ptr), None);
+    assert_eq!(NonNull::from(&*ptr), NonNull::from(&*ptr_clone));
+    assert_eq!(*ptr_clone, [0, 2, 3]);
+
+    drop(ptr);
+
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
+}
+
+#[test]
+fn test_into_raw_from_raw() {
+    let ptr = PointerKind::from_str("hello from test_into_raw_from_raw");
+    let (raw, kind) = PointerKind::into_raw(ptr);
+
+    // SAFETY: `raw` points to a live value owned by the strong reference we just released.
+    assert_eq!(unsafe { raw.as_ref() }, "hello from test_into_raw_from_raw");
+
+    // SAFETY: `raw` and `kind` were returned by `into_raw()`.
+    let ptr = unsafe { PointerKind::from_raw(raw, kind) };
+
+    assert_eq!(NonNull::from(&*ptr), raw);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
+
+#[test]
+fn test_into_raw_from_raw_unsize() {
+    let ptr = PointerKind::new::<i32>(42);
+    let (raw, kind) = PointerKind::into_raw(ptr);
+    let raw: NonNull<dyn ToString> = raw;
+
+    // SAFETY: `raw` was returned by `into_raw()` and then unsized.
+    let ptr = unsafe { PointerKind::from_raw(raw, kind) };
+
+    assert_eq!(ptr.to_string(), "42");
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
+
+#[test]
+fn test_try_unwrap() {
+    let ptr = PointerKind::new::<i32>(42);
+
+    assert_eq!(PointerKind::try_unwrap(ptr).unwrap(), 42);
+
+    let ptr = PointerKind::new::<i32>(42);
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    let ptr_clone = PointerKind::try_unwrap(ptr_clone).unwrap_err();
+    let ptr = PointerKind::try_unwrap(ptr).unwrap_err();
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(*ptr_clone, 42);
+}
+
+#[test]
+fn test_get_mut() {
+    let mut ptr = PointerKind::new::<i32>(42);
+
+    *PointerKind::get_mut(&mut ptr).unwrap() += 1;
+
+    assert_eq!(*ptr, 43);
+
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(PointerKind::get_mut(&mut ptr), None);
+    assert_eq!(PointerKind::get_mut(&mut ptr_clone), None);
+
+    drop(ptr);
+
+    *PointerKind::get_mut(&mut ptr_clone).unwrap() += 1;
+
+    assert_eq!(*ptr_clone, 44);
+}
+
+#[test]
+fn test_make_mut() {
+    let mut ptr = PointerKind::new::<i32>(42);
+
+    *PointerKind::make_mut(&mut ptr) += 1;
+
+    assert_eq!(*ptr, 43);
+
+    // Clone to force make_mut to clone the data.
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    *PointerKind::make_mut(&mut ptr_clone) += 1;
+
+    assert_eq!(*ptr, 43);
+    assert_eq!(*ptr_clone, 44);
+
+    *PointerKind::make_mut(&mut ptr) *= 2;
+
+    assert_eq!(*ptr, 2 * 43);
+    assert_eq!(*ptr_clone, 44);
+}
+
+#[test]
+fn test_make_mut_with() {
+    let mut ptr = PointerKind::new::<i32>(42);
+
+    *PointerKind::make_mut_with(&mut ptr, |_| unreachable!()) += 1;
+
+    assert_eq!(*ptr, 43);
+
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    *PointerKind::make_mut_with(&mut ptr_clone, |v| v * 10) += 1;
+
+    assert_eq!(*ptr, 43);
+    assert_eq!(*ptr_clone, 431);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
+}
+
+#[test]
+fn test_make_mut_panic_safety() {
+    use std::panic::AssertUnwindSafe;
+    use std::panic::catch_unwind;
+
+    struct PanicOnClone(#[allow(dead_code)] u32);
+
+    impl Clone for PanicOnClone {
+        fn clone(&self) -> Self {
+            panic!("intentional panic in T::clone");
+        }
+    }
+
+    let ptr = PointerKind::new::<PanicOnClone>(PanicOnClone(42));
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    // Trigger `make_mut` on a shared handle so it must clone via `T::clone` (which panics).
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        PointerKind::make_mut(&mut ptr_clone);
+    }));
+
+    assert!(result.is_err(), "make_mut should have unwound");
+
+    // A panic in `T::clone` must not desync the strong count: both handles must still own
+    // their strong reference.
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 2);
+}
+
+#[test]
+fn test_clone() {
+    let ptr = PointerKind::new::<Cell<i32>>(Cell::new(42));
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+
+    ptr_clone.set(3);
+
+    assert_eq!(ptr.get(), 3);
+
+    drop(ptr);
+
+    assert_eq!(ptr_clone.get(), 3);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(format!("{:?}", PointerKind::default()), "BiasedArcK");
+}
+
+#[test]
+fn test_clone_drop_other_thread() {
+    let (value, drops) = DropCounter::new();
+    let arc = BiasedArc::new(value);
+    let arc_clone = BiasedArc::clone(&arc);
+
+    thread::spawn(move || {
+        let arc_clone_clone = BiasedArc::clone(&arc_clone);
+
+        // Other threads cannot tell how many references the owner thread counts.
+        assert_eq!(BiasedArc::strong_count(&arc_clone_clone), 2);
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(BiasedArc::strong_count(&arc), 2);
+
+    BiasedArcK::release_deferred();
+
+    assert_eq!(BiasedArc::strong_count(&arc), 1);
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+    drop(arc);
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_drop_last_on_other_thread() {
+    let (value, drops) = DropCounter::new();
+    let arc = BiasedArc::new(value);
+
+    // The owner thread still counts a reference, so this one is handed over to it.
+    thread::spawn(move || drop(arc)).join().unwrap();
+
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+    BiasedArcK::release_deferred();
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_drop_last_on_other_thread_released_on_new() {
+    let (value, drops) = DropCounter::new();
+    let arc = BiasedArc::new(value);
+
+    thread::spawn(move || drop(arc)).join().unwrap();
+
+    let _arc = BiasedArc::new(0);
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_drop_last_on_other_thread_released_on_owner_drop() {
+    let (value, drops) = DropCounter::new();
+    let arc = BiasedArc::new(value);
+    let other = BiasedArc::new(0);
+
+    thread::spawn(move || drop(arc)).join().unwrap();
+
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+    // The owner thread dropping its last reference to any allocation releases the references
+    // handed over to it.
+    drop(other);
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_drop_last_on_other_thread_after_owner_exits() {
+    let (value, drops) = DropCounter::new();
+    let (arc, arc_clone) = thread::spawn(move || {
+        let arc = BiasedArc::new(value);
+        let arc_clone = BiasedArc::clone(&arc);
+
+        (arc, arc_clone)
+    })
+    .join()
+    .unwrap();
+
+    drop(arc);
+
+    assert_eq!(BiasedArc::strong_count(&arc_clone), 1);
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+    drop(arc_clone);
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_owner_exits_with_deferred_reference() {
+    let (value, drops) = DropCounter::new();
+    let (arc_sender, arc_receiver) = std::sync::mpsc::channel();
+    let (done_sender, done_receiver) = std::sync::mpsc::channel::<()>();
+
+    let owner = thread::spawn(move || {
+        arc_sender.send(BiasedArc::new(value)).unwrap();
+
+        // Wait until the reference was handed back to us, and exit without releasing it.
+        done_receiver.recv().unwrap();
+    });
+
+    let arc = arc_receiver.recv().unwrap();
+
+    thread::spawn(move || drop(arc)).join().unwrap();
+
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+    done_sender.send(()).unwrap();
+    owner.join().unwrap();
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_owner_lets_go() {
+    let (value, drops) = DropCounter::new();
+    let mut arc = BiasedArc::new(value);
+    // A clone created by another thread is counted in the shared count.
+    let arc_clone = thread::scope(|s| s.spawn(|| BiasedArc::clone(&arc)).join().unwrap());
+
+    assert!(BiasedArc::get_mut(&mut arc).is_none());
+
+    let thread = thread::spawn(move || {
+        let mut arc_clone = arc_clone;
+
+        // Wait for the owner thread to drop its reference.
+        while BiasedArc::get_mut(&mut arc_clone).is_none() {
+            thread::yield_now();
+        }
+
+        drop(arc_clone);
+    });
+
+    drop(arc);
+    thread.join().unwrap();
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_get_mut_other_thread() {
+    let arc = BiasedArc::new(42);
+
+    thread::spawn(move || {
+        let mut arc = arc;
+
+        // The owner thread still counts the reference, so this thread cannot tell it is unique.
+        assert!(BiasedArc::get_mut(&mut arc).is_none());
+        assert_eq!(BiasedArc::strong_count(&arc), 1);
+        assert_eq!(BiasedArc::into_inner(arc), None);
+    })
+    .join()
+    .unwrap();
+
+    BiasedArcK::release_deferred();
+}
+
+#[test]
+fn test_into_inner_threads() {
+    let (value, drops) = DropCounter::new();
+    let arc = BiasedArc::new(value);
+    let arc_clone = thread::scope(|s| s.spawn(|| BiasedArc::clone(&arc)).join().unwrap());
+
+    drop(arc);
+
+    thread::spawn(move || assert!(BiasedArc::into_inner(arc_clone).is_some())).join().unwrap();
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_many_threads() {
+    let (value, drops) = DropCounter::new();
+    let arc = BiasedArc::new(value);
+
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let arc = BiasedArc::clone(&arc);
+
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    drop(BiasedArc::clone(&arc));
+                }
+            })
+        })
+        .collect();
+
+    for _ in 0..1000 {
+        drop(BiasedArc::clone(&arc));
+    }
+
+    drop(arc);
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    BiasedArcK::release_deferred();
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,184 @@
+//! Bookkeeping of the threads that own [`BiasedArc`](super::BiasedArc) allocations.
+//!
+//! Every thread that creates a `BiasedArc` is registered with a unique id, which is never reused,
+//! and a queue of deferred releases. When a thread other than the owner cannot tell whether it is
+//! dropping the last reference to an allocation, it hands that reference over to the queue of the
+//! owner, which will merge the reference counts of the allocation. If the owner thread has
+//! already exited, the counts are merged right away instead.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::mem;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+/// Owner id of allocations that are not owned by any thread.
+pub(super) const NO_OWNER: usize = 0;
+
+/// Id of threads that cannot own allocations, either because they were never registered or
+/// because they are exiting.
+const NO_ID: usize = usize::MAX;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// Queues of the registered threads, indexed by their id.
+static THREADS: Mutex<BTreeMap<usize, Arc<DeferredQueue>>> = Mutex::new(BTreeMap::new());
+
+std::thread_local! {
+    static ID: Cell<usize> = const { Cell::new(NO_ID) };
+    static REGISTRATION: Registration = Registration::new();
+}
+
+/// A reference to an allocation whose release was handed over to its owner thread.
+pub(super) struct Deferred {
+    /// The `NonNull<T>` to the value of the allocation, for the `T` that `release` expects. Its
+    /// metadata, if any, is kept along with the address, which a pointer never exceeds.
+    ptr: MaybeUninit<[*const (); 2]>,
+    /// Releases the reference. This must run on the owner thread, or after it exited.
+    release: unsafe fn(&Deferred),
+}
+
+// SAFETY: The reference is only released by the owner thread (or once it exited), and the safe
+// wrappers only allow sending a `BiasedArc<T>` to other threads if `T: Send + Sync`.
+unsafe impl Send for Deferred {}
+
+impl Deferred {
+    pub(super) fn new<T: ?Sized>(ptr: NonNull<T>, release: unsafe fn(&Deferred)) -> Deferred {
+        const {
+            assert!(mem::size_of::<NonNull<T>>() <= mem::size_of::<[*const (); 2]>());
+        }
+
+        let mut bits: MaybeUninit<[*const (); 2]> = MaybeUninit::uninit();
+
+        // SAFETY: `bits` is large enough for the pointer, as asserted above, and it is aligned
+        // as a pointer.
+        unsafe { bits.as_mut_ptr().cast::<NonNull<T>>().write(ptr) };
+
+        Deferred { ptr: bits, release }
+    }
+
+    /// Returns the pointer this was created with.
+    ///
+    /// # Safety
+    ///
+    /// `T` must be the type this was created with.
+    pub(super) unsafe fn ptr<T: ?Sized>(&self) -> NonNull<T> {
+        // SAFETY: By the caller's guarantee, a `NonNull<T>` was written to `self.ptr`.
+        unsafe { self.ptr.as_ptr().cast::<NonNull<T>>().read() }
+    }
+
+    fn release(self) {
+        // SAFETY: `Deferred`s are only released by the owner thread or after it exited.
+        unsafe { (self.release)(&self) }
+    }
+}
+
+#[derive(Default)]
+struct DeferredQueue {
+    deferred: Mutex<Vec<Deferred>>,
+    pending: AtomicBool,
+}
+
+impl DeferredQueue {
+    fn take(&self) -> Vec<Deferred> {
+        mem::take(&mut *lock(&self.deferred))
+    }
+
+    fn release_pending(&self) {
+        // Checking first avoids a read-modify-write in the common case of an empty queue.
+        if self.pending.load(Ordering::Relaxed) && self.pending.swap(false, Ordering::Acquire) {
+            // The lock must not be held while releasing, since that can drop values which may
+            // defer more references.
+            self.take().into_iter().for_each(Deferred::release);
+        }
+    }
+}
+
+/// Registration of the current thread, which is undone when the thread exits.
+struct Registration {
+    id: usize,
+    queue: Arc<DeferredQueue>,
+}
+
+impl Registration {
+    fn new() -> Registration {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let queue: Arc<DeferredQueue> = Arc::default();
+
+        lock(&THREADS).insert(id, Arc::clone(&queue));
+        ID.set(id);
+
+        Registration { id, queue }
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        // From now on this thread treats the allocations it owns as any other thread would.
+        ID.set(NO_ID);
+
+        // Once we are unregistered no other thread can add to our queue: they will merge the
+        // reference counts themselves. The lock also makes the biased counts we wrote visible to
+        // them.
+        lock(&THREADS).remove(&self.id);
+
+        self.queue.take().into_iter().for_each(Deferred::release);
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // We never leave the data in an inconsistent state, so a poisoned lock is fine.
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns the id of the current thread, which never matches the owner of any allocation if the
+/// thread is not registered.
+#[inline(always)]
+pub(super) fn current_id() -> usize {
+    ID.get()
+}
+
+/// Registers the current thread, if needed, so that it can own new allocations, and releases the
+/// references handed over to it. Returns the id of the current thread, or [`NO_OWNER`] if it
+/// cannot own allocations because it is exiting.
+pub(super) fn register_current() -> usize {
+    REGISTRATION
+        .try_with(|registration| {
+            registration.queue.release_pending();
+
+            registration.id
+        })
+        .unwrap_or(NO_OWNER)
+}
+
+/// Releases the references that other threads handed over to the current thread.
+pub(super) fn release_pending() {
+    // If the thread is exiting its queue is released by the registration.
+    let _ = REGISTRATION.try_with(|registration| registration.queue.release_pending());
+}
+
+/// Hands `deferred` over to the thread with id `owner`, or releases it right away if that thread
+/// exited.
+pub(super) fn defer(owner: usize, deferred: Deferred) {
+    let threads = lock(&THREADS);
+
+    match threads.get(&owner) {
+        Some(queue) => {
+            lock(&queue.deferred).push(deferred);
+            queue.pending.store(true, Ordering::Release);
+        }
+        None => {
+            drop(threads);
+
+            deferred.release();
+        }
+    }
+}
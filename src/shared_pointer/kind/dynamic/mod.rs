@@ -0,0 +1,407 @@
+use crate::shared_pointer::SharedPointer;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::AllocError;
+use crate::shared_pointer::kind::ArcK;
+use crate::shared_pointer::kind::RcK;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::SharedFallibleKind;
+use crate::shared_pointer::kind::SharedIntoInnerKind;
+use crate::shared_pointer::kind::SharedLockKind;
+use crate::shared_pointer::kind::SharedOnceKind;
+use crate::shared_pointer::kind::SharedPointerKind;
+use crate::shared_pointer::kind::SharedStatelessKind;
+use crate::shared_pointer::lock::SyncLock;
+use crate::shared_pointer::once::SyncOnceCell;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+#[cfg(feature = "coerce_unsized")]
+use core::marker::Unsize;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+#[cfg(feature = "coerce_unsized")]
+use core::ops::DispatchFromDyn;
+use core::ptr::NonNull;
+use core::sync::atomic::AtomicU8;
+use core::sync::atomic::Ordering;
+
+const MODE_UNSET: u8 = 0;
+const MODE_ATOMIC: u8 = 1;
+const MODE_NON_ATOMIC: u8 = 2;
+
+/// The [`DynMode`] of the process.
+///
+/// Once set it never changes, and no pointer is created before it is set. Every `DynK` pointer
+/// is therefore an [`Arc`] in the atomic mode, and an [`Rc`] in the non-atomic mode, which is
+/// what makes the raw parts of a pointer enough to rebuild it.
+static MODE: AtomicU8 = AtomicU8::new(MODE_UNSET);
+
+/// How [`DynK`] pointers count their references.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DynMode {
+    /// Reference counts are updated with atomic operations, as in [`ArcK`].
+    Atomic,
+    /// Reference counts are updated without atomic operations, as in [`RcK`].
+    NonAtomic,
+}
+
+impl DynMode {
+    #[inline(always)]
+    fn from_repr(repr: u8) -> DynMode {
+        match repr {
+            MODE_NON_ATOMIC => DynMode::NonAtomic,
+            _ => DynMode::Atomic,
+        }
+    }
+
+    #[inline(always)]
+    fn repr(self) -> u8 {
+        match self {
+            DynMode::Atomic => MODE_ATOMIC,
+            DynMode::NonAtomic => MODE_NON_ATOMIC,
+        }
+    }
+}
+
+/// [Type constructors](https://en.wikipedia.org/wiki/Type_constructor) for pointers whose
+/// reference counting is selected at runtime.
+///
+/// The [mode](DynMode) is process-global and is selected once, with [`DynK::set_mode()`], before
+/// the first `DynK` pointer is created. Until then it can still be changed, and if no mode was
+/// selected when the first pointer is created the process uses [`DynMode::Atomic`]. In the atomic
+/// mode `DynK` behaves as [`ArcK`], and in the non-atomic mode it behaves as [`RcK`].
+///
+/// Since the mode is only known at runtime, `DynK` is neither [`Send`] nor [`Sync`], and neither
+/// are its pointers. Once the atomic mode is proven by a [`DynAtomic`] token, pointers can be
+/// converted to and from [`ArcK`] pointers, which can cross threads, without copying their
+/// value nor changing their reference count.
+///
+/// ```rust
+/// use archery::*;
+///
+/// # let multithreaded = true;
+/// let mode = match multithreaded {
+///     true => DynMode::Atomic,
+///     false => DynMode::NonAtomic,
+/// };
+///
+/// DynK::set_mode(mode).unwrap();
+///
+/// let value: SharedPointer<_, DynK> = SharedPointer::new(1944);
+///
+/// assert_eq!(*value, 1944);
+///
+/// if let Some(atomic) = DynK::atomic() {
+///     let value: SharedPointer<_, ArcK> = atomic.into_arc(value);
+///
+///     std::thread::spawn(move || assert_eq!(*value, 1944)).join().unwrap();
+/// }
+/// ```
+#[derive(Default)]
+pub struct DynK {
+    /// An `Rc` cannot cross threads, so neither can a pointer whose mode might be non-atomic.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl DynK {
+    /// Selects the mode of the process. Returns the mode already in use if it is different from
+    /// `mode`, in which case it cannot be changed anymore.
+    ///
+    /// The mode can only be selected before the first `DynK` pointer is created.
+    pub fn set_mode(mode: DynMode) -> Result<(), DynMode> {
+        // The mode can only change once, from unset, so every thread that reads a set mode sees
+        // the same one, even with relaxed ordering.
+        match MODE.compare_exchange(MODE_UNSET, mode.repr(), Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => Ok(()),
+            Err(current) if current == mode.repr() => Ok(()),
+            Err(current) => Err(DynMode::from_repr(current)),
+        }
+    }
+
+    /// Gets the mode of the process. If no mode was selected yet, this selects
+    /// [`DynMode::Atomic`].
+    #[inline(always)]
+    #[must_use]
+    pub fn mode() -> DynMode {
+        match MODE.load(Ordering::Relaxed) {
+            MODE_UNSET => match DynK::set_mode(DynMode::Atomic) {
+                Ok(()) => DynMode::Atomic,
+                Err(mode) => mode,
+            },
+            repr => DynMode::from_repr(repr),
+        }
+    }
+
+    /// Returns a [`DynAtomic`] token if the mode of the process is [`DynMode::Atomic`]. If no
+    /// mode was selected yet, this selects [`DynMode::Atomic`].
+    #[inline(always)]
+    #[must_use]
+    pub fn atomic() -> Option<DynAtomic> {
+        match DynK::mode() {
+            DynMode::Atomic => Some(DynAtomic { _private: () }),
+            DynMode::NonAtomic => None,
+        }
+    }
+}
+
+/// Proof that the mode of [`DynK`] is [`DynMode::Atomic`], which is obtained from
+/// [`DynK::atomic()`].
+///
+/// In the atomic mode every `DynK` pointer is an [`Arc`], so it can be converted to and from an
+/// [`ArcK`] pointer, which can cross threads.
+#[derive(Clone, Copy, Debug)]
+pub struct DynAtomic {
+    _private: (),
+}
+
+// The token is not used by the methods, it only proves that the mode is atomic.
+#[allow(clippy::unused_self)]
+impl DynAtomic {
+    /// Converts a `DynK` pointer into an [`ArcK`] pointer to the same allocation. This does not
+    /// copy `T` nor change the reference count.
+    #[inline(always)]
+    #[must_use]
+    pub fn into_arc<T: ?Sized>(self, ptr: SharedPointer<T, DynK>) -> SharedPointer<T, ArcK> {
+        let (ptr, _) = SharedPointer::into_parts(ptr);
+
+        // The mode is atomic, so `ptr` is either static or the raw pointer of an `Arc`. Both
+        // `ArcK` and `DynK` rebuild those from the default kind instance.
+        SharedPointer::from_parts(ptr, ArcK::default())
+    }
+
+    /// Converts an [`ArcK`] pointer into a `DynK` pointer to the same allocation. This does not
+    /// copy `T` nor change the reference count.
+    #[inline(always)]
+    #[must_use]
+    pub fn into_dyn<T: ?Sized>(self, ptr: SharedPointer<T, ArcK>) -> SharedPointer<T, DynK> {
+        let (ptr, _) = SharedPointer::into_parts(ptr);
+
+        // See `into_arc()`.
+        SharedPointer::from_parts(ptr, DynK::default())
+    }
+}
+
+/// A pointer of kind [`DynK`]: an [`Rc`] in the non-atomic mode, or an [`Arc`] in the atomic
+/// mode.
+#[derive(Debug)]
+pub enum DynPointer<T: ?Sized> {
+    Rc(Rc<T>),
+    Arc(Arc<T>),
+}
+
+impl<T: ?Sized> Deref for DynPointer<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        match self {
+            DynPointer::Rc(rc) => rc,
+            DynPointer::Arc(arc) => arc,
+        }
+    }
+}
+
+unsafe impl SharedPointerKind for DynK {
+    type Pointer<T: ?Sized> = DynPointer<T>;
+
+    #[inline(always)]
+    fn new<T>(v: T) -> DynPointer<T> {
+        match DynK::mode() {
+            DynMode::Atomic => DynPointer::Arc(Arc::new(v)),
+            DynMode::NonAtomic => DynPointer::Rc(Rc::new(v)),
+        }
+    }
+
+    #[inline(always)]
+    fn from_box<T>(v: Box<T>) -> DynPointer<T> {
+        match DynK::mode() {
+            DynMode::Atomic => DynPointer::Arc(Arc::from(v)),
+            DynMode::NonAtomic => DynPointer::Rc(Rc::from(v)),
+        }
+    }
+
+    #[inline(always)]
+    fn new_uninit<T>() -> DynPointer<MaybeUninit<T>> {
+        match DynK::mode() {
+            DynMode::Atomic => DynPointer::Arc(Arc::new_uninit()),
+            DynMode::NonAtomic => DynPointer::Rc(Rc::new_uninit()),
+        }
+    }
+
+    #[inline(always)]
+    fn from_slice<T: Clone>(v: &[T]) -> DynPointer<[T]> {
+        match DynK::mode() {
+            DynMode::Atomic => DynPointer::Arc(Arc::from(v)),
+            DynMode::NonAtomic => DynPointer::Rc(Rc::from(v)),
+        }
+    }
+
+    #[inline(always)]
+    fn from_vec<T>(v: Vec<T>) -> DynPointer<[T]> {
+        match DynK::mode() {
+            DynMode::Atomic => DynPointer::Arc(Arc::from(v)),
+            DynMode::NonAtomic => DynPointer::Rc(Rc::from(v)),
+        }
+    }
+
+    #[inline(always)]
+    fn from_str(v: &str) -> DynPointer<str> {
+        match DynK::mode() {
+            DynMode::Atomic => DynPointer::Arc(Arc::from(v)),
+            DynMode::NonAtomic => DynPointer::Rc(Rc::from(v)),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the pointer does not match the mode of the process.
+    #[inline(always)]
+    fn into_raw<T: ?Sized>(ptr: DynPointer<T>) -> (NonNull<T>, DynK) {
+        let ptr: NonNull<T> = match (DynK::mode(), ptr) {
+            (DynMode::Atomic, DynPointer::Arc(arc)) => ArcK::into_raw(arc).0,
+            (DynMode::NonAtomic, DynPointer::Rc(rc)) => RcK::into_raw(rc).0,
+            (mode, _) => panic!("the pointer does not match the {mode:?} mode of `DynK`"),
+        };
+
+        (ptr, DynK::default())
+    }
+
+    #[inline(always)]
+    unsafe fn from_raw<T: ?Sized>(ptr: NonNull<T>, _: DynK) -> DynPointer<T> {
+        // SAFETY: By the caller's guarantee `ptr` was returned by `into_raw()`, which only
+        // accepts pointers that match the mode, and the mode never changes (see `MODE`).
+        unsafe {
+            match DynK::mode() {
+                DynMode::Atomic => DynPointer::Arc(ArcK::from_raw(ptr, ArcK::default())),
+                DynMode::NonAtomic => DynPointer::Rc(RcK::from_raw(ptr, RcK::default())),
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn clone<T: ?Sized>(ptr: &DynPointer<T>) -> DynPointer<T> {
+        match ptr {
+            DynPointer::Rc(rc) => DynPointer::Rc(Rc::clone(rc)),
+            DynPointer::Arc(arc) => DynPointer::Arc(Arc::clone(arc)),
+        }
+    }
+
+    #[inline(always)]
+    fn strong_count<T: ?Sized>(ptr: &DynPointer<T>) -> usize {
+        match ptr {
+            DynPointer::Rc(rc) => Rc::strong_count(rc),
+            DynPointer::Arc(arc) => Arc::strong_count(arc),
+        }
+    }
+
+    #[inline(always)]
+    fn get_mut<T: ?Sized>(ptr: &mut DynPointer<T>) -> Option<&mut T> {
+        match ptr {
+            DynPointer::Rc(rc) => Rc::get_mut(rc),
+            DynPointer::Arc(arc) => Arc::get_mut(arc),
+        }
+    }
+
+    #[inline(always)]
+    fn make_mut<T: Clone>(ptr: &mut DynPointer<T>) -> &mut T {
+        match ptr {
+            DynPointer::Rc(rc) => Rc::make_mut(rc),
+            DynPointer::Arc(arc) => Arc::make_mut(arc),
+        }
+    }
+
+    #[inline(always)]
+    fn make_mut_with<T>(ptr: &mut DynPointer<T>, f: impl FnOnce(&T) -> T) -> &mut T {
+        match ptr {
+            DynPointer::Rc(rc) => RcK::make_mut_with(rc, f),
+            DynPointer::Arc(arc) => ArcK::make_mut_with(arc, f),
+        }
+    }
+
+    #[inline(always)]
+    fn try_unwrap<T>(ptr: DynPointer<T>) -> Result<T, DynPointer<T>> {
+        match ptr {
+            DynPointer::Rc(rc) => Rc::try_unwrap(rc).map_err(DynPointer::Rc),
+            DynPointer::Arc(arc) => Arc::try_unwrap(arc).map_err(DynPointer::Arc),
+        }
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+unsafe impl SharedFallibleKind for DynK {
+    #[inline(always)]
+    fn try_new<T>(v: T) -> Result<DynPointer<T>, AllocError> {
+        match DynK::mode() {
+            DynMode::Atomic => ArcK::try_new(v).map(DynPointer::Arc),
+            DynMode::NonAtomic => RcK::try_new(v).map(DynPointer::Rc),
+        }
+    }
+
+    #[inline(always)]
+    fn try_from_box<T>(v: Box<T>) -> Result<DynPointer<T>, AllocError> {
+        match DynK::mode() {
+            DynMode::Atomic => ArcK::try_from_box(v).map(DynPointer::Arc),
+            DynMode::NonAtomic => RcK::try_from_box(v).map(DynPointer::Rc),
+        }
+    }
+}
+
+unsafe impl SharedIntoInnerKind for DynK {
+    #[inline(always)]
+    fn into_inner<T>(ptr: DynPointer<T>) -> Option<T> {
+        match ptr {
+            DynPointer::Rc(rc) => Rc::into_inner(rc),
+            DynPointer::Arc(arc) => Arc::into_inner(arc),
+        }
+    }
+}
+
+impl SharedLockKind for DynK {
+    type Lock<T> = SyncLock<T>;
+}
+
+impl SharedOnceKind for DynK {
+    type OnceCell<T> = SyncOnceCell<T>;
+}
+
+unsafe impl SharedStatelessKind for DynK {}
+
+impl<T: ?Sized> From<DynPointer<T>> for SharedPointer<T, DynK> {
+    /// # Panics
+    ///
+    /// Panics if the pointer does not match the mode of the process.
+    #[inline(always)]
+    fn from(v: DynPointer<T>) -> SharedPointer<T, DynK> {
+        SharedPointer::from_inner_pointer(v)
+    }
+}
+
+impl<T: ?Sized> From<SharedPointer<T, DynK>> for DynPointer<T> {
+    #[inline(always)]
+    fn from(v: SharedPointer<T, DynK>) -> DynPointer<T> {
+        SharedPointer::into_inner_pointer(v)
+    }
+}
+
+#[cfg(feature = "coerce_unsized")]
+impl<T, U> DispatchFromDyn<SharedPointer<U, DynK>> for SharedPointer<T, DynK>
+where
+    T: Unsize<U> + ?Sized,
+    U: ?Sized,
+{
+}
+
+impl Debug for DynK {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("DynK")
+    }
+}
+
+#[cfg(test)]
+mod test;
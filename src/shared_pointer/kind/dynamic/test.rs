@@ -0,0 +1,307 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use static_assertions::assert_impl_all;
+use static_assertions::assert_not_impl_any;
+use std::cell::Cell;
+use std::string::ToString;
+
+type PointerKind = DynK;
+
+assert_not_impl_any!(DynK: Send, Sync);
+assert_not_impl_any!(SharedPointer<i32, DynK>: Send, Sync);
+assert_impl_all!(DynAtomic: Send, Sync);
+
+#[test]
+fn test_from_box_t() {
+    let ptr = PointerKind::from_box(Box::new(42));
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn test_try_new() {
+    let ptr = PointerKind::try_new::<i32>(42).unwrap();
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn test_try_from_box() {
+    let ptr = PointerKind::try_from_box::<i32>(Box::new(42)).unwrap();
+
+    assert_eq!(*ptr, 42);
+}
+
+#[test]
+fn test_new_uninit_assume_init() {
+    let mut ptr = PointerKind::new_uninit::<i32>();
+
+    PointerKind::get_mut(&mut ptr).unwrap().write(42);
+
+    let (raw, kind) = PointerKind::into_raw(ptr);
+
+    // SAFETY: The value was just initialized, and `MaybeUninit<i32>` has the layout of `i32`.
+    let ptr = unsafe { PointerKind::from_raw(raw.cast::<i32>(), kind) };
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
+
+#[test]
+fn test_from_slice() {
+    let ptr = PointerKind::from_slice::<i32>(&[1, 2, 3]);
+
+    assert_eq!(*ptr, [1, 2, 3]);
+}
+
+#[test]
+fn test_from_vec() {
+    let ptr = PointerKind::from_vec::<i32>(vec![1, 2, 3]);
+
+    assert_eq!(*ptr, [1, 2, 3]);
+}
+
+#[test]
+fn test_from_str() {
+    let ptr = PointerKind::from_str("hello from test_from_str");
+
+    assert_eq!(&*ptr, "hello from test_from_str");
+}
+
+#[test]
+fn test_unsized() {
+    let mut ptr = PointerKind::from_vec::<i32>(vec![1, 2, 3]);
+
+    PointerKind::get_mut(&mut ptr).unwrap()[0] = 0;
+
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+    assert_eq!(PointerKind::get_mut(&mut ptr), None);
+    assert_eq!(NonNull::from(&*ptr), NonNull::from(&*ptr_clone));
+    assert_eq!(*ptr_clone, [0, 2, 3]);
+
+    drop(ptr);
+
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
+}
+
+#[test]
+fn test_into_raw_from_raw() {
+    let ptr = PointerKind::from_str("hello from test_into_raw_from_raw");
+    let (raw, kind) = PointerKind::into_raw(ptr);
+
+    // SAFETY: `raw` points to a live value owned by the strong reference we just released.
+    assert_eq!(unsafe { raw.as_ref() }, "hello from test_into_raw_from_raw");
+
+    // SAFETY: `raw` and `kind` were returned by `into_raw()`.
+    let ptr = unsafe { PointerKind::from_raw(raw, kind) };
+
+    assert_eq!(NonNull::from(&*ptr), raw);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
+
+#[test]
+fn test_into_raw_from_raw_unsize() {
+    let ptr = PointerKind::new::<i32>(42);
+    let (raw, kind) = PointerKind::into_raw(ptr);
+    let raw: NonNull<dyn ToString> = raw;
+
+    // SAFETY: `raw` was returned by `into_raw()` and then unsized.
+    let ptr = unsafe { PointerKind::from_raw(raw, kind) };
+
+    assert_eq!(ptr.to_string(), "42");
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+}
+
+#[test]
+#[should_panic(expected = "the pointer does not match the Atomic mode of `DynK`")]
+fn test_into_raw_mode_mismatch() {
+    let _ = PointerKind::into_raw::<i32>(DynPointer::Rc(alloc::rc::Rc::new(42)));
+}
+
+#[test]
+fn test_mode() {
+    // Tests run in parallel in the same process, so they can only use the default mode.
+    assert_eq!(DynK::mode(), DynMode::Atomic);
+    assert_eq!(DynK::set_mode(DynMode::Atomic), Ok(()));
+    assert_eq!(DynK::set_mode(DynMode::NonAtomic), Err(DynMode::Atomic));
+    assert!(matches!(PointerKind::new::<i32>(42), DynPointer::Arc(_)));
+}
+
+#[test]
+fn test_try_unwrap() {
+    let ptr = PointerKind::new::<i32>(42);
+
+    assert_eq!(PointerKind::try_unwrap(ptr).unwrap(), 42);
+
+    let ptr = PointerKind::new::<i32>(42);
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    let ptr_clone = PointerKind::try_unwrap(ptr_clone).unwrap_err();
+    let ptr = PointerKind::try_unwrap(ptr).unwrap_err();
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(*ptr_clone, 42);
+}
+
+#[test]
+fn test_get_mut() {
+    let mut ptr = PointerKind::new::<i32>(42);
+
+    *PointerKind::get_mut(&mut ptr).unwrap() += 1;
+
+    assert_eq!(*ptr, 43);
+
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(PointerKind::get_mut(&mut ptr), None);
+    assert_eq!(PointerKind::get_mut(&mut ptr_clone), None);
+
+    drop(ptr);
+
+    *PointerKind::get_mut(&mut ptr_clone).unwrap() += 1;
+
+    assert_eq!(*ptr_clone, 44);
+}
+
+#[test]
+fn test_make_mut() {
+    let mut ptr = PointerKind::new::<i32>(42);
+
+    *PointerKind::make_mut(&mut ptr) += 1;
+
+    assert_eq!(*ptr, 43);
+
+    // Clone to force make_mut to clone the data.
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    *PointerKind::make_mut(&mut ptr_clone) += 1;
+
+    assert_eq!(*ptr, 43);
+    assert_eq!(*ptr_clone, 44);
+
+    *PointerKind::make_mut(&mut ptr) *= 2;
+
+    assert_eq!(*ptr, 2 * 43);
+    assert_eq!(*ptr_clone, 44);
+}
+
+#[test]
+fn test_make_mut_with() {
+    let mut ptr = PointerKind::new::<i32>(42);
+
+    *PointerKind::make_mut_with(&mut ptr, |_| unreachable!()) += 1;
+
+    assert_eq!(*ptr, 43);
+
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    *PointerKind::make_mut_with(&mut ptr_clone, |v| v * 10) += 1;
+
+    assert_eq!(*ptr, 43);
+    assert_eq!(*ptr_clone, 431);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
+}
+
+#[test]
+fn test_make_mut_panic_safety() {
+    use std::panic::AssertUnwindSafe;
+    use std::panic::catch_unwind;
+
+    struct PanicOnClone(#[allow(dead_code)] u32);
+
+    impl Clone for PanicOnClone {
+        fn clone(&self) -> Self {
+            panic!("intentional panic in T::clone");
+        }
+    }
+
+    let ptr = PointerKind::new::<PanicOnClone>(PanicOnClone(42));
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    // Trigger `make_mut` on a shared handle so it must clone via `T::clone` (which panics).
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        PointerKind::make_mut(&mut ptr_clone);
+    }));
+
+    assert!(result.is_err(), "make_mut should have unwound");
+
+    // A panic in `T::clone` must not desync the strong count: both handles must still own
+    // their strong reference.
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 2);
+}
+
+#[test]
+fn test_into_inner() {
+    let ptr = PointerKind::new::<i32>(42);
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(PointerKind::into_inner(ptr), None);
+    assert_eq!(PointerKind::into_inner(ptr_clone), Some(42));
+}
+
+#[test]
+fn test_clone() {
+    let ptr = PointerKind::new::<Cell<i32>>(Cell::new(42));
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+
+    ptr_clone.set(3);
+
+    assert_eq!(ptr.get(), 3);
+
+    drop(ptr);
+
+    assert_eq!(ptr_clone.get(), 3);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(format!("{:?}", PointerKind::default()), "DynK");
+}
+
+#[test]
+fn test_atomic_into_arc_into_dyn() {
+    let atomic = DynK::atomic().unwrap();
+    let ptr: SharedPointer<i32, DynK> = SharedPointer::new(42);
+    let ptr_clone = SharedPointer::clone(&ptr);
+    let arc: SharedPointer<i32, ArcK> = atomic.into_arc(ptr_clone);
+
+    assert!(SharedPointer::ptr_eq(&ptr, &arc));
+    assert_eq!(SharedPointer::strong_count(&ptr), 2);
+
+    let arc = std::thread::spawn(move || {
+        assert_eq!(*arc, 42);
+
+        arc
+    })
+    .join()
+    .unwrap();
+    let ptr_clone: SharedPointer<i32, DynK> = atomic.into_dyn(arc);
+
+    assert!(SharedPointer::ptr_eq(&ptr, &ptr_clone));
+
+    drop(ptr_clone);
+
+    assert_eq!(SharedPointer::strong_count(&ptr), 1);
+}
+
+#[test]
+fn test_atomic_into_arc_static() {
+    static VALUE: i32 = 42;
+
+    let atomic = DynK::atomic().unwrap();
+    let arc: SharedPointer<i32, ArcK> = atomic.into_arc(SharedPointer::from_static(&VALUE));
+
+    assert!(SharedPointer::is_static(&arc));
+    assert_eq!(*arc, 42);
+}
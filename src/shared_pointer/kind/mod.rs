@@ -1,34 +1,50 @@
 use core::fmt::Debug;
+use core::ptr::NonNull;
 
 /// Trait for [type constructors](https://en.wikipedia.org/wiki/Type_constructor) of
 /// reference-counting pointers.
 ///
-/// # Type-parameter invariant
+/// A kind is a type constructor of a smart pointer type, [`Self::Pointer<T>`], such as
+/// [`Rc<T>`](alloc::rc::Rc) for [`RcK`]. The methods of this trait are the operations of that
+/// pointer type that [`SharedPointer`] needs.
 ///
-/// Each instance of a `SharedPointerKind` implementer is logically associated with a fixed inner
-/// type `T`, chosen when the instance is constructed via [`Self::new`], [`Self::from_box`], or
-/// [`Self::clone`]. All subsequent calls of the `unsafe` methods on that instance must be called
-/// with the same `T`. Callers of the `unsafe` methods are responsible for upholding this
-/// invariant.
+/// # Raw parts
 ///
-/// # Wrapping in a safe API
+/// A [`SharedPointer<T, P>`][SharedPointer] does not store a `P::Pointer<T>`, whose layout is not
+/// known, but its raw parts, as returned by [`Self::into_raw`]: a [`NonNull<T>`], which is what
+/// lets the compiler coerce the pointer to an unsized `T`, and an instance of the kind itself,
+/// which holds the state of the pointer besides its address, if any (for instance the allocator
+/// of `RcAllocK`). For most kinds this is a zero-sized type, so a `SharedPointer` is a single
+/// pointer.
 ///
-/// Implementers of this trait do not know the `T` they hold, so their [`Send`]/[`Sync`] impls
-/// (if any) are unconditional. For example, [`ArcK`] is always [`Send`] + [`Sync`], but it is
-/// only actually safe to send or share across threads when the inner `T` is itself [`Send`] +
-/// [`Sync`].
+/// The kind instance also decides whether `SharedPointer<T, P>` is [`Send`]/[`Sync`], which
+/// requires `P: Send`/`P: Sync` besides `T: Send + Sync`. A kind whose pointers cannot cross
+/// threads, like [`RcK`], must therefore not be [`Send`] nor [`Sync`].
 ///
-/// A safe wrapper around a `SharedPointerKind` implementer must therefore gate its own
-/// [`Send`]/[`Sync`] impls on `T: Send + Sync`. [`SharedPointer<T, P>`][SharedPointer] achieves
-/// this by including a [`PhantomData<T>`][PhantomData] field, so the compiler only derives
-/// [`Send`]/[`Sync`] for `SharedPointer<T, P>` when both `T` and `P` are appropriate.
+/// # Static values
+///
+/// [`SharedPointer::from_static()`] points to a value that is not in a reference-counted
+/// allocation. Those pointers are handled by `SharedPointer` and never reach the kind: their kind
+/// instance is the [`Default`] one.
 ///
 /// # Safety
 ///
+/// The pointer returned by [`Self::into_raw`] must point to the value, and be valid for reads for
+/// as long as the reference it owns is alive. Unless the target aligns `usize` to a single byte,
+/// its address must be a multiple of two, which is the case for values stored after a reference
+/// count, since `SharedPointer` uses the lowest bit to tell static values apart.
+/// [`Self::from_raw`] must accept the raw parts after the pointer went through an unsizing
+/// coercion, or was cast between `MaybeUninit<T>` and `T`.
+///
+/// `SharedPointer` rebuilds a [`Self::Pointer<T>`] with [`Self::from_raw`] to call the methods
+/// that take it by reference, from a bitwise copy of its kind instance that is never dropped.
+/// Only the kind instance returned with a pointer is required to rebuild it. Kinds whose
+/// [`Default`] instance can rebuild any of their pointers implement [`SharedStatelessKind`].
+///
 /// `T` may be `!`[`Unpin`], and [`SharedPointer`] may be held in a pinned
 /// form ([`Pin`]`<SharedPointer<T, Self>>`).
 /// As such, the implementation of this trait must uphold the pinning invariants
-/// for `T` while it's held in `Self`. Specifically, this necessitates the
+/// for `T` while it's held in `Self::Pointer<T>`. Specifically, this necessitates the
 /// following:
 ///
 /// - `&mut T` is only exposed through the trait methods returning `&mut T`.
@@ -36,82 +52,443 @@ use core::fmt::Debug;
 /// - The implementor must not move out the contained `T` unless the semantics
 ///   of trait methods demands that.
 ///
-/// - [`Self::drop`] drops `T` in place.
+/// - Dropping the last `Self::Pointer<T>` drops `T` in place.
 ///
 /// [SharedPointer]: crate::shared_pointer::SharedPointer
 /// [`SharedPointer`]: crate::shared_pointer::SharedPointer
+/// [`SharedPointer::from_static()`]: crate::shared_pointer::SharedPointer::from_static
 /// [`Pin`]: core::pin::Pin
-/// [PhantomData]: core::marker::PhantomData
-pub unsafe trait SharedPointerKind: Sized + Debug {
-    fn new<T>(v: T) -> Self;
-    fn from_box<T>(v: Box<T>) -> Self;
+pub unsafe trait SharedPointerKind: Sized + Debug + Default {
+    /// The smart pointer type this kind is a type constructor of (for example
+    /// [`Rc<T>`](alloc::rc::Rc) for [`RcK`]).
+    type Pointer<T: ?Sized>;
 
-    /// # Safety
-    ///
-    /// `Self` must have been constructed with the same `T`. See the
-    /// [type-parameter invariant](SharedPointerKind#type-parameter-invariant).
-    unsafe fn as_ptr<T>(&self) -> *const T;
+    fn new<T>(v: T) -> Self::Pointer<T>;
+    fn from_box<T>(v: Box<T>) -> Self::Pointer<T>;
+    fn new_uninit<T>() -> Self::Pointer<MaybeUninit<T>>;
+    fn from_slice<T: Clone>(v: &[T]) -> Self::Pointer<[T]>;
+    fn from_vec<T>(v: Vec<T>) -> Self::Pointer<[T]>;
+    fn from_str(v: &str) -> Self::Pointer<str>;
+
+    /// Gives up ownership of `ptr`, returning its [raw parts](#raw-parts). The reference owned by
+    /// `ptr` is kept, and can be recovered with [`Self::from_raw`].
+    fn into_raw<T: ?Sized>(ptr: Self::Pointer<T>) -> (NonNull<T>, Self);
 
+    /// Takes back ownership of the [raw parts](#raw-parts) returned by [`Self::into_raw`].
+    ///
     /// # Safety
     ///
-    /// `Self` must have been constructed with the same `T`. See the
-    /// [type-parameter invariant](SharedPointerKind#type-parameter-invariant).
-    unsafe fn deref<T>(&self) -> &T;
+    /// `ptr` must have been returned by [`Self::into_raw`] of a `Self::Pointer<U>`, where `U` is
+    /// either `T`, a type that unsizes to `T`, or `MaybeUninit<T>` with an initialized value (or
+    /// the other way around). `kind` must be the kind instance returned with it, or the
+    /// [`Default`] one. Every call of this method must be matched by a reference owned by `ptr`.
+    unsafe fn from_raw<T: ?Sized>(ptr: NonNull<T>, kind: Self) -> Self::Pointer<T>;
+
+    #[must_use]
+    fn clone<T: ?Sized>(ptr: &Self::Pointer<T>) -> Self::Pointer<T>;
+
+    fn strong_count<T: ?Sized>(ptr: &Self::Pointer<T>) -> usize;
+
+    fn get_mut<T: ?Sized>(ptr: &mut Self::Pointer<T>) -> Option<&mut T>;
+
+    fn make_mut<T: Clone>(ptr: &mut Self::Pointer<T>) -> &mut T;
+
+    /// Like [`Self::make_mut`], but uses `f` to clone the value if `ptr` is not the only
+    /// reference to its allocation.
+    fn make_mut_with<T>(ptr: &mut Self::Pointer<T>, f: impl FnOnce(&T) -> T) -> &mut T;
+
+    fn try_unwrap<T>(ptr: Self::Pointer<T>) -> Result<T, Self::Pointer<T>>;
+}
 
+/// Trait for [`SharedPointerKind`]s that support weak references.
+///
+/// Not every reference-counting pointer keeps a weak count (for instance
+/// [`triomphe::Arc`](https://docs.rs/triomphe/latest/triomphe/struct.Arc.html) does not), so this
+/// is a separate trait: kinds that cannot support weak references simply do not implement it, and
+/// [`SharedWeak`] cannot be used with them.
+///
+/// As with strong pointers, a [`SharedWeak`] stores the [raw parts](SharedPointerKind#raw-parts)
+/// of a [`Self::Weak<T>`]. Weak pointers that are not associated with any allocation, and the
+/// ones downgraded from [static values](SharedPointerKind#static-values), are handled by
+/// `SharedWeak` and never reach the kind.
+///
+/// # Safety
+///
+/// Besides the requirements of [`SharedPointerKind`], which also apply to weak pointers and
+/// [`Self::weak_into_raw`], a weak pointer must not keep the `T` alive: `T` is dropped when the
+/// last strong pointer is dropped, even if weak pointers remain.
+///
+/// [`SharedWeak`]: crate::shared_pointer::weak::SharedWeak
+pub unsafe trait SharedWeakKind: SharedPointerKind {
+    /// The weak counterpart of [`SharedPointerKind::Pointer<T>`] (for example
+    /// [`rc::Weak<T>`](alloc::rc::Weak) for [`RcK`]).
+    type Weak<T: ?Sized>;
+
+    /// Creates a pointer to the value returned by `f`, which is given a weak pointer to the
+    /// allocation being created. Upgrading that weak pointer inside `f` fails.
+    fn new_cyclic<T>(f: impl FnOnce(&Self::Weak<T>) -> T) -> Self::Pointer<T>;
+
+    fn downgrade<T: ?Sized>(ptr: &Self::Pointer<T>) -> Self::Weak<T>;
+
+    fn weak_count<T: ?Sized>(ptr: &Self::Pointer<T>) -> usize;
+
+    fn upgrade<T: ?Sized>(weak: &Self::Weak<T>) -> Option<Self::Pointer<T>>;
+
+    /// Like [`SharedPointerKind::into_raw`], for weak pointers.
+    fn weak_into_raw<T: ?Sized>(weak: Self::Weak<T>) -> (NonNull<T>, Self);
+
+    /// Like [`SharedPointerKind::from_raw`], for weak pointers.
+    ///
     /// # Safety
     ///
-    /// `Self` must have been constructed with the same `T`. See the
-    /// [type-parameter invariant](SharedPointerKind#type-parameter-invariant).
-    unsafe fn try_unwrap<T>(self) -> Result<T, Self>;
+    /// The raw parts must have been returned by [`Self::weak_into_raw`], as for
+    /// [`SharedPointerKind::from_raw`].
+    unsafe fn weak_from_raw<T: ?Sized>(ptr: NonNull<T>, kind: Self) -> Self::Weak<T>;
 
-    /// # Safety
+    /// Number of strong pointers to the allocation of `weak`.
+    fn weak_strong_count<T: ?Sized>(weak: &Self::Weak<T>) -> usize;
+
+    /// Number of weak pointers to the allocation of `weak`, or zero if there are no strong
+    /// pointers left.
+    fn weak_weak_count<T: ?Sized>(weak: &Self::Weak<T>) -> usize;
+
+    #[must_use]
+    fn weak_clone<T: ?Sized>(weak: &Self::Weak<T>) -> Self::Weak<T>;
+}
+
+/// Trait for [`SharedPointerKind`]s that can report memory allocation failures instead of
+/// aborting.
+///
+/// This is implemented by [`ArcTK`](crate::ArcTK) and [`BiasedArcK`](crate::BiasedArcK). [`RcK`]
+/// and [`ArcK`] rely on the unstable fallible allocation API of the standard library, so they
+/// only implement it with the `allocator_api` feature, which requires a nightly compiler.
+///
+/// # Safety
+///
+/// The implementation must uphold the same requirements as [`SharedPointerKind`]. The methods of
+/// this trait must return an [`AllocError`] if the memory allocation fails, and never abort.
+pub unsafe trait SharedFallibleKind: SharedPointerKind {
+    /// Like [`SharedPointerKind::new`], but returns an [`AllocError`] instead of aborting if the
+    /// memory allocation fails.
+    fn try_new<T>(v: T) -> Result<Self::Pointer<T>, AllocError>;
+
+    /// Like [`SharedPointerKind::from_box`], but returns an [`AllocError`] instead of aborting if
+    /// the memory allocation fails.
+    fn try_from_box<T>(v: Box<T>) -> Result<Self::Pointer<T>, AllocError>;
+}
+
+/// Trait for [`SharedPointerKind`]s that release the last strong reference to a value in the
+/// thread that drops it, which is what [`SharedPointer::into_inner()`] needs to return the value.
+///
+/// This is implemented by every kind except [`BiasedArcK`](crate::BiasedArcK), where a thread
+/// other than the one that created the value can hand what might be the last reference over to
+/// that thread instead of releasing it.
+///
+/// # Safety
+///
+/// Besides the requirements of [`SharedPointerKind`], if [`Self::into_inner`] is called on every
+/// strong pointer to an allocation, exactly one of the calls must return `Some`, even if they race
+/// with each other.
+///
+/// [`SharedPointer::into_inner()`]: crate::shared_pointer::SharedPointer::into_inner
+pub unsafe trait SharedIntoInnerKind: SharedPointerKind {
+    /// Like [`SharedPointerKind::try_unwrap`], but drops `ptr` if it is not the only strong
+    /// reference. If this is called on every strong reference to an allocation, exactly one of
+    /// the calls returns `Some`, even if they race with each other.
+    fn into_inner<T>(ptr: Self::Pointer<T>) -> Option<T>;
+}
+
+/// Trait for [`SharedPointerKind`]s that can allocate a [`HeaderSlice`], which is what
+/// [`ThinSharedPointer`] needs.
+///
+/// A `HeaderSlice` is a dynamically-sized type that is not a slice nor a string slice, so it
+/// cannot be created with the other constructors of [`SharedPointerKind`].
+///
+/// # Safety
+///
+/// Besides the requirements of [`SharedStatelessKind`], the pointer returned by
+/// [`Self::from_header_and_iter`] must point to a valid `HeaderSlice` holding the given header and
+/// items. A `ThinSharedPointer` only stores the address of the value, which is why it needs a
+/// stateless kind.
+///
+/// [`HeaderSlice`]: crate::shared_pointer::thin::HeaderSlice
+/// [`ThinSharedPointer`]: crate::shared_pointer::thin::ThinSharedPointer
+pub unsafe trait SharedThinKind: SharedStatelessKind {
+    /// Creates a pointer to a [`HeaderSlice`] with `header` and the items yielded by `items`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` does not yield exactly as many items as its length.
     ///
-    /// `Self` must have been constructed with the same `T`. See the
-    /// [type-parameter invariant](SharedPointerKind#type-parameter-invariant).
-    unsafe fn get_mut<T>(&mut self) -> Option<&mut T>;
+    /// [`HeaderSlice`]: crate::shared_pointer::thin::HeaderSlice
+    fn from_header_and_iter<H, T, I>(header: H, items: I) -> Self::Pointer<HeaderSlice<H, T>>
+    where
+        I: ExactSizeIterator<Item = T>;
 
+    /// Adds a strong reference to the [`HeaderSlice`] pointed to by `ptr`, which is owned by the
+    /// caller.
+    ///
+    /// The `thin_*` methods are what [`ThinSharedPointer`] is made of, and they take the address
+    /// it stores. By default they rebuild a [`SharedPointerKind::Pointer`] with
+    /// [`SharedPointerKind::from_raw`], which does not allocate, but a kind can implement them
+    /// directly on the allocation.
+    ///
     /// # Safety
     ///
-    /// `Self` must have been constructed with the same `T`. See the
-    /// [type-parameter invariant](SharedPointerKind#type-parameter-invariant).
-    unsafe fn make_mut<T: Clone>(&mut self) -> &mut T;
+    /// For all the `thin_*` methods, `ptr` and the [`Default`] kind instance must be the raw parts
+    /// of a pointer built by this kind, whose strong reference is alive.
+    ///
+    /// [`HeaderSlice`]: crate::shared_pointer::thin::HeaderSlice
+    /// [`ThinSharedPointer`]: crate::shared_pointer::thin::ThinSharedPointer
+    #[inline(always)]
+    unsafe fn thin_clone<H, T>(ptr: NonNull<HeaderSlice<H, T>>) {
+        // SAFETY: By the caller's guarantee.
+        let ptr = ManuallyDrop::new(unsafe { Self::from_raw(ptr, Self::default()) });
 
+        mem::forget(Self::clone(&ptr));
+    }
+
+    /// Releases the strong reference of `ptr`, dropping the [`HeaderSlice`] if it was the last
+    /// one.
+    ///
     /// # Safety
     ///
-    /// `Self` must have been constructed with the same `T`. See the
-    /// [type-parameter invariant](SharedPointerKind#type-parameter-invariant).
-    unsafe fn strong_count<T>(&self) -> usize;
+    /// See [`Self::thin_clone`]. The strong reference of `ptr` is given away.
+    ///
+    /// [`HeaderSlice`]: crate::shared_pointer::thin::HeaderSlice
+    #[inline(always)]
+    unsafe fn thin_drop<H, T>(ptr: NonNull<HeaderSlice<H, T>>) {
+        // SAFETY: By the caller's guarantee.
+        drop(unsafe { Self::from_raw(ptr, Self::default()) });
+    }
 
-    /// The returned `Self` inherits the same type-parameter `T` as `self`.
+    /// Number of strong pointers to the [`HeaderSlice`] pointed to by `ptr`.
     ///
     /// # Safety
     ///
-    /// `Self` must have been constructed with the same `T`. See the
-    /// [type-parameter invariant](SharedPointerKind#type-parameter-invariant).
+    /// See [`Self::thin_clone`].
+    ///
+    /// [`HeaderSlice`]: crate::shared_pointer::thin::HeaderSlice
+    #[inline(always)]
     #[must_use]
-    unsafe fn clone<T>(&self) -> Self;
+    unsafe fn thin_strong_count<H, T>(ptr: NonNull<HeaderSlice<H, T>>) -> usize {
+        // SAFETY: By the caller's guarantee.
+        let ptr = ManuallyDrop::new(unsafe { Self::from_raw(ptr, Self::default()) });
 
+        Self::strong_count(&ptr)
+    }
+
+    /// Returns a mutable reference to the [`HeaderSlice`] pointed to by `ptr`, if `ptr` is its
+    /// only pointer.
+    ///
     /// # Safety
     ///
-    /// `Self` must have been constructed with the same `T`. See the
-    /// [type-parameter invariant](SharedPointerKind#type-parameter-invariant).
+    /// See [`Self::thin_clone`]. The strong reference of `ptr` must be alive, and not be used by
+    /// anything else, for the lifetime `'a`.
     ///
-    /// This method must be called at most once per instance, when `Self` is being disposed of.
-    /// After the call, `Self` must not be used again.
-    unsafe fn drop<T>(&mut self);
+    /// [`HeaderSlice`]: crate::shared_pointer::thin::HeaderSlice
+    #[inline(always)]
+    #[must_use]
+    unsafe fn thin_get_mut<'a, H, T>(
+        ptr: NonNull<HeaderSlice<H, T>>,
+    ) -> Option<&'a mut HeaderSlice<H, T>> {
+        // SAFETY: By the caller's guarantee.
+        let mut ptr = ManuallyDrop::new(unsafe { Self::from_raw(ptr, Self::default()) });
+
+        // SAFETY: The value is kept alive, and only accessed through the returned reference, for
+        // the lifetime `'a`.
+        Self::get_mut(&mut ptr).map(|v| unsafe { &mut *ptr::from_mut(v) })
+    }
+}
+
+/// Marker trait for [`SharedPointerKind`]s whose [`Default`] instance can rebuild any of their
+/// pointers, since the kind instance holds no state a pointer depends on. This is the case for
+/// all kinds except the ones with a custom allocator, such as `RcAllocK`, whose kind instance
+/// holds the allocator that owns the memory.
+///
+/// This is what converting a [`SharedPointer`] into a bare pointer needs, as
+/// [`SharedPointer::into_raw()`] and [`SharedPointer::into_waker()`] do.
+///
+/// # Safety
+///
+/// Besides the requirements of [`SharedPointerKind`], for raw parts `(ptr, kind)` returned by
+/// [`SharedPointerKind::into_raw`], `Self::from_raw(ptr, Self::default())` must be equivalent to
+/// `Self::from_raw(ptr, kind)`, and dropping `kind` must have no effect.
+///
+/// [`SharedPointer`]: crate::shared_pointer::SharedPointer
+/// [`SharedPointer::into_raw()`]: crate::shared_pointer::SharedPointer::into_raw
+/// [`SharedPointer::into_waker()`]: crate::shared_pointer::SharedPointer::into_waker
+pub unsafe trait SharedStatelessKind: SharedPointerKind {}
+
+/// Marker trait for [`SharedPointerKind`]s whose reference counts are updated atomically, such as
+/// [`ArcK`] and [`ArcTK`](crate::ArcTK).
+///
+/// This is what [`SyncSharedPointerCell`] needs, since it clones and drops pointers concurrently
+/// from any thread.
+///
+/// # Safety
+///
+/// Besides the requirements of [`SharedPointerKind`], pointers to a `T: Send + Sync` must be safe
+/// to clone and drop concurrently from multiple threads. Cloning must not block nor run code
+/// provided by the user of the kind (such as a custom allocator), since [`SyncSharedPointerCell`]
+/// clones pointers while holding a lock.
+///
+/// [`SyncSharedPointerCell`]: crate::shared_pointer::cell::SyncSharedPointerCell
+pub unsafe trait SharedAtomicKind: SharedPointerKind + Send + Sync {}
+
+/// Trait for [`SharedPointerKind`]s that have a matching lock type, so that code generic over the
+/// pointer kind can share mutable values without paying for synchronization it does not need.
+///
+/// A `SharedPointer<P::Lock<T>, P>` is an `Rc<RefCell<T>>` for [`RcK`], and an
+/// `Arc<SyncLock<T>>` for atomic kinds such as [`ArcK`]. All locks have the same API, given by
+/// [`SharedLock`].
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+///
+/// struct Counter<P: SharedLockKind> {
+///     count: SharedPointer<P::Lock<u32>, P>,
+/// }
+///
+/// impl<P: SharedLockKind> Counter<P> {
+///     fn increment(&self) {
+///         *self.count.lock() += 1;
+///     }
+/// }
+///
+/// let counter: Counter<RcK> = Counter { count: SharedPointer::new(SharedLock::new(0)) };
+///
+/// counter.increment();
+///
+/// assert_eq!(*counter.count.read(), 1);
+/// ```
+///
+/// [`SharedLock`]: crate::shared_pointer::lock::SharedLock
+pub trait SharedLockKind: SharedPointerKind {
+    /// Lock type for values shared with this kind of pointer: a
+    /// [`RefCell`](core::cell::RefCell) for non-atomic kinds, or a
+    /// [`SyncLock`](crate::shared_pointer::lock::SyncLock) for kinds whose pointers can be shared
+    /// between threads.
+    type Lock<T>: SharedLock<T>;
+}
+
+/// Trait for [`SharedPointerKind`]s that have a matching once-cell type, used by [`SharedOnce`]
+/// and [`SharedLazy`].
+///
+/// The once-cell is a [`OnceCell`](core::cell::OnceCell) for non-atomic kinds, like [`RcK`], and
+/// a [`SyncOnceCell`](crate::shared_pointer::once::SyncOnceCell) for kinds whose pointers can be
+/// shared between threads, like [`ArcK`]. All once-cells have the same API, given by
+/// [`SharedOnceCell`].
+///
+/// [`SharedLazy`]: crate::shared_pointer::once::SharedLazy
+/// [`SharedOnce`]: crate::shared_pointer::once::SharedOnce
+/// [`SharedOnceCell`]: crate::shared_pointer::once::SharedOnceCell
+pub trait SharedOnceKind: SharedPointerKind {
+    type OnceCell<T>: SharedOnceCell<T>;
 }
 
+/// Trait for [`SharedPointerKind`]s whose memory is allocated by an [`Allocator`].
+///
+/// This is implemented by [`RcK`] and [`ArcK`], which use the [`Global`] allocator, and by
+/// [`RcAllocK`] and [`ArcAllocK`], which use any allocator. The allocator of a pointer is part of
+/// its kind instance.
+///
+/// # Safety
+///
+/// The implementation must uphold the same requirements as [`SharedPointerKind`]. Memory of
+/// pointers created with an allocator must be allocated and released by that allocator.
+///
+/// [`Global`]: alloc::alloc::Global
+#[cfg(feature = "allocator_api")]
+pub unsafe trait SharedAllocatorKind: SharedPointerKind {
+    /// The allocator used by this kind.
+    type Allocator: Allocator;
+
+    fn new_in<T>(v: T, alloc: Self::Allocator) -> Self::Pointer<T>;
+
+    /// Like [`Self::new_in`], but returns an [`AllocError`] instead of aborting if the memory
+    /// allocation fails.
+    fn try_new_in<T>(v: T, alloc: Self::Allocator) -> Result<Self::Pointer<T>, AllocError>;
+
+    /// The allocator of `v` is used.
+    fn from_box_in<T: ?Sized>(v: Box<T, Self::Allocator>) -> Self::Pointer<T>;
+
+    /// The allocator of the pointer whose [raw parts](SharedPointerKind#raw-parts) include
+    /// `self`.
+    fn allocator(&self) -> &Self::Allocator;
+}
+
+mod alloc_error;
 mod arc;
+#[cfg(feature = "allocator_api")]
+mod arc_alloc;
 #[cfg(feature = "triomphe")]
 mod arct;
-mod erased_ptr;
+#[cfg(feature = "std")]
+mod biased_arc;
+#[cfg(feature = "std")]
+mod dynamic;
 mod rc;
+#[cfg(feature = "allocator_api")]
+mod rc_alloc;
 
+use crate::shared_pointer::lock::SharedLock;
+use crate::shared_pointer::once::SharedOnceCell;
+use crate::shared_pointer::thin::HeaderSlice;
 use alloc::boxed::Box;
+use alloc::vec::Vec;
+pub use alloc_error::AllocError;
 #[doc(inline)]
 pub use arc::ArcK;
+#[cfg(feature = "allocator_api")]
+#[doc(inline)]
+pub use arc_alloc::ArcAllocK;
 #[cfg(feature = "triomphe")]
 #[doc(inline)]
 pub use arct::ArcTK;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use biased_arc::BiasedArc;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use biased_arc::BiasedArcK;
+#[cfg(feature = "allocator_api")]
+use core::alloc::Allocator;
+use core::mem;
+use core::mem::ManuallyDrop;
+use core::mem::MaybeUninit;
+use core::ptr;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use dynamic::DynAtomic;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use dynamic::DynK;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use dynamic::DynMode;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use dynamic::DynPointer;
 #[doc(inline)]
 pub use rc::RcK;
+#[cfg(feature = "allocator_api")]
+#[doc(inline)]
+pub use rc_alloc::RcAllocK;
+
+/// Moves the value of `v` to `dst` and releases the memory of `v`. Unlike `*v`, this never copies
+/// the value to the stack, which matters for large values.
+#[cfg(any(feature = "allocator_api", feature = "triomphe", feature = "std"))]
+#[inline(always)]
+fn move_from_box<T>(v: Box<T>, dst: &mut MaybeUninit<T>) {
+    let v: *mut T = Box::into_raw(v);
+
+    // SAFETY: `v` comes from a box, so it is valid for reads and does not overlap `dst`. The value
+    // is moved out before the box is released as a `MaybeUninit<T>`, which does not drop it.
+    unsafe {
+        ptr::copy_nonoverlapping(v, dst.as_mut_ptr(), 1);
+        drop(Box::from_raw(v.cast::<MaybeUninit<T>>()));
+    }
+}
@@ -1,156 +1,289 @@
+use crate::shared_pointer::SharedPointer;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::AllocError;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::SharedAllocatorKind;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::SharedFallibleKind;
+use crate::shared_pointer::kind::SharedIntoInnerKind;
+use crate::shared_pointer::kind::SharedLockKind;
+use crate::shared_pointer::kind::SharedOnceKind;
 use crate::shared_pointer::kind::SharedPointerKind;
-use crate::shared_pointer::kind::erased_ptr::ErasedPtr;
+use crate::shared_pointer::kind::SharedStatelessKind;
+use crate::shared_pointer::kind::SharedThinKind;
+use crate::shared_pointer::kind::SharedWeakKind;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::move_from_box;
+use crate::shared_pointer::thin;
+use crate::shared_pointer::thin::HeaderSlice;
+use crate::shared_pointer::thin::HeaderSliceUnit;
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::Global;
 use alloc::boxed::Box;
 use alloc::rc::Rc;
+use alloc::rc::Weak;
+use alloc::vec::Vec;
+use core::cell::OnceCell;
+use core::cell::RefCell;
 use core::fmt;
 use core::fmt::Debug;
 use core::fmt::Formatter;
-use core::mem::ManuallyDrop;
+use core::marker::PhantomData;
+#[cfg(feature = "coerce_unsized")]
+use core::marker::Unsize;
+use core::mem::MaybeUninit;
+#[cfg(feature = "coerce_unsized")]
+use core::ops::DispatchFromDyn;
+use core::ptr::NonNull;
 
 /// [Type constructors](https://en.wikipedia.org/wiki/Type_constructor) for
 /// [`Rc`] pointers.
+#[derive(Default)]
 pub struct RcK {
-    /// A pointer previously obtained from [`Rc::into_raw()`] for the `T` this instance was
-    /// constructed with, and round-tripped through [`Rc::from_raw()`] on every operation. This
-    /// avoids relying on the layout of `Rc<T>` (which is not part of Rust's stable ABI) to
-    /// perform the type erasure.
-    ///
-    /// The referenced [`Rc`] allocation is kept alive by this pointer (which owns one strong
-    /// reference) until [`SharedPointerKind::drop()`] is called.
-    inner: ErasedPtr,
+    /// `Rc` pointers cannot be sent to other threads, so neither can this.
+    _phantom: PhantomData<*mut ()>,
 }
 
-impl RcK {
+unsafe impl SharedPointerKind for RcK {
+    type Pointer<T: ?Sized> = Rc<T>;
+
+    #[inline(always)]
+    fn new<T>(v: T) -> Rc<T> {
+        Rc::new(v)
+    }
+
+    #[inline(always)]
+    fn from_box<T>(v: Box<T>) -> Rc<T> {
+        Rc::from(v)
+    }
+
+    #[inline(always)]
+    fn new_uninit<T>() -> Rc<MaybeUninit<T>> {
+        Rc::new_uninit()
+    }
+
+    #[inline(always)]
+    fn from_slice<T: Clone>(v: &[T]) -> Rc<[T]> {
+        Rc::from(v)
+    }
+
+    #[inline(always)]
+    fn from_vec<T>(v: Vec<T>) -> Rc<[T]> {
+        Rc::from(v)
+    }
+
+    #[inline(always)]
+    fn from_str(v: &str) -> Rc<str> {
+        Rc::from(v)
+    }
+
     #[inline(always)]
-    fn new_from_inner<T>(rc: Rc<T>) -> RcK {
-        RcK { inner: ErasedPtr::new(Rc::into_raw(rc)) }
+    fn into_raw<T: ?Sized>(ptr: Rc<T>) -> (NonNull<T>, RcK) {
+        // SAFETY: `Rc::into_raw()` never returns a null pointer.
+        (unsafe { NonNull::new_unchecked(Rc::into_raw(ptr).cast_mut()) }, RcK::default())
     }
 
-    /// Reconstructs a non-owning view of the inner [`Rc<T>`].
-    ///
-    /// The returned [`ManuallyDrop`] must not be unwrapped: dropping the inner [`Rc`] would
-    /// decrement a refcount that this instance still logically owns.
-    ///
-    /// # Safety
-    ///
-    /// `Self` must have been constructed with the same `T`.
     #[inline(always)]
-    unsafe fn as_inner<T>(&self) -> ManuallyDrop<Rc<T>> {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Rc::into_raw::<T>` and points to a live `Rc<T>` allocation. Wrapping the
-        // reconstructed `Rc` in `ManuallyDrop` prevents it from decrementing the strong count
-        // when this local goes out of scope.
-        ManuallyDrop::new(unsafe { Rc::from_raw(self.inner.cast::<T>()) })
+    unsafe fn from_raw<T: ?Sized>(ptr: NonNull<T>, _: RcK) -> Rc<T> {
+        // SAFETY: By the caller's guarantee, `ptr` was returned by `Rc::into_raw()`, possibly
+        // after a cast that `Rc::from_raw()` accepts, and owns a strong reference.
+        unsafe { Rc::from_raw(ptr.as_ptr()) }
     }
 
-    /// Takes ownership of the inner [`Rc<T>`], consuming `self`.
-    ///
-    /// # Safety
-    ///
-    /// `Self` must have been constructed with the same `T`.
     #[inline(always)]
-    unsafe fn take_inner<T>(self) -> Rc<T> {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Rc::into_raw::<T>`. `self` is consumed by value and `RcK` has no `Drop` impl, so the
-        // ownership of the strong reference transfers cleanly to the returned `Rc<T>`.
-        unsafe { Rc::from_raw(self.inner.cast::<T>()) }
+    fn clone<T: ?Sized>(ptr: &Rc<T>) -> Rc<T> {
+        Rc::clone(ptr)
+    }
+
+    #[inline(always)]
+    fn strong_count<T: ?Sized>(ptr: &Rc<T>) -> usize {
+        Rc::strong_count(ptr)
+    }
+
+    #[inline(always)]
+    fn get_mut<T: ?Sized>(ptr: &mut Rc<T>) -> Option<&mut T> {
+        Rc::get_mut(ptr)
+    }
+
+    #[inline(always)]
+    fn make_mut<T: Clone>(ptr: &mut Rc<T>) -> &mut T {
+        Rc::make_mut(ptr)
+    }
+
+    #[inline(always)]
+    fn make_mut_with<T>(ptr: &mut Rc<T>, f: impl FnOnce(&T) -> T) -> &mut T {
+        if Rc::get_mut(ptr).is_none() {
+            *ptr = Rc::new(f(ptr));
+        }
+
+        // SAFETY: `ptr` is either the only reference to its allocation, as checked by
+        // `Rc::get_mut()`, or a new allocation.
+        unsafe { Rc::get_mut(ptr).unwrap_unchecked() }
+    }
+
+    #[inline(always)]
+    fn try_unwrap<T>(ptr: Rc<T>) -> Result<T, Rc<T>> {
+        Rc::try_unwrap(ptr)
     }
 }
 
-unsafe impl SharedPointerKind for RcK {
+#[cfg(feature = "allocator_api")]
+unsafe impl SharedFallibleKind for RcK {
+    #[inline(always)]
+    fn try_new<T>(v: T) -> Result<Rc<T>, AllocError> {
+        Ok(Rc::try_new(v)?)
+    }
+
     #[inline(always)]
-    fn new<T>(v: T) -> RcK {
-        RcK::new_from_inner(Rc::new(v))
+    fn try_from_box<T>(v: Box<T>) -> Result<Rc<T>, AllocError> {
+        let mut rc: Rc<MaybeUninit<T>> = Rc::try_new_uninit()?;
+
+        // SAFETY: `rc` was just created, so it is the only reference to its value.
+        move_from_box(v, unsafe { Rc::get_mut(&mut rc).unwrap_unchecked() });
+
+        // SAFETY: The value was initialized by `move_from_box()`.
+        Ok(unsafe { rc.assume_init() })
     }
+}
 
+unsafe impl SharedIntoInnerKind for RcK {
     #[inline(always)]
-    fn from_box<T>(v: Box<T>) -> RcK {
-        RcK::new_from_inner::<T>(Rc::from(v))
+    fn into_inner<T>(ptr: Rc<T>) -> Option<T> {
+        Rc::into_inner(ptr)
     }
+}
+
+impl SharedLockKind for RcK {
+    type Lock<T> = RefCell<T>;
+}
+
+impl SharedOnceKind for RcK {
+    type OnceCell<T> = OnceCell<T>;
+}
+
+unsafe impl SharedStatelessKind for RcK {}
+
+unsafe impl SharedWeakKind for RcK {
+    type Weak<T: ?Sized> = Weak<T>;
 
     #[inline(always)]
-    unsafe fn as_ptr<T>(&self) -> *const T {
-        // SAFETY: The type-parameter invariant is forwarded to `ErasedPtr::cast`.
-        unsafe { self.inner.cast::<T>() }
+    fn new_cyclic<T>(f: impl FnOnce(&Weak<T>) -> T) -> Rc<T> {
+        Rc::new_cyclic(f)
     }
 
     #[inline(always)]
-    unsafe fn deref<T>(&self) -> &T {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Rc::into_raw::<T>`, so it points to a valid `T` inside an allocation that is kept
-        // alive by `self`. The returned reference is tied to the lifetime of `&self`.
-        unsafe { &*self.inner.cast::<T>() }
+    fn downgrade<T: ?Sized>(ptr: &Rc<T>) -> Weak<T> {
+        Rc::downgrade(ptr)
     }
 
     #[inline(always)]
-    unsafe fn try_unwrap<T>(self) -> Result<T, RcK> {
-        // SAFETY: The type-parameter invariant is forwarded to `take_inner`.
-        let rc: Rc<T> = unsafe { self.take_inner::<T>() };
+    fn weak_count<T: ?Sized>(ptr: &Rc<T>) -> usize {
+        Rc::weak_count(ptr)
+    }
 
-        Rc::try_unwrap(rc).map_err(RcK::new_from_inner)
+    #[inline(always)]
+    fn upgrade<T: ?Sized>(weak: &Weak<T>) -> Option<Rc<T>> {
+        weak.upgrade()
     }
 
     #[inline(always)]
-    unsafe fn get_mut<T>(&mut self) -> Option<&mut T> {
-        // SAFETY: The type-parameter invariant is forwarded to `map_owned`; `Rc::from_raw` and
-        // `Rc::into_raw` are an inverse pair for `Rc<T>`.
-        let ret: Option<*mut T> = unsafe {
-            self.inner.map_owned::<T, Rc<T>, _>(Rc::from_raw, Rc::as_ptr, |rc| {
-                Rc::get_mut(rc).map(core::ptr::from_mut)
-            })
-        };
+    fn weak_into_raw<T: ?Sized>(weak: Weak<T>) -> (NonNull<T>, RcK) {
+        // SAFETY: `Weak::into_raw()` never returns a null pointer.
+        (unsafe { NonNull::new_unchecked(Weak::into_raw(weak).cast_mut()) }, RcK::default())
+    }
 
-        // SAFETY: If `Rc::get_mut` returned `Some`, no other strong or weak reference existed
-        // at the time of the call, so we have exclusive access to the `T`. The allocation is
-        // kept alive for at least as long as `self` because `self.inner` still owns a strong
-        // reference. The returned reference's lifetime is bound to `&mut self`, so no other
-        // access through `self` can occur while it is live.
-        ret.map(|p| unsafe { &mut *p })
+    #[inline(always)]
+    unsafe fn weak_from_raw<T: ?Sized>(ptr: NonNull<T>, _: RcK) -> Weak<T> {
+        // SAFETY: By the caller's guarantee, `ptr` was returned by `Weak::into_raw()`, possibly
+        // after a cast that `Weak::from_raw()` accepts, and owns a weak reference.
+        unsafe { Weak::from_raw(ptr.as_ptr()) }
     }
 
     #[inline(always)]
-    unsafe fn make_mut<T: Clone>(&mut self) -> &mut T {
-        // SAFETY: The type-parameter invariant is forwarded to `map_owned`; `Rc::from_raw` and
-        // `Rc::into_raw` are an inverse pair for `Rc<T>`.
-        let ret: *mut T = unsafe {
-            self.inner.map_owned::<T, Rc<T>, _>(Rc::from_raw, Rc::as_ptr, |rc| {
-                core::ptr::from_mut(Rc::make_mut(rc))
-            })
-        };
+    fn weak_strong_count<T: ?Sized>(weak: &Weak<T>) -> usize {
+        weak.strong_count()
+    }
 
-        // SAFETY: `Rc::make_mut` guarantees exclusive access to the (possibly freshly cloned)
-        // `T`. The allocation is kept alive by `self.inner`. The returned reference's lifetime
-        // is bound to `&mut self`, so no other access through `self` can occur while it is
-        // live.
-        unsafe { &mut *ret }
+    #[inline(always)]
+    fn weak_weak_count<T: ?Sized>(weak: &Weak<T>) -> usize {
+        weak.weak_count()
     }
 
     #[inline(always)]
-    unsafe fn strong_count<T>(&self) -> usize {
-        // SAFETY: The type-parameter invariant is forwarded to `as_inner`.
-        let rc: ManuallyDrop<Rc<T>> = unsafe { self.as_inner::<T>() };
+    fn weak_clone<T: ?Sized>(weak: &Weak<T>) -> Weak<T> {
+        Weak::clone(weak)
+    }
+}
 
-        Rc::strong_count(&*rc)
+unsafe impl SharedThinKind for RcK {
+    #[inline(always)]
+    fn from_header_and_iter<H, T, I>(header: H, items: I) -> Rc<HeaderSlice<H, T>>
+    where
+        I: ExactSizeIterator<Item = T>,
+    {
+        let length = items.len();
+        // `Rc` cannot allocate a custom dynamically-sized type, so it allocates a slice of units
+        // with the same layout instead, and the value is written in place.
+        let mut units =
+            Rc::<[HeaderSliceUnit<H, T>]>::new_uninit_slice(thin::unit_count::<H, T>(length));
+        let mem = Rc::get_mut(&mut units).expect("a new `Rc` is unique").as_mut_ptr().cast();
+
+        // SAFETY: `mem` points to the units, which are not shared.
+        unsafe { thin::write_header_and_iter(mem, header, items) };
+
+        // SAFETY: The units hold a valid `HeaderSlice<H, T>`, and `Rc<[HeaderSliceUnit<H, T>]>`
+        // has the same layout as `Rc<HeaderSlice<H, T>>`.
+        unsafe { Rc::from_raw(thin::header_slice_ptr(Rc::into_raw(units).cast(), length)) }
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+unsafe impl SharedAllocatorKind for RcK {
+    type Allocator = Global;
+
+    #[inline(always)]
+    fn new_in<T>(v: T, _alloc: Global) -> Rc<T> {
+        Rc::new(v)
     }
 
     #[inline(always)]
-    unsafe fn clone<T>(&self) -> RcK {
-        // SAFETY: The type-parameter invariant is forwarded to `as_inner`.
-        let rc: ManuallyDrop<Rc<T>> = unsafe { self.as_inner::<T>() };
+    fn try_new_in<T>(v: T, _alloc: Global) -> Result<Rc<T>, AllocError> {
+        RcK::try_new(v)
+    }
 
-        RcK::new_from_inner(Rc::clone(&*rc))
+    #[inline(always)]
+    fn from_box_in<T: ?Sized>(v: Box<T>) -> Rc<T> {
+        Rc::from(v)
     }
 
     #[inline(always)]
-    unsafe fn drop<T>(&mut self) {
-        // SAFETY: By the type-parameter invariant, `self.inner` was produced by
-        // `Rc::into_raw::<T>`. Reconstructing the `Rc<T>` and letting it drop decrements the
-        // strong count matching the initial `Rc::into_raw`. The caller guarantees this is the
-        // last use of `self`.
-        drop(unsafe { Rc::from_raw(self.inner.cast::<T>()) });
+    fn allocator(&self) -> &Global {
+        &Global
     }
 }
 
+impl<T: ?Sized> From<Rc<T>> for SharedPointer<T, RcK> {
+    #[inline(always)]
+    fn from(v: Rc<T>) -> SharedPointer<T, RcK> {
+        SharedPointer::from_inner_pointer(v)
+    }
+}
+
+impl<T: ?Sized> From<SharedPointer<T, RcK>> for Rc<T> {
+    #[inline(always)]
+    fn from(v: SharedPointer<T, RcK>) -> Rc<T> {
+        SharedPointer::into_inner_pointer(v)
+    }
+}
+
+#[cfg(feature = "coerce_unsized")]
+impl<T, U> DispatchFromDyn<SharedPointer<U, RcK>> for SharedPointer<T, RcK>
+where
+    T: Unsize<U> + ?Sized,
+    U: ?Sized,
+{
+}
+
 impl Debug for RcK {
     #[inline(always)]
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
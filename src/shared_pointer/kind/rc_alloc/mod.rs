@@ -0,0 +1,229 @@
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::AllocError;
+use crate::shared_pointer::kind::SharedAllocatorKind;
+use crate::shared_pointer::kind::SharedFallibleKind;
+use crate::shared_pointer::kind::SharedIntoInnerKind;
+use crate::shared_pointer::kind::SharedLockKind;
+use crate::shared_pointer::kind::SharedOnceKind;
+use crate::shared_pointer::kind::SharedPointerKind;
+use crate::shared_pointer::kind::move_from_box;
+use alloc::alloc::Global;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::alloc::Allocator;
+use core::cell::OnceCell;
+use core::cell::RefCell;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+/// [Type constructors](https://en.wikipedia.org/wiki/Type_constructor) for
+/// [`Rc`] pointers whose memory is allocated by the allocator `A`.
+///
+/// Constructors that do not take an allocator, such as
+/// [`SharedPointer::new()`](crate::shared_pointer::SharedPointer::new), use
+/// [`A::default()`](Default::default). Use
+/// [`SharedPointer::new_in()`](crate::shared_pointer::SharedPointer::new_in) to allocate with a
+/// specific allocator.
+#[derive(Default)]
+pub struct RcAllocK<A = Global> {
+    /// The allocator of the [`Rc`] allocation.
+    alloc: A,
+    /// `Rc` pointers cannot be sent to other threads, so neither can this.
+    _phantom: PhantomData<*mut ()>,
+}
+
+impl<A: Allocator> RcAllocK<A> {
+    #[inline(always)]
+    fn new_from_alloc(alloc: A) -> RcAllocK<A> {
+        RcAllocK { alloc, _phantom: PhantomData }
+    }
+}
+
+unsafe impl<A: Allocator + Clone + Default> SharedPointerKind for RcAllocK<A> {
+    type Pointer<T: ?Sized> = Rc<T, A>;
+
+    #[inline(always)]
+    fn new<T>(v: T) -> Rc<T, A> {
+        Rc::new_in(v, A::default())
+    }
+
+    #[inline(always)]
+    fn from_box<T>(v: Box<T>) -> Rc<T, A> {
+        RcAllocK::new(*v)
+    }
+
+    #[inline(always)]
+    fn new_uninit<T>() -> Rc<MaybeUninit<T>, A> {
+        Rc::new_uninit_in(A::default())
+    }
+
+    #[inline(always)]
+    fn from_slice<T: Clone>(v: &[T]) -> Rc<[T], A> {
+        let mut vec: Vec<T, A> = Vec::with_capacity_in(v.len(), A::default());
+
+        vec.extend_from_slice(v);
+
+        Rc::from(vec)
+    }
+
+    #[inline(always)]
+    fn from_vec<T>(v: Vec<T>) -> Rc<[T], A> {
+        let mut vec: Vec<T, A> = Vec::with_capacity_in(v.len(), A::default());
+
+        vec.extend(v);
+
+        Rc::from(vec)
+    }
+
+    #[inline(always)]
+    fn from_str(v: &str) -> Rc<str, A> {
+        let mut vec: Vec<u8, A> = Vec::with_capacity_in(v.len(), A::default());
+
+        vec.extend_from_slice(v.as_bytes());
+
+        let (ptr, alloc) = Rc::into_raw_with_allocator(Rc::<[u8], A>::from(vec));
+
+        // SAFETY: `str` has the same layout as `[u8]`, and the bytes were copied from a `str`,
+        // so they are valid UTF-8.
+        unsafe { Rc::from_raw_in(ptr as *const str, alloc) }
+    }
+
+    #[inline(always)]
+    fn into_raw<T: ?Sized>(ptr: Rc<T, A>) -> (NonNull<T>, RcAllocK<A>) {
+        let (ptr, alloc) = Rc::into_raw_with_allocator(ptr);
+
+        // SAFETY: `Rc::into_raw_with_allocator()` never returns a null pointer.
+        (unsafe { NonNull::new_unchecked(ptr.cast_mut()) }, RcAllocK::new_from_alloc(alloc))
+    }
+
+    #[inline(always)]
+    unsafe fn from_raw<T: ?Sized>(ptr: NonNull<T>, kind: RcAllocK<A>) -> Rc<T, A> {
+        // SAFETY: By the caller's guarantee, `ptr` was returned by
+        // `Rc::into_raw_with_allocator()`, possibly after a cast that `Rc::from_raw_in()`
+        // accepts, and owns a strong reference. `kind` was returned with it, so `kind.alloc` is
+        // the allocator that owns its memory.
+        unsafe { Rc::from_raw_in(ptr.as_ptr(), kind.alloc) }
+    }
+
+    #[inline(always)]
+    fn clone<T: ?Sized>(ptr: &Rc<T, A>) -> Rc<T, A> {
+        Rc::clone(ptr)
+    }
+
+    #[inline(always)]
+    fn strong_count<T: ?Sized>(ptr: &Rc<T, A>) -> usize {
+        Rc::strong_count(ptr)
+    }
+
+    #[inline(always)]
+    fn get_mut<T: ?Sized>(ptr: &mut Rc<T, A>) -> Option<&mut T> {
+        Rc::get_mut(ptr)
+    }
+
+    #[inline(always)]
+    fn make_mut<T: Clone>(ptr: &mut Rc<T, A>) -> &mut T {
+        Rc::make_mut(ptr)
+    }
+
+    #[inline(always)]
+    fn make_mut_with<T>(ptr: &mut Rc<T, A>, f: impl FnOnce(&T) -> T) -> &mut T {
+        if Rc::get_mut(ptr).is_none() {
+            *ptr = Rc::new_in(f(ptr), Rc::allocator(ptr).clone());
+        }
+
+        // SAFETY: `ptr` is either the only reference to its allocation, as checked by
+        // `Rc::get_mut()`, or a new allocation.
+        unsafe { Rc::get_mut(ptr).unwrap_unchecked() }
+    }
+
+    #[inline(always)]
+    fn try_unwrap<T>(ptr: Rc<T, A>) -> Result<T, Rc<T, A>> {
+        Rc::try_unwrap(ptr)
+    }
+}
+
+unsafe impl<A: Allocator + Clone + Default> SharedFallibleKind for RcAllocK<A> {
+    #[inline(always)]
+    fn try_new<T>(v: T) -> Result<Rc<T, A>, AllocError> {
+        RcAllocK::try_new_in(v, A::default())
+    }
+
+    #[inline(always)]
+    fn try_from_box<T>(v: Box<T>) -> Result<Rc<T, A>, AllocError> {
+        let mut rc: Rc<MaybeUninit<T>, A> = Rc::try_new_uninit_in(A::default())?;
+
+        // SAFETY: `rc` was just created, so it is the only reference to its value.
+        move_from_box(v, unsafe { Rc::get_mut(&mut rc).unwrap_unchecked() });
+
+        // SAFETY: The value was initialized by `move_from_box()`.
+        Ok(unsafe { rc.assume_init() })
+    }
+}
+
+unsafe impl<A: Allocator + Clone + Default> SharedIntoInnerKind for RcAllocK<A> {
+    #[inline(always)]
+    fn into_inner<T>(ptr: Rc<T, A>) -> Option<T> {
+        Rc::into_inner(ptr)
+    }
+}
+
+unsafe impl<A: Allocator + Clone + Default> SharedAllocatorKind for RcAllocK<A> {
+    type Allocator = A;
+
+    #[inline(always)]
+    fn new_in<T>(v: T, alloc: A) -> Rc<T, A> {
+        Rc::new_in(v, alloc)
+    }
+
+    #[inline(always)]
+    fn try_new_in<T>(v: T, alloc: A) -> Result<Rc<T, A>, AllocError> {
+        Ok(Rc::try_new_in(v, alloc)?)
+    }
+
+    #[inline(always)]
+    fn from_box_in<T: ?Sized>(v: Box<T, A>) -> Rc<T, A> {
+        Rc::from(v)
+    }
+
+    #[inline(always)]
+    fn allocator(&self) -> &A {
+        &self.alloc
+    }
+}
+
+impl<A: Allocator + Clone + Default> SharedLockKind for RcAllocK<A> {
+    type Lock<T> = RefCell<T>;
+}
+
+impl<A: Allocator + Clone + Default> SharedOnceKind for RcAllocK<A> {
+    type OnceCell<T> = OnceCell<T>;
+}
+
+impl<T: ?Sized, A: Allocator + Clone + Default> From<Rc<T, A>> for SharedPointer<T, RcAllocK<A>> {
+    #[inline(always)]
+    fn from(v: Rc<T, A>) -> SharedPointer<T, RcAllocK<A>> {
+        SharedPointer::from_inner_pointer(v)
+    }
+}
+
+impl<T: ?Sized, A: Allocator + Clone + Default> From<SharedPointer<T, RcAllocK<A>>> for Rc<T, A> {
+    #[inline(always)]
+    fn from(v: SharedPointer<T, RcAllocK<A>>) -> Rc<T, A> {
+        SharedPointer::into_inner_pointer(v)
+    }
+}
+
+impl<A> Debug for RcAllocK<A> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("RcAllocK")
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,249 @@
+use super::*;
+use crate::shared_pointer::kind::SharedStatelessKind;
+use core::alloc::Layout;
+use core::ptr::NonNull;
+use static_assertions::assert_not_impl_any;
+use std::cell::Cell;
+use std::string::String;
+use std::string::ToString;
+
+type PointerKind = RcAllocK<CountingAllocator>;
+
+// The kind instance holds the allocator, so a pointer cannot be rebuilt from its address alone.
+assert_not_impl_any!(PointerKind: SharedStatelessKind);
+
+/// Allocator that keeps track of the number of live allocations made through it (or any of its
+/// clones).
+#[derive(Clone, Default)]
+struct CountingAllocator {
+    live: Rc<Cell<usize>>,
+}
+
+impl CountingAllocator {
+    fn live(&self) -> usize {
+        self.live.get()
+    }
+}
+
+unsafe impl Allocator for CountingAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, core::alloc::AllocError> {
+        self.live.set(self.live.get() + 1);
+
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn test_new_in() {
+    let alloc = CountingAllocator::default();
+    let ptr = PointerKind::new_in::<i32>(42, alloc.clone());
+
+    assert_eq!(alloc.live(), 1);
+
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(PointerKind::strong_count(&ptr), 2);
+    assert_eq!(alloc.live(), 1);
+
+    drop(ptr);
+
+    assert_eq!(alloc.live(), 1);
+
+    drop(ptr_clone);
+
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_try_new_in() {
+    let alloc = CountingAllocator::default();
+    let ptr = PointerKind::try_new_in::<i32>(42, alloc.clone()).unwrap();
+
+    assert_eq!(alloc.live(), 1);
+    assert_eq!(*ptr, 42);
+
+    drop(ptr);
+
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_from_box_in() {
+    let alloc = CountingAllocator::default();
+    let boxed: Box<[i32], CountingAllocator> = Box::new_in([1, 2, 3], alloc.clone());
+    let ptr = PointerKind::from_box_in::<[i32]>(boxed);
+
+    assert_eq!(alloc.live(), 1);
+    assert_eq!(*ptr, [1, 2, 3]);
+
+    drop(ptr);
+
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_allocator() {
+    let alloc = CountingAllocator::default();
+    let ptr = PointerKind::new_in::<i32>(42, alloc.clone());
+    let (raw, kind) = PointerKind::into_raw(ptr);
+
+    assert!(Rc::ptr_eq(&kind.allocator().live, &alloc.live));
+
+    // SAFETY: `raw` and `kind` were returned by `into_raw()`.
+    drop(unsafe { PointerKind::from_raw(raw, kind) });
+
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_new_uninit_assume_init() {
+    let mut ptr = PointerKind::new_uninit::<String>();
+
+    PointerKind::get_mut(&mut ptr).unwrap().write("hello".to_string());
+
+    // SAFETY: The value was just initialized.
+    let ptr = unsafe { ptr.assume_init() };
+
+    assert_eq!(*ptr, "hello");
+}
+
+#[test]
+fn test_default_allocator() {
+    let ptr_slice = RcAllocK::<Global>::from_slice::<i32>(&[1, 2, 3]);
+    let ptr_vec = RcAllocK::<Global>::from_vec::<i32>(vec![1, 2, 3]);
+    let ptr_str = RcAllocK::<Global>::from_str("hello from test_default_allocator");
+    let ptr_box = RcAllocK::<Global>::from_box::<i32>(Box::new(42));
+
+    assert_eq!(*ptr_slice, [1, 2, 3]);
+    assert_eq!(*ptr_vec, [1, 2, 3]);
+    assert_eq!(&*ptr_str, "hello from test_default_allocator");
+    assert_eq!(*ptr_box, 42);
+}
+
+#[test]
+fn test_try_unwrap() {
+    let alloc = CountingAllocator::default();
+    let ptr = PointerKind::new_in::<i32>(42, alloc.clone());
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    drop(PointerKind::try_unwrap(ptr_clone).unwrap_err());
+
+    assert_eq!(PointerKind::try_unwrap(ptr).unwrap(), 42);
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_get_mut() {
+    let mut ptr = PointerKind::new_in::<i32>(42, CountingAllocator::default());
+
+    *PointerKind::get_mut(&mut ptr).unwrap() += 1;
+
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(PointerKind::get_mut(&mut ptr), None);
+
+    drop(ptr_clone);
+
+    assert_eq!(PointerKind::get_mut(&mut ptr), Some(&mut 43));
+}
+
+#[test]
+fn test_make_mut() {
+    let alloc = CountingAllocator::default();
+    let mut ptr = PointerKind::new_in::<i32>(42, alloc.clone());
+
+    *PointerKind::make_mut(&mut ptr) += 1;
+
+    assert_eq!(alloc.live(), 1);
+
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    *PointerKind::make_mut(&mut ptr_clone) += 1;
+
+    assert_eq!(alloc.live(), 2);
+    assert_eq!(*ptr, 43);
+    assert_eq!(*ptr_clone, 44);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
+
+    drop(ptr);
+    drop(ptr_clone);
+
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_make_mut_with() {
+    let alloc = CountingAllocator::default();
+    let mut ptr = PointerKind::new_in::<i32>(42, alloc.clone());
+
+    *PointerKind::make_mut_with(&mut ptr, |_| unreachable!()) += 1;
+
+    assert_eq!(*ptr, 43);
+
+    let mut ptr_clone = PointerKind::clone(&ptr);
+
+    *PointerKind::make_mut_with(&mut ptr_clone, |v| v * 10) += 1;
+
+    assert_eq!(*ptr, 43);
+    assert_eq!(*ptr_clone, 431);
+    assert_eq!(PointerKind::strong_count(&ptr), 1);
+    assert_eq!(PointerKind::strong_count(&ptr_clone), 1);
+    // The new value is allocated with the allocator of the original pointer.
+    assert_eq!(alloc.live(), 2);
+
+    drop(ptr);
+    drop(ptr_clone);
+
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_into_inner() {
+    let ptr = PointerKind::new::<i32>(42);
+    let ptr_clone = PointerKind::clone(&ptr);
+
+    assert_eq!(PointerKind::into_inner(ptr), None);
+    assert_eq!(PointerKind::into_inner(ptr_clone), Some(42));
+}
+
+#[test]
+fn test_into_raw_from_raw_unsize() {
+    let alloc = CountingAllocator::default();
+    let ptr = PointerKind::new_in::<i32>(42, alloc.clone());
+    let (raw, kind) = PointerKind::into_raw(ptr);
+    let raw: NonNull<dyn ToString> = raw;
+
+    // SAFETY: `raw` was returned by `into_raw()` and then unsized.
+    let ptr = unsafe { PointerKind::from_raw(raw, kind) };
+
+    assert_eq!(ptr.to_string(), "42");
+
+    drop(ptr);
+
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_into_raw_from_raw() {
+    let ptr = RcAllocK::<Global>::new::<i32>(42);
+    let (raw, kind) = RcAllocK::<Global>::into_raw(ptr);
+
+    // SAFETY: `raw` and `kind` were returned by `into_raw()`.
+    let ptr = unsafe { RcAllocK::<Global>::from_raw(raw, kind) };
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(RcAllocK::<Global>::strong_count(&ptr), 1);
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(format!("{:?}", PointerKind::default()), "RcAllocK");
+}
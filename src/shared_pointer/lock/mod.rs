@@ -0,0 +1,276 @@
+use core::cell::Ref;
+use core::cell::RefCell;
+use core::cell::RefMut;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::ops::Deref;
+use core::ops::DerefMut;
+#[cfg(feature = "std")]
+use std::sync::PoisonError;
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+#[cfg(feature = "std")]
+use std::sync::RwLockReadGuard;
+#[cfg(feature = "std")]
+use std::sync::RwLockWriteGuard;
+#[cfg(feature = "std")]
+use std::sync::TryLockError;
+
+#[cfg(not(feature = "std"))]
+mod spin;
+
+/// Interior mutability with a uniform API for the lock types of
+/// [`SharedLockKind`](crate::shared_pointer::kind::SharedLockKind)s.
+///
+/// This is implemented by [`RefCell`], whose guards are [`Ref`] and [`RefMut`], and by
+/// [`SyncLock`], which can be shared between threads.
+///
+/// A lock can be locked for reading, with [`Self::read()`], by any number of readers at the same
+/// time, or for writing, with [`Self::write()`] (or its alias [`Self::lock()`]), by a single
+/// writer. Whether a conflicting access panics (as with `RefCell`) or blocks (as with `SyncLock`)
+/// depends on the lock.
+pub trait SharedLock<T>: Sized {
+    type ReadGuard<'a>: Deref<Target = T>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>: DerefMut<Target = T>
+    where
+        Self: 'a;
+
+    fn new(value: T) -> Self;
+
+    /// Locks for shared read access.
+    fn read(&self) -> Self::ReadGuard<'_>;
+
+    /// Locks for exclusive write access.
+    fn write(&self) -> Self::WriteGuard<'_>;
+
+    /// Locks for exclusive access. This is the same as [`Self::write()`].
+    #[inline(always)]
+    fn lock(&self) -> Self::WriteGuard<'_> {
+        self.write()
+    }
+
+    /// Returns a mutable reference to the value. No locking is needed since this borrows the lock
+    /// mutably.
+    fn get_mut(&mut self) -> &mut T;
+
+    fn into_inner(self) -> T;
+}
+
+impl<T> SharedLock<T> for RefCell<T> {
+    type ReadGuard<'a>
+        = Ref<'a, T>
+    where
+        T: 'a;
+
+    type WriteGuard<'a>
+        = RefMut<'a, T>
+    where
+        T: 'a;
+
+    #[inline(always)]
+    fn new(value: T) -> RefCell<T> {
+        RefCell::new(value)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the value is locked for writing.
+    #[inline(always)]
+    fn read(&self) -> Ref<'_, T> {
+        self.borrow()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the value is locked.
+    #[inline(always)]
+    fn write(&self) -> RefMut<'_, T> {
+        self.borrow_mut()
+    }
+
+    #[inline(always)]
+    fn get_mut(&mut self) -> &mut T {
+        RefCell::get_mut(self)
+    }
+
+    #[inline(always)]
+    fn into_inner(self) -> T {
+        RefCell::into_inner(self)
+    }
+}
+
+/// Readers-writer lock that can be shared between threads.
+///
+/// With the `std` feature this is a [`std::sync::RwLock`], otherwise it is a spin lock. Unlike
+/// `std::sync::RwLock`, a panic while the lock is held does not poison it.
+///
+/// The operations are provided by the [`SharedLock`] trait.
+pub struct SyncLock<T> {
+    #[cfg(feature = "std")]
+    inner: RwLock<T>,
+    #[cfg(not(feature = "std"))]
+    inner: spin::RwLock<T>,
+}
+
+impl<T> SyncLock<T> {
+    /// Like [`SharedLock::new()`], but usable in constant expressions.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(value: T) -> SyncLock<T> {
+        #[cfg(feature = "std")]
+        let inner = RwLock::new(value);
+        #[cfg(not(feature = "std"))]
+        let inner = spin::RwLock::new(value);
+
+        SyncLock { inner }
+    }
+}
+
+impl<T> SharedLock<T> for SyncLock<T> {
+    type ReadGuard<'a>
+        = SyncLockReadGuard<'a, T>
+    where
+        T: 'a;
+
+    type WriteGuard<'a>
+        = SyncLockWriteGuard<'a, T>
+    where
+        T: 'a;
+
+    #[inline(always)]
+    fn new(value: T) -> SyncLock<T> {
+        SyncLock::new(value)
+    }
+
+    #[inline(always)]
+    fn read(&self) -> SyncLockReadGuard<'_, T> {
+        #[cfg(feature = "std")]
+        let inner = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let inner = self.inner.read();
+
+        SyncLockReadGuard { inner }
+    }
+
+    #[inline(always)]
+    fn write(&self) -> SyncLockWriteGuard<'_, T> {
+        #[cfg(feature = "std")]
+        let inner = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let inner = self.inner.write();
+
+        SyncLockWriteGuard { inner }
+    }
+
+    #[inline(always)]
+    fn get_mut(&mut self) -> &mut T {
+        #[cfg(feature = "std")]
+        let value = self.inner.get_mut().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let value = self.inner.get_mut();
+
+        value
+    }
+
+    #[inline(always)]
+    fn into_inner(self) -> T {
+        #[cfg(feature = "std")]
+        let value = self.inner.into_inner().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let value = self.inner.into_inner();
+
+        value
+    }
+}
+
+impl<T: Default> Default for SyncLock<T> {
+    #[inline(always)]
+    fn default() -> SyncLock<T> {
+        SyncLock::new(T::default())
+    }
+}
+
+impl<T> From<T> for SyncLock<T> {
+    #[inline(always)]
+    fn from(value: T) -> SyncLock<T> {
+        SyncLock::new(value)
+    }
+}
+
+impl<T: Debug> Debug for SyncLock<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "std")]
+        let guard = match self.inner.try_read() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(err)) => Some(err.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        };
+        #[cfg(not(feature = "std"))]
+        let guard = self.inner.try_read();
+
+        match guard {
+            Some(guard) => f.debug_struct("SyncLock").field("value", &*guard).finish(),
+            None => f.debug_struct("SyncLock").field("value", &format_args!("<locked>")).finish(),
+        }
+    }
+}
+
+/// Guard of a [`SyncLock`] locked for reading. The lock is released when the guard is dropped.
+pub struct SyncLockReadGuard<'a, T> {
+    #[cfg(feature = "std")]
+    inner: RwLockReadGuard<'a, T>,
+    #[cfg(not(feature = "std"))]
+    inner: spin::RwLockReadGuard<'a, T>,
+}
+
+impl<T> Deref for SyncLockReadGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: Debug> Debug for SyncLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+/// Guard of a [`SyncLock`] locked for writing. The lock is released when the guard is dropped.
+pub struct SyncLockWriteGuard<'a, T> {
+    #[cfg(feature = "std")]
+    inner: RwLockWriteGuard<'a, T>,
+    #[cfg(not(feature = "std"))]
+    inner: spin::RwLockWriteGuard<'a, T>,
+}
+
+impl<T> Deref for SyncLockWriteGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for SyncLockWriteGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: Debug> Debug for SyncLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,157 @@
+//! Readers-writer spin lock, used by [`SyncLock`](super::SyncLock) when `std` is not available.
+
+use core::cell::UnsafeCell;
+use core::hint;
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+/// Value of the state when the lock is held by a writer. Otherwise the state is the number of
+/// readers holding the lock.
+const WRITER: usize = usize::MAX;
+
+pub struct RwLock<T> {
+    state: AtomicUsize,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: Same bounds as `std::sync::RwLock`: the lock owns a `T`, which can be mutably accessed
+// (and therefore sent) by any thread holding it for writing, and shared by all threads holding it
+// for reading.
+unsafe impl<T: Send> Send for RwLock<T> {}
+unsafe impl<T: Send + Sync> Sync for RwLock<T> {}
+
+impl<T> RwLock<T> {
+    #[inline(always)]
+    pub const fn new(value: T) -> RwLock<T> {
+        RwLock { state: AtomicUsize::new(0), value: UnsafeCell::new(value) }
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        let mut state = self.state.load(Ordering::Relaxed);
+
+        loop {
+            // The number of readers cannot reach `WRITER - 1` without exhausting memory first, so
+            // it never overflows into the writer state.
+            if state == WRITER {
+                hint::spin_loop();
+                state = self.state.load(Ordering::Relaxed);
+                continue;
+            }
+
+            match self.state.compare_exchange_weak(
+                state,
+                state + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return RwLockReadGuard { lock: self, _marker: PhantomData },
+                Err(current) => state = current,
+            }
+        }
+    }
+
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        let mut state = self.state.load(Ordering::Relaxed);
+
+        while state != WRITER {
+            match self.state.compare_exchange_weak(
+                state,
+                state + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(RwLockReadGuard { lock: self, _marker: PhantomData }),
+                Err(current) => state = current,
+            }
+        }
+
+        None
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        while self
+            .state
+            .compare_exchange_weak(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.state.load(Ordering::Relaxed) != 0 {
+                hint::spin_loop();
+            }
+        }
+
+        RwLockWriteGuard { lock: self, _marker: PhantomData }
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+pub struct RwLockReadGuard<'a, T> {
+    lock: &'a RwLock<T>,
+    /// Makes the guard `!Send`, like the guards of `std::sync::RwLock`.
+    _marker: PhantomData<*const ()>,
+}
+
+// SAFETY: The guard only gives access to the `T`, like the guards of `std::sync::RwLock`.
+unsafe impl<T: Sync> Sync for RwLockReadGuard<'_, T> {}
+
+impl<T> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // SAFETY: The lock is held for reading, so there is no writer.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> Drop for RwLockReadGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.lock.state.fetch_sub(1, Ordering::Release);
+    }
+}
+
+pub struct RwLockWriteGuard<'a, T> {
+    lock: &'a RwLock<T>,
+    /// Makes the guard `!Send`, like the guards of `std::sync::RwLock`.
+    _marker: PhantomData<*const ()>,
+}
+
+// SAFETY: The guard only gives access to the `T`, like the guards of `std::sync::RwLock`.
+unsafe impl<T: Sync> Sync for RwLockWriteGuard<'_, T> {}
+
+impl<T> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // SAFETY: The lock is held for writing, so this is the only access to the value.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> DerefMut for RwLockWriteGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: The lock is held for writing, so this is the only access to the value.
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T> Drop for RwLockWriteGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.lock.state.store(0, Ordering::Release);
+    }
+}
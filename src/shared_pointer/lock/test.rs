@@ -0,0 +1,151 @@
+use super::*;
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::ArcK;
+#[cfg(feature = "triomphe")]
+use crate::shared_pointer::kind::ArcTK;
+use crate::shared_pointer::kind::RcK;
+use crate::shared_pointer::kind::SharedLockKind;
+use pretty_assertions::assert_eq;
+use static_assertions::assert_impl_all;
+use static_assertions::assert_not_impl_any;
+use static_assertions::assert_type_eq_all;
+use std::cell::Cell;
+use std::format;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::thread;
+use std::vec::Vec;
+
+assert_type_eq_all!(<RcK as SharedLockKind>::Lock<i32>, RefCell<i32>);
+assert_type_eq_all!(<ArcK as SharedLockKind>::Lock<i32>, SyncLock<i32>);
+#[cfg(feature = "triomphe")]
+assert_type_eq_all!(<ArcTK as SharedLockKind>::Lock<i32>, SyncLock<i32>);
+
+assert_impl_all!(SyncLock<i32>: Send, Sync);
+assert_impl_all!(SyncLock<Cell<i32>>: Send);
+assert_not_impl_any!(SyncLock<Cell<i32>>: Sync);
+assert_impl_all!(SharedPointer<SyncLock<Vec<i32>>, ArcK>: Send, Sync);
+assert_not_impl_any!(SyncLockReadGuard<'static, i32>: Send);
+assert_not_impl_any!(SyncLockWriteGuard<'static, i32>: Send);
+
+fn check_read_write<P: SharedLockKind>() {
+    let ptr: SharedPointer<P::Lock<Vec<i32>>, P> = SharedPointer::new(SharedLock::new(Vec::new()));
+
+    ptr.lock().push(1);
+    ptr.write().push(2);
+
+    {
+        let a = ptr.read();
+        let b = ptr.read();
+
+        assert_eq!(*a, [1, 2]);
+        assert_eq!(*b, [1, 2]);
+    }
+
+    let other = SharedPointer::clone(&ptr);
+
+    other.lock().push(3);
+
+    assert_eq!(*ptr.read(), [1, 2, 3]);
+}
+
+#[test]
+fn test_read_write() {
+    check_read_write::<RcK>();
+    check_read_write::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_read_write::<ArcTK>();
+}
+
+fn check_get_mut_into_inner<L: SharedLock<i32>>() {
+    let mut lock = L::new(1);
+
+    *lock.get_mut() += 1;
+
+    assert_eq!(*lock.read(), 2);
+    assert_eq!(lock.into_inner(), 2);
+}
+
+#[test]
+fn test_get_mut_into_inner() {
+    check_get_mut_into_inner::<RefCell<i32>>();
+    check_get_mut_into_inner::<SyncLock<i32>>();
+}
+
+#[test]
+#[should_panic(expected = "already borrowed")]
+fn test_ref_cell_write_while_read() {
+    let lock: RefCell<i32> = SharedLock::new(0);
+    let _guard = SharedLock::read(&lock);
+
+    drop(SharedLock::write(&lock));
+}
+
+#[test]
+fn test_sync_lock_threads() {
+    let ptr: SharedPointer<SyncLock<u32>, ArcK> = SharedPointer::new(SyncLock::new(0));
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..1_000 {
+                    *ptr.lock() += 1;
+
+                    assert!(*ptr.read() > 0);
+                }
+            });
+        }
+    });
+
+    assert_eq!(*ptr.read(), 4_000);
+}
+
+#[test]
+fn test_sync_lock_const() {
+    static LOCK: SyncLock<u32> = SyncLock::new(0);
+
+    *LOCK.write() += 1;
+
+    assert_eq!(*LOCK.read(), 1);
+}
+
+#[test]
+fn test_sync_lock_not_poisoned() {
+    let lock: SyncLock<i32> = SyncLock::new(0);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut guard = lock.write();
+
+        *guard = 1;
+
+        panic!("panic while locked");
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(*lock.read(), 1);
+    assert_eq!(lock.into_inner(), 1);
+}
+
+#[test]
+fn test_debug() {
+    let lock: SyncLock<i32> = SyncLock::new(42);
+
+    assert_eq!(format!("{:?}", lock), "SyncLock { value: 42 }");
+    assert_eq!(format!("{:?}", lock.read()), "42");
+
+    let guard = lock.write();
+
+    assert_eq!(format!("{:?}", guard), "42");
+    assert_eq!(format!("{:?}", lock), "SyncLock { value: <locked> }");
+}
+
+#[test]
+fn test_default_from() {
+    let lock: SyncLock<i32> = SyncLock::default();
+
+    assert_eq!(*lock.read(), 0);
+
+    let lock: SyncLock<i32> = SyncLock::from(7);
+
+    assert_eq!(*lock.read(), 7);
+}
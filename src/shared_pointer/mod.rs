@@ -1,5 +1,16 @@
+use crate::shared_pointer::kind::AllocError;
+#[cfg(feature = "allocator_api")]
+use crate::shared_pointer::kind::SharedAllocatorKind;
+use crate::shared_pointer::kind::SharedFallibleKind;
+use crate::shared_pointer::kind::SharedIntoInnerKind;
 use crate::shared_pointer::kind::SharedPointerKind;
+use crate::shared_pointer::kind::SharedStatelessKind;
+use crate::shared_pointer::kind::SharedWeakKind;
+use crate::shared_pointer::unique::UniqueSharedPointer;
+use crate::shared_pointer::weak::SharedWeak;
 use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::fmt;
@@ -9,11 +20,17 @@ use core::fmt::Formatter;
 use core::hash::Hash;
 use core::hash::Hasher;
 use core::marker::PhantomData;
+#[cfg(feature = "coerce_unsized")]
+use core::marker::Unsize;
 use core::mem;
 use core::mem::ManuallyDrop;
+use core::mem::MaybeUninit;
+#[cfg(feature = "coerce_unsized")]
+use core::ops::CoerceUnsized;
 use core::ops::Deref;
 use core::pin::Pin;
 use core::ptr;
+use core::ptr::NonNull;
 
 /// Pointer to shared data with reference-counting.
 ///
@@ -71,73 +88,858 @@ use core::ptr;
 ///
 /// assert_eq!(*pair.value, 1944);
 /// ```
-pub struct SharedPointer<T, P>
+pub struct SharedPointer<T: ?Sized, P>
 where
     P: SharedPointerKind,
 {
-    ptr: ManuallyDrop<P>,
+    /// The pointer of the [raw parts](SharedPointerKind#raw-parts) of a `P::Pointer<T>`, or a
+    /// pointer to a static value tagged with [`STATIC_TAG`].
+    ptr: NonNull<T>,
+    /// The kind instance of the raw parts, which is the [`Default`] one for static values.
+    kind: ManuallyDrop<P>,
     _phantom_t: PhantomData<T>,
-    _phantom_no_send_sync: PhantomData<*mut ()>,
 }
 
-unsafe impl<T: Sync + Send, P: Send> Send for SharedPointer<T, P> where P: SharedPointerKind {}
-unsafe impl<T: Sync + Send, P: Sync> Sync for SharedPointer<T, P> where P: SharedPointerKind {}
+unsafe impl<T: Sync + Send + ?Sized, P: Send> Send for SharedPointer<T, P> where P: SharedPointerKind
+{}
+unsafe impl<T: Sync + Send + ?Sized, P: Sync> Sync for SharedPointer<T, P> where P: SharedPointerKind
+{}
 
-impl<T, P> Unpin for SharedPointer<T, P> where P: SharedPointerKind {}
+impl<T: ?Sized, P> Unpin for SharedPointer<T, P> where P: SharedPointerKind {}
+
+#[cfg(feature = "coerce_unsized")]
+impl<T, U, P> CoerceUnsized<SharedPointer<U, P>> for SharedPointer<T, P>
+where
+    T: Unsize<U> + ?Sized,
+    U: ?Sized,
+    P: SharedPointerKind,
+{
+}
+
+/// Tag of pointers to static values, stored in the lowest bit of the address. Kinds never return
+/// odd addresses (see [`SharedPointerKind`]), so it never appears on reference-counted pointers.
+///
+/// On targets where `usize` is aligned to a single byte, such as AVR, values stored after a
+/// reference count can have odd addresses, so there is no room for a tag: it is zero, which means
+/// that no pointer is static, and [`SharedPointer::from_static()`] panics.
+const STATIC_TAG: usize = if mem::align_of::<usize>() >= 2 { 0b1 } else { 0 };
 
 impl<T, P> SharedPointer<T, P>
 where
     P: SharedPointerKind,
 {
     #[inline(always)]
-    fn new_from_inner(ptr: P) -> SharedPointer<T, P> {
-        SharedPointer {
-            ptr: ManuallyDrop::new(ptr),
-            _phantom_t: PhantomData,
-            _phantom_no_send_sync: PhantomData,
+    pub fn new(v: T) -> SharedPointer<T, P> {
+        SharedPointer::from_pointer(P::new::<T>(v))
+    }
+
+    #[inline(always)]
+    pub fn pin(v: T) -> Pin<SharedPointer<T, P>> {
+        unsafe { Pin::new_unchecked(Self::new(v)) }
+    }
+
+    /// Creates a `SharedPointer` with uninitialized contents. The value can be initialized in
+    /// place through [`SharedPointer::get_mut()`], without building it on the stack first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut ptr: SharedPointer<MaybeUninit<u32>, RcK> = SharedPointer::new_uninit();
+    ///
+    /// SharedPointer::get_mut(&mut ptr).unwrap().write(1944);
+    ///
+    /// let ptr: SharedPointer<u32, RcK> = unsafe { SharedPointer::assume_init(ptr) };
+    ///
+    /// assert_eq!(*ptr, 1944);
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn new_uninit() -> SharedPointer<MaybeUninit<T>, P> {
+        SharedPointer::from_pointer(P::new_uninit::<T>())
+    }
+
+    /// Creates a `SharedPointer` with uninitialized contents, with the memory filled with `0`
+    /// bytes. See [`MaybeUninit::zeroed()`] for the cases where this is a valid `T`.
+    #[inline(always)]
+    #[must_use]
+    pub fn new_zeroed() -> SharedPointer<MaybeUninit<T>, P> {
+        let ptr: SharedPointer<MaybeUninit<T>, P> = SharedPointer::new_uninit();
+
+        // SAFETY: The pointer was just created, so no one else can access the value, and it is
+        // valid for writes of one `T` since it points to a `MaybeUninit<T>`.
+        unsafe { SharedPointer::as_ptr(&ptr).cast_mut().write_bytes(0, 1) };
+
+        ptr
+    }
+
+    #[inline(always)]
+    pub fn try_unwrap(this: SharedPointer<T, P>) -> Result<T, SharedPointer<T, P>> {
+        if SharedPointer::is_static(&this) {
+            return Err(this);
         }
+
+        // SAFETY: The pointer is not static.
+        P::try_unwrap::<T>(unsafe { SharedPointer::into_pointer(this) })
+            .map_err(SharedPointer::from_pointer)
     }
 
+    /// Like [`SharedPointer::make_mut()`], but uses `f` to clone the value if this is not the only
+    /// reference to it. This allows `T` to be cloned in a different way than [`Clone`] (or at
+    /// all, if `T` does not implement it).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    ///
+    /// let mut ptr: SharedPointer<Vec<i32>, RcK> = SharedPointer::new(vec![1, 2, 3]);
+    /// let ptr_clone = SharedPointer::clone(&ptr);
+    ///
+    /// SharedPointer::make_mut_with(&mut ptr, |v| v.iter().map(|x| x * 10).collect()).push(4);
+    ///
+    /// assert_eq!(*ptr, [10, 20, 30, 4]);
+    /// assert_eq!(*ptr_clone, [1, 2, 3]);
+    /// ```
     #[inline(always)]
-    pub fn new(v: T) -> SharedPointer<T, P> {
-        SharedPointer::new_from_inner(P::new::<T>(v))
+    pub fn make_mut_with(this: &mut SharedPointer<T, P>, f: impl FnOnce(&T) -> T) -> &mut T {
+        if SharedPointer::is_static(this) {
+            *this = SharedPointer::new(f(this));
+
+            // SAFETY: `this` is the only reference to its new allocation.
+            return unsafe { SharedPointer::get_mut_unchecked(this) };
+        }
+
+        // SAFETY: The pointer is not static.
+        let ptr: *mut T =
+            unsafe { this.with_pointer_mut(|ptr| ptr::from_mut(P::make_mut_with::<T>(ptr, f))) };
+
+        // SAFETY: The value is only referenced by `this`, which is borrowed for as long as the
+        // returned reference.
+        unsafe { &mut *ptr }
     }
 
+    /// Converts this pointer into a pointer of kind `PO`, if it is the only reference to its
+    /// value. Otherwise, returns the pointer unchanged.
+    ///
+    /// The value is moved into a new allocation of the target kind. Note that this is also the
+    /// case when converting between [`ArcK`](kind::ArcK) and [`ArcTK`](kind::ArcTK): the header of
+    /// `triomphe::Arc` has a single counter, while the one of `std::sync::Arc` also has a weak
+    /// counter, so the allocation cannot be reused.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    ///
+    /// let ptr: SharedPointer<_, RcK> = SharedPointer::new(vec![1, 2, 3]);
+    /// let ptr: SharedPointer<_, ArcK> = SharedPointer::try_convert_kind(ptr).unwrap();
+    ///
+    /// std::thread::spawn(move || assert_eq!(*ptr, [1, 2, 3]))
+    ///     .join()
+    ///     .unwrap();
+    /// ```
     #[inline(always)]
-    pub fn pin(v: T) -> Pin<SharedPointer<T, P>> {
-        unsafe { Pin::new_unchecked(Self::new(v)) }
+    pub fn try_convert_kind<PO: SharedPointerKind>(
+        this: SharedPointer<T, P>,
+    ) -> Result<SharedPointer<T, PO>, SharedPointer<T, P>> {
+        SharedPointer::try_unwrap(this).map(SharedPointer::new)
+    }
+
+    /// Converts this pointer into a pointer of kind `PO`. The value is moved if this is the only
+    /// reference to it, and cloned otherwise. See [`SharedPointer::try_convert_kind()`].
+    #[inline(always)]
+    pub fn convert_kind_cloning<PO: SharedPointerKind>(
+        this: SharedPointer<T, P>,
+    ) -> SharedPointer<T, PO>
+    where
+        T: Clone,
+    {
+        match SharedPointer::try_convert_kind(this) {
+            Ok(ptr) => ptr,
+            Err(ptr) => SharedPointer::new(T::clone(&ptr)),
+        }
+    }
+}
+
+impl<T: ?Sized, P> SharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    /// Creates a `SharedPointer` to a value with `'static` lifetime, which is not in a
+    /// reference-counted allocation. Cloning and dropping such a pointer does not touch any
+    /// reference count, so it is cheap to share global constants, such as sentinel values, even
+    /// across threads. To declare these pointers in `static` items see
+    /// [`StaticSharedPointer`](crate::shared_pointer::static_pointer::StaticSharedPointer).
+    ///
+    /// A static value is never mutated nor moved out: [`SharedPointer::get_mut()`] returns `None`,
+    /// [`SharedPointer::make_mut()`] clones the value into a new allocation, and
+    /// [`SharedPointer::try_unwrap()`] fails. Since there is no allocation,
+    /// [`SharedPointer::into_raw()`] and [`SharedPointer::into_inner_pointer()`] panic.
+    ///
+    /// `T` can be unsized, and the pointer can later be
+    /// [coerced](crate::coerce_shared_pointer) to an unsized type, like any other pointer.
+    ///
+    /// Static pointers are told apart from reference-counted ones by a tag in the lowest bit of
+    /// the address, so the value must be aligned to at least two bytes, unless it is zero-sized.
+    /// Values with a smaller alignment, such as `u8`, `bool`, or `[u8]`, can be shared through a
+    /// [`StaticSharedPointer`](crate::shared_pointer::static_pointer::StaticSharedPointer), which
+    /// aligns its value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is aligned to a single byte and is not zero-sized, or if the target
+    /// aligns `usize` to a single byte, which leaves no room for the tag.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    ///
+    /// static EMPTY: Vec<i32> = Vec::new();
+    ///
+    /// let ptr: SharedPointer<_, ArcK> = SharedPointer::from_static(&EMPTY);
+    /// let mut ptr_clone = SharedPointer::clone(&ptr);
+    ///
+    /// assert!(SharedPointer::is_static(&ptr_clone));
+    ///
+    /// SharedPointer::make_mut(&mut ptr_clone).push(1944);
+    ///
+    /// assert!(!SharedPointer::is_static(&ptr_clone));
+    /// assert!(ptr.is_empty());
+    /// assert_eq!(*ptr_clone, [1944]);
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn from_static(v: &'static T) -> SharedPointer<T, P> {
+        let ptr: &'static T = match mem::size_of_val(v) {
+            // A zero-sized value can be read from any aligned address, so it is moved to one
+            // that has room for the tag.
+            0 => {
+                let addr = mem::align_of_val(v).max(2);
+
+                // SAFETY: `addr` is a multiple of the alignment of the value, and a zero-sized
+                // value is valid at any aligned, non-null address.
+                unsafe { &*ptr::from_ref(v).map_addr(|_| addr) }
+            }
+            _ => {
+                assert!(
+                    mem::align_of_val(v) >= 2,
+                    "static values must be aligned to at least two bytes, see `StaticSharedPointer`",
+                );
+
+                v
+            }
+        };
+
+        SharedPointer::new_static(ptr)
+    }
+
+    #[inline(always)]
+    fn from_parts(ptr: NonNull<T>, kind: P) -> SharedPointer<T, P> {
+        SharedPointer { ptr, kind: ManuallyDrop::new(kind), _phantom_t: PhantomData }
+    }
+
+    /// Creates a pointer to a static value, whose address must be a multiple of two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target has no room for the [`STATIC_TAG`].
+    #[inline(always)]
+    fn new_static(v: &'static T) -> SharedPointer<T, P> {
+        #[allow(clippy::assertions_on_constants)]
+        {
+            assert!(
+                STATIC_TAG != 0,
+                "static values are not supported on targets where `usize` is aligned to a single byte",
+            );
+        }
+
+        let ptr: *mut T = ptr::from_ref(v).cast_mut().map_addr(|addr| addr | STATIC_TAG);
+
+        // SAFETY: `ptr` comes from a reference, so it is not null, and the tag keeps it that way.
+        SharedPointer::from_parts(unsafe { NonNull::new_unchecked(ptr) }, P::default())
+    }
+
+    #[inline(always)]
+    fn from_pointer(ptr: P::Pointer<T>) -> SharedPointer<T, P> {
+        let (ptr, kind) = P::into_raw::<T>(ptr);
+
+        SharedPointer::from_parts(ptr, kind)
+    }
+
+    #[inline(always)]
+    fn into_parts(this: SharedPointer<T, P>) -> (NonNull<T>, P) {
+        let mut this = ManuallyDrop::new(this);
+
+        // SAFETY: `this` is never used again.
+        (this.ptr, unsafe { ManuallyDrop::take(&mut this.kind) })
+    }
+
+    /// Converts `this` into the pointer it holds the raw parts of.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must not be static.
+    #[inline(always)]
+    unsafe fn into_pointer(this: SharedPointer<T, P>) -> P::Pointer<T> {
+        let (ptr, kind) = SharedPointer::into_parts(this);
+
+        // SAFETY: The raw parts of a pointer that is not static come from `P::into_raw()`.
+        unsafe { P::from_raw::<T>(ptr, kind) }
+    }
+
+    /// Returns a view of the pointer `self` holds the raw parts of, which must not be dropped.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must not be static.
+    #[inline(always)]
+    unsafe fn pointer(&self) -> ManuallyDrop<P::Pointer<T>> {
+        // SAFETY: The raw parts of a pointer that is not static come from `P::into_raw()`. The
+        // kind instance is a bitwise copy, which is fine since the view is never dropped (see
+        // `SharedPointerKind`).
+        ManuallyDrop::new(unsafe { P::from_raw::<T>(self.ptr, ptr::read(&raw const *self.kind)) })
+    }
+
+    /// Calls `f` with the pointer `self` holds the raw parts of, and stores back its raw parts,
+    /// even if `f` panics.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must not be static.
+    #[inline(always)]
+    unsafe fn with_pointer_mut<R>(&mut self, f: impl FnOnce(&mut P::Pointer<T>) -> R) -> R {
+        struct Guard<'a, T: ?Sized, P: SharedPointerKind> {
+            this: &'a mut SharedPointer<T, P>,
+            ptr: ManuallyDrop<P::Pointer<T>>,
+        }
+
+        impl<T: ?Sized, P: SharedPointerKind> Drop for Guard<'_, T, P> {
+            #[inline(always)]
+            fn drop(&mut self) {
+                // SAFETY: The pointer is not used again.
+                let ptr: P::Pointer<T> = unsafe { ManuallyDrop::take(&mut self.ptr) };
+                let (ptr, kind) = P::into_raw::<T>(ptr);
+
+                // The old kind instance was moved to the pointer, so it must not be dropped,
+                // which this assignment does not do.
+                self.this.ptr = ptr;
+                self.this.kind = ManuallyDrop::new(kind);
+            }
+        }
+
+        // SAFETY: The caller guarantees that the pointer is not static.
+        let ptr = unsafe { self.pointer() };
+        let mut guard = Guard { this: self, ptr };
+
+        f(&mut guard.ptr)
+    }
+
+    /// # Safety
+    ///
+    /// The pointer must not be static, and it must be the only reference to its value.
+    #[inline(always)]
+    unsafe fn get_mut_unchecked(&mut self) -> &mut T {
+        // SAFETY: By the caller's guarantee, nothing else can access the value.
+        unsafe { self.ptr.as_mut() }
+    }
+
+    /// Creates a `SharedPointer` from the smart pointer its kind is a type constructor of (for
+    /// example [`Rc<T>`](alloc::rc::Rc) for [`RcK`](crate::RcK)). This does not copy `T` nor
+    /// change the reference count.
+    #[inline(always)]
+    pub fn from_inner_pointer(ptr: P::Pointer<T>) -> SharedPointer<T, P> {
+        SharedPointer::from_pointer(ptr)
+    }
+
+    /// Converts this `SharedPointer` into the smart pointer its kind is a type constructor of
+    /// (for example [`Rc<T>`](alloc::rc::Rc) for [`RcK`](crate::RcK)). This does not copy `T`
+    /// nor change the reference count.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pointer points to a [static value](SharedPointer::from_static).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    /// use std::sync::Arc;
+    ///
+    /// let ptr: SharedPointer<_, ArcK> = SharedPointer::new(42);
+    /// let arc: Arc<i32> = SharedPointer::into_inner_pointer(ptr);
+    ///
+    /// assert_eq!(*arc, 42);
+    /// ```
+    #[inline(always)]
+    pub fn into_inner_pointer(this: SharedPointer<T, P>) -> P::Pointer<T> {
+        SharedPointer::assert_not_static(&this);
+
+        // SAFETY: The pointer is not static.
+        unsafe { SharedPointer::into_pointer(this) }
     }
 
     #[inline(always)]
     pub fn as_ptr(this: &Self) -> *const T {
-        unsafe { this.ptr.as_ptr::<T>() }
+        this.ptr.as_ptr().map_addr(|addr| addr & !STATIC_TAG).cast_const()
     }
 
     #[inline(always)]
-    pub fn try_unwrap(mut this: SharedPointer<T, P>) -> Result<T, SharedPointer<T, P>> {
-        let ptr: P = unsafe { ManuallyDrop::take(&mut this.ptr) };
+    pub fn get_mut(this: &mut SharedPointer<T, P>) -> Option<&mut T> {
+        if SharedPointer::is_static(this) {
+            return None;
+        }
+
+        // SAFETY: The pointer is not static.
+        let ptr: Option<*mut T> =
+            unsafe { this.with_pointer_mut(|ptr| P::get_mut::<T>(ptr).map(ptr::from_mut)) };
+
+        // SAFETY: The value is only referenced by `this`, which is borrowed for as long as the
+        // returned reference.
+        ptr.map(|ptr| unsafe { &mut *ptr })
+    }
+
+    /// Returns `true` if this pointer points to a static value, i.e. if it was created by
+    /// [`SharedPointer::from_static()`] or cloned from such a pointer.
+    #[inline(always)]
+    pub fn is_static(this: &Self) -> bool {
+        this.ptr.as_ptr().addr() & STATIC_TAG != 0
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the pointer points to a [static value](SharedPointer::from_static).
+    #[inline(always)]
+    fn assert_not_static(this: &Self) {
+        assert!(
+            !SharedPointer::is_static(this),
+            "this operation is not supported by pointers to static values",
+        );
+    }
+
+    /// Consumes the pointer, returning a reference to its value that lives for as long as
+    /// needed. The reference owned by the pointer is never released, so the value is never
+    /// dropped (nor freed), as with [`Box::leak()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    ///
+    /// let ptr: SharedPointer<_, RcK> = SharedPointer::new(1944);
+    /// let value: &'static i32 = SharedPointer::leak(ptr);
+    ///
+    /// assert_eq!(*value, 1944);
+    /// ```
+    #[inline(always)]
+    pub fn leak<'a>(this: SharedPointer<T, P>) -> &'a T
+    where
+        T: 'a,
+    {
+        let ptr: *const T = SharedPointer::as_ptr(&this);
 
         mem::forget(this);
 
-        unsafe { ptr.try_unwrap::<T>() }.map_err(SharedPointer::new_from_inner)
+        // SAFETY: The reference owned by `this` was forgotten, so the value lives forever, and
+        // it can only be mutated through a unique pointer, which can no longer exist.
+        unsafe { &*ptr }
     }
 
+    /// Converts this pointer into a [`UniqueSharedPointer`] if it is the only reference to its
+    /// value (i.e. there are no other strong or weak pointers to it). Otherwise, returns the
+    /// pointer unchanged. This does not copy `T` nor change the reference count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    ///
+    /// let ptr: SharedPointer<_, ArcK> = SharedPointer::new(42);
+    /// let ptr_clone = SharedPointer::clone(&ptr);
+    ///
+    /// let ptr = SharedPointer::try_into_unique(ptr).unwrap_err();
+    ///
+    /// drop(ptr_clone);
+    ///
+    /// let mut unique = SharedPointer::try_into_unique(ptr).unwrap();
+    ///
+    /// *unique += 1;
+    ///
+    /// assert_eq!(*unique, 43);
+    /// ```
     #[inline(always)]
-    pub fn get_mut(this: &mut SharedPointer<T, P>) -> Option<&mut T> {
-        unsafe { this.ptr.get_mut::<T>() }
+    pub fn try_into_unique(
+        mut this: SharedPointer<T, P>,
+    ) -> Result<UniqueSharedPointer<T, P>, SharedPointer<T, P>> {
+        if SharedPointer::get_mut(&mut this).is_none() {
+            return Err(this);
+        }
+
+        let (ptr, kind) = SharedPointer::into_parts(this);
+
+        // SAFETY: `SharedPointer::get_mut()` checked that this is the only reference to a value
+        // in a reference-counted allocation.
+        Ok(unsafe { UniqueSharedPointer::from_parts(ptr, kind) })
     }
 
+    /// Returns the number of strong references to the value.
+    ///
+    /// [Static values](SharedPointer::from_static) are not reference-counted, and never dropped,
+    /// so this returns `usize::MAX` for them. Use [`SharedPointer::is_static()`] to tell them
+    /// apart.
     #[inline(always)]
     pub fn strong_count(this: &Self) -> usize {
-        unsafe { this.ptr.strong_count::<T>() }
+        if SharedPointer::is_static(this) {
+            return usize::MAX;
+        }
+
+        // SAFETY: The pointer is not static.
+        let ptr = unsafe { this.pointer() };
+
+        P::strong_count::<T>(&ptr)
+    }
+
+    /// Converts this pointer into a pointer to an unsized version of `T`, such as a trait object
+    /// or a slice.
+    ///
+    /// Prefer the safe [`coerce_shared_pointer!`](crate::coerce_shared_pointer) macro, which
+    /// calls this with a closure that only performs the coercion.
+    ///
+    /// # Safety
+    ///
+    /// `f` must return the pointer it receives, only changing its type through an
+    /// [unsizing coercion](https://doc.rust-lang.org/reference/type-coercions.html#unsized-coercions).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    /// use std::fmt::Display;
+    ///
+    /// let ptr: SharedPointer<i32, RcK> = SharedPointer::new(42);
+    /// let ptr: SharedPointer<dyn Display, RcK> = unsafe { SharedPointer::unsize(ptr, |p| p) };
+    ///
+    /// assert_eq!(ptr.to_string(), "42");
+    /// ```
+    #[inline(always)]
+    pub unsafe fn unsize<U: ?Sized>(
+        this: SharedPointer<T, P>,
+        f: fn(*const T) -> *const U,
+    ) -> SharedPointer<U, P> {
+        let (ptr, kind) = SharedPointer::into_parts(this);
+        // The coercion keeps the address, and with it the tag of static values.
+        let ptr: *const U = f(ptr.as_ptr());
+
+        // SAFETY: By the caller's guarantee, `ptr` is the same pointer, which is not null, after
+        // an unsizing coercion, which `P::from_raw()` accepts.
+        SharedPointer::from_parts(unsafe { NonNull::new_unchecked(ptr.cast_mut()) }, kind)
     }
 
+    /// Returns `true` if both pointers point to the same allocation. Like
+    /// [`ptr::addr_eq()`], this ignores the metadata of pointers to unsized types.
+    ///
+    /// To hash, compare and order pointers by the allocation they point to, for instance to use
+    /// them as keys of a map, wrap them in a [`ByAddress`](crate::shared_pointer::by_address::ByAddress).
     #[inline(always)]
     pub fn ptr_eq<PO: SharedPointerKind>(
         this: &SharedPointer<T, P>,
         other: &SharedPointer<T, PO>,
     ) -> bool {
-        ptr::eq(this.deref(), other.deref())
+        ptr::addr_eq(SharedPointer::as_ptr(this), SharedPointer::as_ptr(other))
+    }
+}
+
+impl<T: ?Sized, P> SharedPointer<T, P>
+where
+    P: SharedStatelessKind,
+{
+    /// Consumes the `SharedPointer`, returning the pointer to `T`. The strong reference is kept:
+    /// to avoid a memory leak the pointer must be converted back with
+    /// [`SharedPointer::from_raw()`] (or released with
+    /// [`SharedPointer::decrement_strong_count()`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pointer points to a [static value](SharedPointer::from_static).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    ///
+    /// let ptr: SharedPointer<_, ArcK> = SharedPointer::new(42);
+    /// let raw: *const i32 = SharedPointer::into_raw(ptr);
+    ///
+    /// let ptr: SharedPointer<_, ArcK> = unsafe { SharedPointer::from_raw(raw) };
+    ///
+    /// assert_eq!(*ptr, 42);
+    /// ```
+    #[inline(always)]
+    #[must_use = "losing the pointer will leak memory"]
+    pub fn into_raw(this: SharedPointer<T, P>) -> *const T {
+        SharedPointer::assert_not_static(&this);
+
+        // The kind is stateless, so `SharedPointer::from_raw()` rebuilds the pointer with the
+        // default kind instance.
+        let (ptr, _) = SharedPointer::into_parts(this);
+
+        ptr.as_ptr().cast_const()
+    }
+
+    /// Constructs a `SharedPointer` from a pointer returned by [`SharedPointer::into_raw()`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [`SharedPointer::into_raw()`] of a `SharedPointer<T, P>`
+    /// with the same `T` and `P`. Each call must be matched by a strong reference owned by `ptr`,
+    /// i.e. by a call to [`SharedPointer::into_raw()`] or
+    /// [`SharedPointer::increment_strong_count()`].
+    #[inline(always)]
+    pub unsafe fn from_raw(ptr: *const T) -> SharedPointer<T, P> {
+        // SAFETY: By the caller's guarantee, `ptr` is the pointer of the raw parts of a
+        // `P::Pointer<T>`, which is not null. The kind is stateless, so the default kind instance
+        // can take the place of the one returned with it.
+        SharedPointer::from_parts(unsafe { NonNull::new_unchecked(ptr.cast_mut()) }, P::default())
+    }
+
+    /// Increments the strong count of the value `ptr` points to.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [`SharedPointer::into_raw()`] of a `SharedPointer<T, P>`
+    /// with the same `T` and `P`, and the value must still be alive (its strong count must be at
+    /// least one).
+    #[inline(always)]
+    pub unsafe fn increment_strong_count(ptr: *const T) {
+        // SAFETY: The reference owned by `ptr` is not released, since the pointer is not dropped.
+        let this: ManuallyDrop<SharedPointer<T, P>> =
+            ManuallyDrop::new(unsafe { SharedPointer::from_raw(ptr) });
+
+        // The new reference is owned by `ptr`.
+        mem::forget(SharedPointer::clone(&this));
+    }
+
+    /// Decrements the strong count of the value `ptr` points to, dropping it if the count reaches
+    /// zero.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [`SharedPointer::into_raw()`] of a `SharedPointer<T, P>`
+    /// with the same `T` and `P`, and the value must still be alive (its strong count must be at
+    /// least one).
+    #[inline(always)]
+    pub unsafe fn decrement_strong_count(ptr: *const T) {
+        // SAFETY: The caller's guarantees are the ones of `SharedPointer::from_raw()`.
+        drop(unsafe { SharedPointer::<T, P>::from_raw(ptr) });
+    }
+}
+
+impl<T, P> SharedPointer<MaybeUninit<T>, P>
+where
+    P: SharedPointerKind,
+{
+    /// Converts to `SharedPointer<T, P>`.
+    ///
+    /// # Safety
+    ///
+    /// The value must be fully initialized. See [`MaybeUninit::assume_init()`].
+    #[inline(always)]
+    pub unsafe fn assume_init(this: SharedPointer<MaybeUninit<T>, P>) -> SharedPointer<T, P> {
+        let (ptr, kind) = SharedPointer::into_parts(this);
+
+        SharedPointer::from_parts(ptr.cast::<T>(), kind)
+    }
+}
+
+impl<T, P> SharedPointer<T, P>
+where
+    P: SharedWeakKind,
+{
+    /// Creates a `SharedPointer` to a value that holds a weak pointer to itself. The value is
+    /// returned by `f`, which is given a [`SharedWeak`] to the allocation being created. Calling
+    /// [`upgrade()`](SharedWeak::upgrade) on it inside `f` returns `None`, but it can be cloned
+    /// and stored in the value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    ///
+    /// struct Node {
+    ///     this: SharedWeak<Node, RcK>,
+    ///     value: i32,
+    /// }
+    ///
+    /// let node: SharedPointer<Node, RcK> =
+    ///     SharedPointer::new_cyclic(|this| Node { this: this.clone(), value: 1944 });
+    ///
+    /// assert_eq!(node.this.upgrade().unwrap().value, 1944);
+    /// ```
+    #[inline(always)]
+    pub fn new_cyclic(f: impl FnOnce(&SharedWeak<T, P>) -> T) -> SharedPointer<T, P> {
+        SharedPointer::from_pointer(P::new_cyclic::<T>(|weak: &P::Weak<T>| {
+            // SAFETY: The weak reference is owned by `P::new_cyclic()`, and this bitwise copy of it
+            // is never dropped, so it is only a borrowed view that lives as long as `weak`.
+            let weak: ManuallyDrop<SharedWeak<T, P>> =
+                ManuallyDrop::new(SharedWeak::from_weak(unsafe { ptr::read(weak) }));
+
+            f(&weak)
+        }))
+    }
+}
+
+impl<T: ?Sized, P> SharedPointer<T, P>
+where
+    P: SharedWeakKind,
+{
+    #[inline(always)]
+    pub fn downgrade(this: &SharedPointer<T, P>) -> SharedWeak<T, P> {
+        if SharedPointer::is_static(this) {
+            return SharedWeak::from_static(this.ptr);
+        }
+
+        // SAFETY: The pointer is not static.
+        let ptr = unsafe { this.pointer() };
+
+        SharedWeak::from_weak(P::downgrade::<T>(&ptr))
+    }
+
+    #[inline(always)]
+    pub fn weak_count(this: &Self) -> usize {
+        if SharedPointer::is_static(this) {
+            return 0;
+        }
+
+        // SAFETY: The pointer is not static.
+        let ptr = unsafe { this.pointer() };
+
+        P::weak_count::<T>(&ptr)
+    }
+}
+
+impl<T, P> SharedPointer<T, P>
+where
+    P: SharedFallibleKind,
+{
+    /// Like [`SharedPointer::new()`], but returns an [`AllocError`] instead of aborting if the
+    /// memory allocation fails.
+    ///
+    /// This is only available for [kinds that can report allocation failures](SharedFallibleKind).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "triomphe")]
+    /// # {
+    /// use archery::*;
+    ///
+    /// let ptr: Result<SharedPointer<_, ArcTK>, AllocError> = SharedPointer::try_new(42);
+    ///
+    /// assert_eq!(ptr.as_deref(), Ok(&42));
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn try_new(v: T) -> Result<SharedPointer<T, P>, AllocError> {
+        P::try_new::<T>(v).map(SharedPointer::from_pointer)
+    }
+
+    /// Like [`SharedPointer::from()`] a [`Box<T>`], but returns an [`AllocError`] instead of
+    /// aborting if the memory allocation fails. The value is moved from the box to the new
+    /// allocation without going through the stack.
+    #[inline(always)]
+    pub fn try_from_box(v: Box<T>) -> Result<SharedPointer<T, P>, AllocError> {
+        P::try_from_box::<T>(v).map(SharedPointer::from_pointer)
+    }
+}
+
+impl<T, P> SharedPointer<T, P>
+where
+    P: SharedIntoInnerKind,
+{
+    /// Returns the value if this is the only strong reference to it. Otherwise, drops this
+    /// pointer and returns `None`.
+    ///
+    /// Unlike [`SharedPointer::try_unwrap()`]`(this).ok()`, if this is called on every strong
+    /// reference to a value, it is guaranteed that exactly one of the calls returns the value,
+    /// even if they race with each other on different threads.
+    ///
+    /// This is only available for [kinds that release the last reference in the thread that drops
+    /// it](SharedIntoInnerKind), which is needed for that guarantee.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    ///
+    /// let ptr: SharedPointer<_, ArcK> = SharedPointer::new(42);
+    /// let ptr_clone = SharedPointer::clone(&ptr);
+    ///
+    /// let thread = std::thread::spawn(move || SharedPointer::into_inner(ptr_clone));
+    /// let value = SharedPointer::into_inner(ptr);
+    ///
+    /// assert_eq!(value.or(thread.join().unwrap()), Some(42));
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(this: SharedPointer<T, P>) -> Option<T> {
+        if SharedPointer::is_static(&this) {
+            return None;
+        }
+
+        // SAFETY: The pointer is not static.
+        P::into_inner::<T>(unsafe { SharedPointer::into_pointer(this) })
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, P> SharedPointer<T, P>
+where
+    P: SharedAllocatorKind,
+{
+    /// Creates a `SharedPointer` whose memory is allocated by `alloc`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![feature(allocator_api)]
+    ///
+    /// use archery::*;
+    /// use std::alloc::Global;
+    ///
+    /// let ptr: SharedPointer<_, RcAllocK<Global>> = SharedPointer::new_in(42, Global);
+    ///
+    /// assert_eq!(*ptr, 42);
+    /// ```
+    #[inline(always)]
+    pub fn new_in(v: T, alloc: P::Allocator) -> SharedPointer<T, P> {
+        SharedPointer::from_pointer(P::new_in::<T>(v, alloc))
+    }
+
+    /// Like [`SharedPointer::new_in()`], but returns an [`AllocError`] instead of aborting if the
+    /// memory allocation fails.
+    #[inline(always)]
+    pub fn try_new_in(v: T, alloc: P::Allocator) -> Result<SharedPointer<T, P>, AllocError> {
+        P::try_new_in::<T>(v, alloc).map(SharedPointer::from_pointer)
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T: ?Sized, P> SharedPointer<T, P>
+where
+    P: SharedAllocatorKind,
+{
+    /// Creates a `SharedPointer` from a [`Box`] allocated by the allocator of `P`. The memory of
+    /// the `SharedPointer` is allocated by the allocator of the box.
+    #[inline(always)]
+    pub fn from_box_in(v: Box<T, P::Allocator>) -> SharedPointer<T, P> {
+        SharedPointer::from_pointer(P::from_box_in::<T>(v))
+    }
+
+    #[inline(always)]
+    pub fn allocator(this: &Self) -> &P::Allocator {
+        this.kind.allocator()
     }
 }
 
@@ -148,7 +950,47 @@ where
 {
     #[inline(always)]
     pub fn make_mut(this: &mut SharedPointer<T, P>) -> &mut T {
-        unsafe { this.ptr.make_mut::<T>() }
+        if SharedPointer::is_static(this) {
+            *this = SharedPointer::new(T::clone(this));
+
+            // SAFETY: `this` is the only reference to its new allocation.
+            return unsafe { SharedPointer::get_mut_unchecked(this) };
+        }
+
+        // SAFETY: The pointer is not static.
+        let ptr: *mut T =
+            unsafe { this.with_pointer_mut(|ptr| ptr::from_mut(P::make_mut::<T>(ptr))) };
+
+        // SAFETY: The value is only referenced by `this`, which is borrowed for as long as the
+        // returned reference.
+        unsafe { &mut *ptr }
+    }
+
+    /// Returns the value if this is the only reference to it, and a clone of it otherwise.
+    #[inline(always)]
+    pub fn unwrap_or_clone(this: SharedPointer<T, P>) -> T {
+        SharedPointer::try_unwrap(this).unwrap_or_else(|this| T::clone(&this))
+    }
+
+    /// Mutates the value with `f`, cloning it first if this is not the only reference to it. See
+    /// [`SharedPointer::make_mut()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use archery::*;
+    ///
+    /// let mut ptr: SharedPointer<_, RcK> = SharedPointer::new(vec![1, 2, 3]);
+    /// let ptr_clone = SharedPointer::clone(&ptr);
+    ///
+    /// SharedPointer::update(&mut ptr, |v| v.push(4));
+    ///
+    /// assert_eq!(*ptr, [1, 2, 3, 4]);
+    /// assert_eq!(*ptr_clone, [1, 2, 3]);
+    /// ```
+    #[inline(always)]
+    pub fn update(this: &mut SharedPointer<T, P>, f: impl FnOnce(&mut T)) {
+        f(SharedPointer::make_mut(this));
     }
 }
 
@@ -163,7 +1005,7 @@ where
     }
 }
 
-impl<T, P> Deref for SharedPointer<T, P>
+impl<T: ?Sized, P> Deref for SharedPointer<T, P>
 where
     P: SharedPointerKind,
 {
@@ -171,11 +1013,13 @@ where
 
     #[inline(always)]
     fn deref(&self) -> &T {
-        unsafe { self.ptr.deref().deref() }
+        // SAFETY: The pointer, without the tag of static values, points to a value that is kept
+        // alive by `self`.
+        unsafe { &*SharedPointer::as_ptr(self) }
     }
 }
 
-impl<T, P> Borrow<T> for SharedPointer<T, P>
+impl<T: ?Sized, P> Borrow<T> for SharedPointer<T, P>
 where
     P: SharedPointerKind,
 {
@@ -185,7 +1029,7 @@ where
     }
 }
 
-impl<T, P> AsRef<T> for SharedPointer<T, P>
+impl<T: ?Sized, P> AsRef<T> for SharedPointer<T, P>
 where
     P: SharedPointerKind,
 {
@@ -195,17 +1039,24 @@ where
     }
 }
 
-impl<T, P> Clone for SharedPointer<T, P>
+impl<T: ?Sized, P> Clone for SharedPointer<T, P>
 where
     P: SharedPointerKind,
 {
     #[inline(always)]
     fn clone(&self) -> SharedPointer<T, P> {
-        SharedPointer::new_from_inner(unsafe { self.ptr.deref().clone::<T>() })
+        if SharedPointer::is_static(self) {
+            return SharedPointer::from_parts(self.ptr, P::default());
+        }
+
+        // SAFETY: The pointer is not static.
+        let ptr = unsafe { self.pointer() };
+
+        SharedPointer::from_pointer(P::clone::<T>(&ptr))
     }
 }
 
-impl<T, P> Hash for SharedPointer<T, P>
+impl<T: ?Sized, P> Hash for SharedPointer<T, P>
 where
     T: Hash,
     P: SharedPointerKind,
@@ -216,7 +1067,7 @@ where
     }
 }
 
-impl<T, P, PO> PartialEq<SharedPointer<T, PO>> for SharedPointer<T, P>
+impl<T: ?Sized, P, PO> PartialEq<SharedPointer<T, PO>> for SharedPointer<T, P>
 where
     T: PartialEq,
     P: SharedPointerKind,
@@ -233,14 +1084,14 @@ where
     }
 }
 
-impl<T, P> Eq for SharedPointer<T, P>
+impl<T: ?Sized, P> Eq for SharedPointer<T, P>
 where
     T: Eq,
     P: SharedPointerKind,
 {
 }
 
-impl<T, P, PO> PartialOrd<SharedPointer<T, PO>> for SharedPointer<T, P>
+impl<T: ?Sized, P, PO> PartialOrd<SharedPointer<T, PO>> for SharedPointer<T, P>
 where
     T: PartialOrd,
     P: SharedPointerKind,
@@ -272,7 +1123,7 @@ where
     }
 }
 
-impl<T, P> Ord for SharedPointer<T, P>
+impl<T: ?Sized, P> Ord for SharedPointer<T, P>
 where
     T: Ord,
     P: SharedPointerKind,
@@ -299,11 +1150,62 @@ where
 {
     #[inline(always)]
     fn from(v: Box<T>) -> SharedPointer<T, P> {
-        SharedPointer::new_from_inner(P::from_box(v))
+        SharedPointer::from_pointer(P::from_box(v))
+    }
+}
+
+impl<T, P> From<&[T]> for SharedPointer<[T], P>
+where
+    T: Clone,
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn from(v: &[T]) -> SharedPointer<[T], P> {
+        SharedPointer::from_pointer(P::from_slice::<T>(v))
+    }
+}
+
+impl<T, P> From<Vec<T>> for SharedPointer<[T], P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn from(v: Vec<T>) -> SharedPointer<[T], P> {
+        SharedPointer::from_pointer(P::from_vec::<T>(v))
+    }
+}
+
+impl<T, P> FromIterator<T> for SharedPointer<[T], P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> SharedPointer<[T], P> {
+        SharedPointer::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl<P> From<&str> for SharedPointer<str, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn from(v: &str) -> SharedPointer<str, P> {
+        SharedPointer::from_pointer(P::from_str(v))
     }
 }
 
-impl<T, P> Debug for SharedPointer<T, P>
+impl<P> From<String> for SharedPointer<str, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn from(v: String) -> SharedPointer<str, P> {
+        SharedPointer::from(v.as_str())
+    }
+}
+
+impl<T: ?Sized, P> Debug for SharedPointer<T, P>
 where
     T: Debug,
     P: SharedPointerKind,
@@ -314,7 +1216,7 @@ where
     }
 }
 
-impl<T, P> fmt::Pointer for SharedPointer<T, P>
+impl<T: ?Sized, P> fmt::Pointer for SharedPointer<T, P>
 where
     P: SharedPointerKind,
 {
@@ -324,7 +1226,7 @@ where
     }
 }
 
-impl<T, P> Display for SharedPointer<T, P>
+impl<T: ?Sized, P> Display for SharedPointer<T, P>
 where
     T: Display,
     P: SharedPointerKind,
@@ -335,48 +1237,83 @@ where
     }
 }
 
-impl<T, P> Drop for SharedPointer<T, P>
+impl<T: ?Sized, P> Drop for SharedPointer<T, P>
 where
     P: SharedPointerKind,
 {
     #[inline(always)]
     fn drop(&mut self) {
-        unsafe {
-            self.ptr.drop::<T>();
+        // SAFETY: `self` is not used again.
+        let kind: P = unsafe { ManuallyDrop::take(&mut self.kind) };
+
+        if !SharedPointer::is_static(self) {
+            // SAFETY: The pointer is not static, so its raw parts come from `P::into_raw()`.
+            drop(unsafe { P::from_raw::<T>(self.ptr, kind) });
         }
     }
 }
 
+/// Converts a [`SharedPointer<T, P>`](crate::shared_pointer::SharedPointer) into a
+/// [`SharedPointer<U, P>`](crate::shared_pointer::SharedPointer), where `U` is an unsized version
+/// of `T`, such as `dyn Trait` or `[E]` (from `[E; N]`).
+///
+/// This works for every [`SharedPointerKind`] on stable Rust. The target type must be known from
+/// the context.
+///
+/// With the `coerce_unsized` feature, which requires a nightly compiler, `SharedPointer`,
+/// [`SharedWeak`](crate::shared_pointer::weak::SharedWeak), and
+/// [`UniqueSharedPointer`](crate::shared_pointer::unique::UniqueSharedPointer) also implement
+/// [`CoerceUnsized`](core::ops::CoerceUnsized), so this conversion happens implicitly. Methods
+/// with a `self: SharedPointer<Self, P>` receiver on trait objects (through
+/// [`DispatchFromDyn`](core::ops::DispatchFromDyn)) are supported for the kinds that hold no
+/// state: `RcK`, `ArcK`, `ArcTK`, `BiasedArcK`, and `DynK`. The compiler only allows
+/// `DispatchFromDyn` for pointers whose other fields are zero-sized, which rules out the allocator
+/// kinds.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+/// use std::fmt::Display;
+///
+/// let ptr: SharedPointer<i32, ArcK> = SharedPointer::new(42);
+/// let ptr: SharedPointer<dyn Display, ArcK> = coerce_shared_pointer!(ptr);
+///
+/// assert_eq!(ptr.to_string(), "42");
+///
+/// let ptr: SharedPointer<[i32; 3], RcK> = SharedPointer::new([1, 2, 3]);
+/// let ptr: SharedPointer<[i32], RcK> = coerce_shared_pointer!(ptr);
+///
+/// assert_eq!(ptr.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! coerce_shared_pointer {
+    ($ptr:expr) => {
+        match $ptr {
+            // SAFETY: The closure only performs an unsizing coercion of its argument.
+            ptr => unsafe { $crate::shared_pointer::SharedPointer::unsize(ptr, |p| p) },
+        }
+    };
+}
+
+pub mod by_address;
+pub mod cell;
+pub mod future;
+#[cfg(feature = "std")]
+pub mod interner;
 pub mod kind;
+pub mod lock;
+pub mod once;
+pub mod static_pointer;
+pub mod thin;
+pub mod unique;
+pub mod wake;
+pub mod weak;
 
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
 #[cfg(feature = "serde")]
-pub mod serde {
-    use super::*;
-    use ::serde::de::{Deserialize, Deserializer};
-    use ::serde::ser::{Serialize, Serializer};
-
-    impl<T, P> Serialize for SharedPointer<T, P>
-    where
-        T: Serialize,
-        P: SharedPointerKind,
-    {
-        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-            self.as_ref().serialize(serializer)
-        }
-    }
-
-    impl<'de, T, P> Deserialize<'de> for SharedPointer<T, P>
-    where
-        T: Deserialize<'de>,
-        P: SharedPointerKind,
-    {
-        fn deserialize<D: Deserializer<'de>>(
-            deserializer: D,
-        ) -> Result<SharedPointer<T, P>, D::Error> {
-            T::deserialize(deserializer).map(SharedPointer::new)
-        }
-    }
-}
+pub mod serde;
 
 #[cfg(test)]
 mod test;
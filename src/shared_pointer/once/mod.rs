@@ -0,0 +1,384 @@
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::SharedLockKind;
+use crate::shared_pointer::kind::SharedOnceKind;
+use crate::shared_pointer::lock::SharedLock;
+use core::cell::OnceCell;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+#[cfg(not(feature = "std"))]
+mod spin;
+
+/// Cell that can be written only once, with a uniform API for the once-cell types of
+/// [`SharedOnceKind`](crate::shared_pointer::kind::SharedOnceKind)s.
+///
+/// This is implemented by [`OnceCell`] and by [`SyncOnceCell`], which can be shared between
+/// threads.
+pub trait SharedOnceCell<T>: Sized {
+    /// Creates an uninitialized cell.
+    fn new() -> Self;
+
+    /// Returns the value, if the cell is initialized.
+    fn get(&self) -> Option<&T>;
+
+    /// Returns the value, initializing the cell with `f` if it is not initialized.
+    ///
+    /// If `f` panics, the panic is propagated and the cell stays uninitialized.
+    fn get_or_init(&self, f: impl FnOnce() -> T) -> &T;
+
+    /// Initializes the cell with `value`, or returns `value` back if the cell is already
+    /// initialized.
+    fn set(&self, value: T) -> Result<(), T> {
+        let mut value = Some(value);
+
+        self.get_or_init(|| value.take().unwrap());
+
+        match value {
+            None => Ok(()),
+            Some(value) => Err(value),
+        }
+    }
+
+    fn get_mut(&mut self) -> Option<&mut T>;
+
+    fn into_inner(self) -> Option<T>;
+}
+
+impl<T> SharedOnceCell<T> for OnceCell<T> {
+    #[inline(always)]
+    fn new() -> OnceCell<T> {
+        OnceCell::new()
+    }
+
+    #[inline(always)]
+    fn get(&self) -> Option<&T> {
+        OnceCell::get(self)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `f` initializes the cell reentrantly.
+    #[inline(always)]
+    fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        OnceCell::get_or_init(self, f)
+    }
+
+    #[inline(always)]
+    fn set(&self, value: T) -> Result<(), T> {
+        OnceCell::set(self, value)
+    }
+
+    #[inline(always)]
+    fn get_mut(&mut self) -> Option<&mut T> {
+        OnceCell::get_mut(self)
+    }
+
+    #[inline(always)]
+    fn into_inner(self) -> Option<T> {
+        OnceCell::into_inner(self)
+    }
+}
+
+/// Cell that can be written only once and can be shared between threads.
+///
+/// With the `std` feature this is a [`std::sync::OnceLock`], otherwise threads that find the cell
+/// being initialized by another thread spin until it is initialized.
+///
+/// The operations are provided by the [`SharedOnceCell`] trait.
+pub struct SyncOnceCell<T> {
+    #[cfg(feature = "std")]
+    inner: OnceLock<T>,
+    #[cfg(not(feature = "std"))]
+    inner: spin::OnceLock<T>,
+}
+
+impl<T> SharedOnceCell<T> for SyncOnceCell<T> {
+    #[inline(always)]
+    fn new() -> SyncOnceCell<T> {
+        #[cfg(feature = "std")]
+        let inner = OnceLock::new();
+        #[cfg(not(feature = "std"))]
+        let inner = spin::OnceLock::new();
+
+        SyncOnceCell { inner }
+    }
+
+    #[inline(always)]
+    fn get(&self) -> Option<&T> {
+        self.inner.get()
+    }
+
+    /// Blocks while another thread initializes the cell.
+    #[inline(always)]
+    fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        self.inner.get_or_init(f)
+    }
+
+    #[inline(always)]
+    fn get_mut(&mut self) -> Option<&mut T> {
+        self.inner.get_mut()
+    }
+
+    #[inline(always)]
+    fn into_inner(self) -> Option<T> {
+        self.inner.into_inner()
+    }
+}
+
+impl<T> Default for SyncOnceCell<T> {
+    #[inline(always)]
+    fn default() -> SyncOnceCell<T> {
+        SyncOnceCell::new()
+    }
+}
+
+impl<T: Debug> Debug for SyncOnceCell<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_cell("SyncOnceCell", self.get(), f)
+    }
+}
+
+fn fmt_cell<T: Debug>(name: &str, value: Option<&T>, f: &mut Formatter<'_>) -> fmt::Result {
+    match value {
+        Some(value) => f.debug_tuple(name).field(value).finish(),
+        None => f.debug_tuple(name).field(&format_args!("<uninit>")).finish(),
+    }
+}
+
+/// Shared cell that can be written only once: all clones of a `SharedOnce` share the same cell,
+/// so they all observe the value it is initialized with.
+///
+/// The cell is a [`OnceCell`] for non-atomic pointer kinds, like [`RcK`](crate::RcK), or a
+/// [`SyncOnceCell`] for atomic ones, like [`ArcK`](crate::ArcK), picked by
+/// [`SharedOnceKind`](crate::shared_pointer::kind::SharedOnceKind).
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+///
+/// let config: SharedOnce<String, RcK> = SharedOnce::new();
+/// let config_clone = SharedOnce::clone(&config);
+///
+/// assert!(config_clone.get().is_none());
+///
+/// config.get_or_init(|| "parsed".to_owned());
+///
+/// assert_eq!(config_clone.get().map(String::as_str), Some("parsed"));
+/// ```
+pub struct SharedOnce<T, P>
+where
+    P: SharedOnceKind,
+{
+    ptr: SharedPointer<P::OnceCell<T>, P>,
+}
+
+impl<T, P> SharedOnce<T, P>
+where
+    P: SharedOnceKind,
+{
+    /// Creates an uninitialized `SharedOnce`.
+    #[inline(always)]
+    #[must_use]
+    pub fn new() -> SharedOnce<T, P> {
+        SharedOnce { ptr: SharedPointer::new(SharedOnceCell::new()) }
+    }
+
+    /// Returns the value, if it is initialized.
+    #[inline(always)]
+    #[must_use]
+    pub fn get(&self) -> Option<&T> {
+        self.ptr.get()
+    }
+
+    /// Returns the value, initializing it with `f` if it is not initialized. See
+    /// [`SharedOnceCell::get_or_init()`].
+    #[inline(always)]
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        self.ptr.get_or_init(f)
+    }
+
+    /// Initializes the value, or returns `value` back if it is already initialized.
+    #[inline(always)]
+    pub fn set(&self, value: T) -> Result<(), T> {
+        self.ptr.set(value)
+    }
+
+    /// Returns `true` if the two `SharedOnce`s share the same cell.
+    #[inline(always)]
+    #[must_use]
+    pub fn ptr_eq(this: &SharedOnce<T, P>, other: &SharedOnce<T, P>) -> bool {
+        SharedPointer::ptr_eq(&this.ptr, &other.ptr)
+    }
+}
+
+impl<T, P> Clone for SharedOnce<T, P>
+where
+    P: SharedOnceKind,
+{
+    #[inline(always)]
+    fn clone(&self) -> SharedOnce<T, P> {
+        SharedOnce { ptr: SharedPointer::clone(&self.ptr) }
+    }
+}
+
+impl<T, P> Default for SharedOnce<T, P>
+where
+    P: SharedOnceKind,
+{
+    #[inline(always)]
+    fn default() -> SharedOnce<T, P> {
+        SharedOnce::new()
+    }
+}
+
+impl<T, P> From<T> for SharedOnce<T, P>
+where
+    P: SharedOnceKind,
+{
+    /// Creates an initialized `SharedOnce`.
+    #[inline(always)]
+    fn from(value: T) -> SharedOnce<T, P> {
+        let once = SharedOnce::new();
+
+        once.get_or_init(|| value);
+
+        once
+    }
+}
+
+impl<T, P> Debug for SharedOnce<T, P>
+where
+    T: Debug,
+    P: SharedOnceKind,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_cell("SharedOnce", self.get(), f)
+    }
+}
+
+struct LazyInner<T, F, P>
+where
+    P: SharedOnceKind + SharedLockKind,
+{
+    cell: P::OnceCell<T>,
+    /// The function that computes the value, until it is taken to run it.
+    init: P::Lock<Option<F>>,
+}
+
+/// Shared value that is computed on first access: all clones of a `SharedLazy` share the same
+/// value, which is computed only once.
+///
+/// The value is stored in the once-cell of `P`, like in [`SharedOnce`]. Initialization happens on
+/// the first [dereference](Deref), or with [`SharedLazy::force()`].
+///
+/// Until it runs, `F` is kept in the lock of `P`, as defined by
+/// [`SharedLockKind`](crate::shared_pointer::kind::SharedLockKind). It is run at most once, so it
+/// can be an [`FnOnce`], and it is dropped once it has run. As with
+/// [`LazyCell`](core::cell::LazyCell), if `F` panics the panic is propagated and the `SharedLazy`
+/// is poisoned: every later access panics too.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+///
+/// let table: SharedLazy<Vec<u64>, _, ArcK> = SharedLazy::new(|| (0..10).map(|i| i * i).collect());
+/// let table_clone = SharedLazy::clone(&table);
+///
+/// assert!(SharedLazy::get(&table).is_none());
+/// assert_eq!(table_clone[3], 9);
+/// assert_eq!(SharedLazy::get(&table).map(Vec::len), Some(10));
+/// ```
+pub struct SharedLazy<T, F, P>
+where
+    P: SharedOnceKind + SharedLockKind,
+{
+    ptr: SharedPointer<LazyInner<T, F, P>, P>,
+}
+
+impl<T, F, P> SharedLazy<T, F, P>
+where
+    F: FnOnce() -> T,
+    P: SharedOnceKind + SharedLockKind,
+{
+    #[inline(always)]
+    #[must_use]
+    pub fn new(init: F) -> SharedLazy<T, F, P> {
+        let init = SharedLock::new(Some(init));
+
+        SharedLazy { ptr: SharedPointer::new(LazyInner { cell: SharedOnceCell::new(), init }) }
+    }
+
+    /// Returns the value, computing it if it was not computed yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if computing the value panicked before.
+    #[inline(always)]
+    pub fn force(this: &SharedLazy<T, F, P>) -> &T {
+        this.ptr.cell.get_or_init(|| {
+            // The lock is released before running `init`.
+            let init = this.ptr.init.write().take();
+
+            match init {
+                Some(init) => init(),
+                None => panic!("`SharedLazy` instance has previously been poisoned"),
+            }
+        })
+    }
+
+    /// Returns the value, if it was already computed.
+    #[inline(always)]
+    #[must_use]
+    pub fn get(this: &SharedLazy<T, F, P>) -> Option<&T> {
+        this.ptr.cell.get()
+    }
+
+    /// Returns `true` if the two `SharedLazy`s share the same value.
+    #[inline(always)]
+    #[must_use]
+    pub fn ptr_eq(this: &SharedLazy<T, F, P>, other: &SharedLazy<T, F, P>) -> bool {
+        SharedPointer::ptr_eq(&this.ptr, &other.ptr)
+    }
+}
+
+impl<T, F, P> Clone for SharedLazy<T, F, P>
+where
+    P: SharedOnceKind + SharedLockKind,
+{
+    #[inline(always)]
+    fn clone(&self) -> SharedLazy<T, F, P> {
+        SharedLazy { ptr: SharedPointer::clone(&self.ptr) }
+    }
+}
+
+impl<T, F, P> Deref for SharedLazy<T, F, P>
+where
+    F: FnOnce() -> T,
+    P: SharedOnceKind + SharedLockKind,
+{
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        SharedLazy::force(self)
+    }
+}
+
+impl<T, F, P> Debug for SharedLazy<T, F, P>
+where
+    T: Debug,
+    P: SharedOnceKind + SharedLockKind,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_cell("SharedLazy", self.ptr.cell.get(), f)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,116 @@
+//! Once cell that spins while another thread initializes it, used by
+//! [`SyncOnceCell`](super::SyncOnceCell) when `std` is not available.
+
+use core::cell::UnsafeCell;
+use core::hint;
+use core::mem;
+use core::mem::MaybeUninit;
+use core::sync::atomic::AtomicU8;
+use core::sync::atomic::Ordering;
+
+const UNINIT: u8 = 0;
+const RUNNING: u8 = 1;
+const INIT: u8 = 2;
+
+pub struct OnceLock<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// SAFETY: Same bounds as `std::sync::OnceLock`: any thread can initialize the value (so it must be
+// `Send`), and all threads holding a reference share it (so it must be `Sync`).
+unsafe impl<T: Send> Send for OnceLock<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceLock<T> {}
+
+/// Resets the state of a cell whose initialization panicked, so it can be initialized again.
+struct ResetOnUnwind<'a> {
+    state: &'a AtomicU8,
+}
+
+impl Drop for ResetOnUnwind<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.state.store(UNINIT, Ordering::Release);
+    }
+}
+
+impl<T> OnceLock<T> {
+    #[inline(always)]
+    pub const fn new() -> OnceLock<T> {
+        OnceLock { state: AtomicU8::new(UNINIT), value: UnsafeCell::new(MaybeUninit::uninit()) }
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> Option<&T> {
+        match self.state.load(Ordering::Acquire) {
+            // SAFETY: The value is initialized and never mutated again while shared.
+            INIT => Some(unsafe { (*self.value.get()).assume_init_ref() }),
+            _ => None,
+        }
+    }
+
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        loop {
+            if let Some(value) = self.get() {
+                return value;
+            }
+
+            match self.state.compare_exchange_weak(
+                UNINIT,
+                RUNNING,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    let reset = ResetOnUnwind { state: &self.state };
+                    let value = f();
+
+                    // SAFETY: The state is `RUNNING`, which we set, so no one else accesses the
+                    // value.
+                    let value: &T = unsafe { (*self.value.get()).write(value) };
+
+                    mem::forget(reset);
+                    self.state.store(INIT, Ordering::Release);
+
+                    return value;
+                }
+                Err(RUNNING) => {
+                    while self.state.load(Ordering::Relaxed) == RUNNING {
+                        hint::spin_loop();
+                    }
+                }
+                Err(_) => (),
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        match *self.state.get_mut() {
+            // SAFETY: The value is initialized.
+            INIT => Some(unsafe { self.value.get_mut().assume_init_mut() }),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub fn into_inner(mut self) -> Option<T> {
+        let state = mem::replace(self.state.get_mut(), UNINIT);
+
+        match state {
+            // SAFETY: The value is initialized, and the state was reset so it is not dropped
+            // again.
+            INIT => Some(unsafe { self.value.get_mut().assume_init_read() }),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Drop for OnceLock<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == INIT {
+            // SAFETY: The value is initialized.
+            unsafe { self.value.get_mut().assume_init_drop() };
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+#[cfg(feature = "triomphe")]
+use crate::shared_pointer::kind::ArcTK;
+use crate::shared_pointer::kind::RcK;
+use pretty_assertions::assert_eq;
+use static_assertions::assert_impl_all;
+use static_assertions::assert_not_impl_any;
+use static_assertions::assert_type_eq_all;
+use std::cell::Cell;
+use std::format;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::string::String;
+use std::string::ToString;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::vec::Vec;
+
+assert_type_eq_all!(<RcK as SharedOnceKind>::OnceCell<i32>, OnceCell<i32>);
+assert_type_eq_all!(<ArcK as SharedOnceKind>::OnceCell<i32>, SyncOnceCell<i32>);
+
+assert_impl_all!(SyncOnceCell<i32>: Send, Sync);
+assert_impl_all!(SharedOnce<i32, ArcK>: Send, Sync);
+assert_not_impl_any!(SharedOnce<i32, RcK>: Send, Sync);
+assert_not_impl_any!(SharedOnce<Cell<i32>, ArcK>: Send, Sync);
+assert_impl_all!(SharedLazy<i32, fn() -> i32, ArcK>: Send, Sync);
+assert_not_impl_any!(SharedLazy<i32, fn() -> i32, RcK>: Send, Sync);
+
+fn check_once<P: SharedOnceKind>() {
+    let once: SharedOnce<String, P> = SharedOnce::new();
+    let once_clone = SharedOnce::clone(&once);
+
+    assert!(SharedOnce::ptr_eq(&once, &once_clone));
+    assert!(once.get().is_none());
+
+    assert_eq!(once_clone.get_or_init(|| "a".to_string()), "a");
+    assert_eq!(once.get_or_init(|| unreachable!()), "a");
+    assert_eq!(once.get().map(String::as_str), Some("a"));
+    assert_eq!(once.set("b".to_string()), Err("b".to_string()));
+
+    let other: SharedOnce<String, P> = SharedOnce::new();
+
+    assert!(!SharedOnce::ptr_eq(&once, &other));
+    assert_eq!(other.set("b".to_string()), Ok(()));
+    assert_eq!(other.get().map(String::as_str), Some("b"));
+}
+
+#[test]
+fn test_once() {
+    check_once::<RcK>();
+    check_once::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_once::<ArcTK>();
+}
+
+#[test]
+fn test_once_from() {
+    let once: SharedOnce<i32, RcK> = SharedOnce::from(42);
+
+    assert_eq!(once.get(), Some(&42));
+}
+
+fn check_once_cell<C: SharedOnceCell<i32>>() {
+    let mut cell = C::new();
+
+    assert!(cell.get_mut().is_none());
+
+    cell.get_or_init(|| 1);
+
+    *cell.get_mut().unwrap() += 1;
+
+    assert_eq!(cell.get(), Some(&2));
+    assert_eq!(cell.into_inner(), Some(2));
+    assert_eq!(C::new().into_inner(), None);
+}
+
+#[test]
+fn test_once_cell() {
+    check_once_cell::<OnceCell<i32>>();
+    check_once_cell::<SyncOnceCell<i32>>();
+}
+
+#[test]
+fn test_sync_once_cell_drop() {
+    struct DropCounter<'a>(&'a AtomicUsize);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let dropped = AtomicUsize::new(0);
+    let cell: SyncOnceCell<DropCounter> = SyncOnceCell::new();
+
+    cell.get_or_init(|| DropCounter(&dropped));
+    drop(cell);
+
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+
+    drop(SyncOnceCell::<DropCounter>::new());
+
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+}
+
+fn check_init_panic<C: SharedOnceCell<i32>>() {
+    let cell = C::new();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| cell.get_or_init(|| panic!("init"))));
+
+    assert!(result.is_err());
+    assert!(cell.get().is_none());
+    assert_eq!(*cell.get_or_init(|| 1), 1);
+}
+
+#[test]
+fn test_init_panic() {
+    check_init_panic::<OnceCell<i32>>();
+    check_init_panic::<SyncOnceCell<i32>>();
+}
+
+#[test]
+fn test_once_threads() {
+    let once: SharedOnce<usize, ArcK> = SharedOnce::new();
+    let calls = AtomicUsize::new(0);
+
+    let values: Vec<usize> = thread::scope(|s| {
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let once = SharedOnce::clone(&once);
+                let calls = &calls;
+
+                s.spawn(move || {
+                    *once.get_or_init(|| {
+                        calls.fetch_add(1, Ordering::Relaxed);
+                        i
+                    })
+                })
+            })
+            .collect();
+
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+    assert!(values.iter().all(|v| Some(v) == once.get()));
+}
+
+fn check_lazy<P: SharedOnceKind + SharedLockKind>() {
+    let calls = Cell::new(0);
+    let lazy: SharedLazy<String, _, P> = SharedLazy::new(|| {
+        calls.set(calls.get() + 1);
+        "value".to_string()
+    });
+    let lazy_clone = SharedLazy::clone(&lazy);
+
+    assert!(SharedLazy::ptr_eq(&lazy, &lazy_clone));
+    assert!(SharedLazy::get(&lazy).is_none());
+    assert_eq!(calls.get(), 0);
+
+    assert_eq!(*lazy_clone, "value");
+    assert_eq!(SharedLazy::force(&lazy), "value");
+    assert_eq!(SharedLazy::get(&lazy).map(String::as_str), Some("value"));
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn test_lazy() {
+    check_lazy::<RcK>();
+}
+
+#[test]
+fn test_lazy_fn_once() {
+    let value = "value".to_string();
+    // Moves `value` out, so it is only `FnOnce`.
+    let lazy: SharedLazy<String, _, RcK> = SharedLazy::new(move || value);
+
+    assert_eq!(*lazy, "value");
+    assert_eq!(*lazy, "value");
+}
+
+#[test]
+fn test_lazy_poisoned_after_panic() {
+    let calls = Cell::new(0);
+    let lazy: SharedLazy<i32, _, RcK> = SharedLazy::new(|| {
+        calls.set(calls.get() + 1);
+
+        panic!("intentional panic in the initializer");
+    });
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| *lazy));
+
+    assert!(result.is_err());
+    assert!(SharedLazy::get(&lazy).is_none());
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| *lazy));
+
+    assert!(result.is_err());
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn test_lazy_threads() {
+    let calls = AtomicUsize::new(0);
+    let lazy: SharedLazy<Vec<usize>, _, ArcK> = SharedLazy::new(|| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        (0..100).collect()
+    });
+
+    thread::scope(|s| {
+        for _ in 0..8 {
+            let lazy = SharedLazy::clone(&lazy);
+
+            s.spawn(move || assert_eq!(lazy.len(), 100));
+        }
+    });
+
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_debug() {
+    let once: SharedOnce<i32, RcK> = SharedOnce::new();
+
+    assert_eq!(format!("{:?}", once), "SharedOnce(<uninit>)");
+
+    once.get_or_init(|| 1);
+
+    assert_eq!(format!("{:?}", once), "SharedOnce(1)");
+
+    let lazy: SharedLazy<i32, _, ArcK> = SharedLazy::new(|| 2);
+
+    assert_eq!(format!("{:?}", lazy), "SharedLazy(<uninit>)");
+    assert_eq!(*lazy, 2);
+    assert_eq!(format!("{:?}", lazy), "SharedLazy(2)");
+
+    let cell: SyncOnceCell<i32> = SyncOnceCell::new();
+
+    assert_eq!(format!("{:?}", cell), "SyncOnceCell(<uninit>)");
+}
@@ -0,0 +1,162 @@
+//! Zero-copy serialization of [`SharedPointer`]s with [rkyv](https://crates.io/crates/rkyv).
+//!
+//! A `SharedPointer<T, P>` is archived as an [`ArchivedRc`], like the `Rc` and `Arc` of the
+//! standard library. Values shared by several pointers are written once, and the deserialized
+//! pointers share values the same way. The [`Flavor`] of the archived pointer is
+//! [`SharedPointerFlavor<P>`], so pointers of different kinds are archived with different types:
+//! validation rejects an archive where pointers of two kinds share a value, which would otherwise
+//! be deserialized into a single allocation owned by both kinds. Pointers to
+//! [static values](SharedPointer::from_static) are not reference-counted by their kind, so each
+//! of them is archived with its own copy of the value.
+//!
+//! Pointers to unsized values, such as `SharedPointer<str, P>`, can be archived and accessed, but
+//! only pointers to sized values can be deserialized.
+//!
+//! # Example
+//!
+//! ```rust
+//! use archery::*;
+//! use rkyv::rancor::Error;
+//!
+//! let value: SharedPointer<u32, ArcK> = SharedPointer::new(1944);
+//! let pointers = vec![SharedPointer::clone(&value), value];
+//!
+//! let bytes = rkyv::to_bytes::<Error>(&pointers).unwrap();
+//! let pointers: Vec<SharedPointer<u32, ArcK>> = rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+//!
+//! assert_eq!(*pointers[0], 1944);
+//! assert!(SharedPointer::ptr_eq(&pointers[0], &pointers[1]));
+//! ```
+
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::SharedPointerKind;
+use crate::shared_pointer::kind::SharedStatelessKind;
+use ::rkyv::de::Pooling;
+use ::rkyv::de::PoolingExt;
+use ::rkyv::rancor::Fallible;
+use ::rkyv::rancor::Source;
+use ::rkyv::rc::ArchivedRc;
+use ::rkyv::rc::Flavor;
+use ::rkyv::rc::RcResolver;
+use ::rkyv::ser::Sharing;
+use ::rkyv::ser::Writer;
+use ::rkyv::ser::WriterExt;
+use ::rkyv::traits::ArchivePointee;
+use ::rkyv::{Archive, ArchiveUnsized, Deserialize, Place, Serialize, SerializeUnsized};
+use core::alloc::LayoutError;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+
+/// The [`Flavor`] of archived `SharedPointer`s of kind `P`.
+pub struct SharedPointerFlavor<P> {
+    _kind: PhantomData<fn() -> P>,
+}
+
+impl<P: SharedPointerKind + 'static> Flavor for SharedPointerFlavor<P> {
+    const ALLOW_CYCLES: bool = false;
+}
+
+impl<T: ?Sized, P> Archive for SharedPointer<T, P>
+where
+    T: ArchiveUnsized,
+    P: SharedPointerKind + 'static,
+{
+    type Archived = ArchivedRc<T::Archived, SharedPointerFlavor<P>>;
+    type Resolver = RcResolver;
+
+    fn resolve(&self, resolver: RcResolver, out: Place<Self::Archived>) {
+        ArchivedRc::resolve_from_ref(self.as_ref(), resolver, out);
+    }
+}
+
+impl<T: ?Sized, P, S> Serialize<S> for SharedPointer<T, P>
+where
+    T: SerializeUnsized<S> + 'static,
+    P: SharedPointerKind + 'static,
+    S: Fallible + Writer + Sharing + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<RcResolver, S::Error> {
+        if SharedPointer::is_static(self) {
+            // Sharing is keyed by address, so sharing a static value would let pointers of other
+            // kinds to the same value point to an archived value of the wrong flavor.
+            let pos = self.as_ref().serialize_unsized(serializer)?;
+
+            // The positions of archived pointers must be unique, as in
+            // `ArchivedRc::serialize_from_ref()`.
+            if serializer.pos() == pos {
+                serializer.pad(1)?;
+            }
+
+            return Ok(RcResolver::from_pos(pos));
+        }
+
+        ArchivedRc::<T::Archived, SharedPointerFlavor<P>>::serialize_from_ref(
+            self.as_ref(),
+            serializer,
+        )
+    }
+}
+
+// SAFETY: `alloc()` returns a pointer to an uninitialized `T` owned by a `SharedPointer`, and
+//         `from_value()` and `drop()` keep that ownership.
+unsafe impl<T, P> ::rkyv::de::SharedPointer<T> for SharedPointer<T, P>
+where
+    P: SharedStatelessKind,
+{
+    fn alloc((): ()) -> Result<*mut T, LayoutError> {
+        let ptr: SharedPointer<MaybeUninit<T>, P> = SharedPointer::new_uninit();
+
+        Ok(SharedPointer::into_raw(ptr).cast::<T>().cast_mut())
+    }
+
+    unsafe fn from_value(ptr: *mut T) -> *mut T {
+        // SAFETY: `ptr` was returned by `alloc()` and the value was initialized.
+        let ptr: SharedPointer<T, P> = unsafe {
+            SharedPointer::assume_init(SharedPointer::<MaybeUninit<T>, P>::from_raw(ptr.cast()))
+        };
+
+        SharedPointer::into_raw(ptr).cast_mut()
+    }
+
+    unsafe fn drop(ptr: *mut T) {
+        // SAFETY: `ptr` was returned by `from_value()`, and owns a strong reference.
+        drop(unsafe { SharedPointer::<T, P>::from_raw(ptr) });
+    }
+}
+
+impl<T, P, D> Deserialize<SharedPointer<T, P>, D>
+    for ArchivedRc<T::Archived, SharedPointerFlavor<P>>
+where
+    T: Archive + 'static,
+    T::Archived: Deserialize<T, D>,
+    P: SharedStatelessKind + 'static,
+    D: Fallible + Pooling + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<SharedPointer<T, P>, D::Error> {
+        let ptr = deserializer.deserialize_shared::<T, SharedPointer<T, P>>(self.get())?;
+
+        // SAFETY: The deserializer owns a strong reference to `ptr` until it is dropped, so we
+        //         need to get our own.
+        unsafe {
+            SharedPointer::<T, P>::increment_strong_count(ptr);
+
+            Ok(SharedPointer::from_raw(ptr))
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, P> PartialEq<SharedPointer<U, P>>
+    for ArchivedRc<T, SharedPointerFlavor<P>>
+where
+    T: ArchivePointee + PartialEq<U>,
+    P: SharedPointerKind,
+{
+    fn eq(&self, other: &SharedPointer<U, P>) -> bool {
+        self.get() == other.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,134 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+#[cfg(feature = "triomphe")]
+use crate::shared_pointer::kind::ArcTK;
+use crate::shared_pointer::kind::RcK;
+use ::rkyv::Archived;
+use ::rkyv::rancor::Error;
+use core::ptr;
+use pretty_assertions::assert_eq;
+use std::string::String;
+use std::string::ToString;
+use std::vec;
+use std::vec::Vec;
+
+fn check_roundtrip<P: SharedStatelessKind + 'static>() {
+    let a: SharedPointer<String, P> = SharedPointer::new("a".to_string());
+    let b: SharedPointer<String, P> = SharedPointer::new("b".to_string());
+    let pointers = vec![SharedPointer::clone(&a), b, a];
+
+    let bytes = ::rkyv::to_bytes::<Error>(&pointers).unwrap();
+    let archived =
+        ::rkyv::access::<Archived<Vec<SharedPointer<String, P>>>, Error>(&bytes).unwrap();
+
+    assert_eq!(archived.len(), 3);
+    assert_eq!(archived[0], pointers[0]);
+    assert_eq!(archived[1], pointers[1]);
+    assert!(ptr::eq(archived[0].get(), archived[2].get()));
+
+    let deserialized: Vec<SharedPointer<String, P>> =
+        ::rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+
+    assert_eq!(deserialized, pointers);
+    assert!(SharedPointer::ptr_eq(&deserialized[0], &deserialized[2]));
+    assert!(!SharedPointer::ptr_eq(&deserialized[0], &deserialized[1]));
+    assert_eq!(SharedPointer::strong_count(&deserialized[0]), 2);
+    assert_eq!(SharedPointer::strong_count(&deserialized[1]), 1);
+}
+
+#[test]
+fn test_roundtrip() {
+    check_roundtrip::<RcK>();
+    check_roundtrip::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_roundtrip::<ArcTK>();
+}
+
+#[test]
+fn test_shared_value_is_written_once() {
+    let value: SharedPointer<String, RcK> = SharedPointer::new("x".repeat(1024));
+
+    let bytes_one = ::rkyv::to_bytes::<Error>(&vec![SharedPointer::clone(&value)]).unwrap();
+    let bytes_many = ::rkyv::to_bytes::<Error>(&vec![SharedPointer::clone(&value); 16]).unwrap();
+
+    assert!(bytes_many.len() < bytes_one.len() + 1024);
+}
+
+#[test]
+fn test_mixed_kinds() {
+    let rc: SharedPointer<u64, RcK> = SharedPointer::new(1944);
+    let arc: SharedPointer<u64, ArcK> = SharedPointer::new(1984);
+    let pointers = (SharedPointer::clone(&rc), rc, arc);
+
+    let bytes = ::rkyv::to_bytes::<Error>(&pointers).unwrap();
+    let (rc_a, rc_b, arc): (
+        SharedPointer<u64, RcK>,
+        SharedPointer<u64, RcK>,
+        SharedPointer<u64, ArcK>,
+    ) = ::rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+
+    assert_eq!(*rc_a, 1944);
+    assert!(SharedPointer::ptr_eq(&rc_a, &rc_b));
+    assert_eq!(*arc, 1984);
+    assert_eq!(SharedPointer::strong_count(&arc), 1);
+}
+
+#[test]
+fn test_mixed_kinds_static() {
+    static VALUE: u64 = 42;
+
+    let rc: SharedPointer<u64, RcK> = SharedPointer::from_static(&VALUE);
+    let arc: SharedPointer<u64, ArcK> = SharedPointer::from_static(&VALUE);
+
+    let bytes = ::rkyv::to_bytes::<Error>(&(rc, arc)).unwrap();
+    let (rc, arc): (SharedPointer<u64, RcK>, SharedPointer<u64, ArcK>) =
+        ::rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+
+    assert_eq!(*rc, 42);
+    assert_eq!(*arc, 42);
+    assert_ne!(SharedPointer::as_ptr(&rc), SharedPointer::as_ptr(&arc));
+    assert_eq!(SharedPointer::strong_count(&rc), 1);
+    assert_eq!(SharedPointer::strong_count(&arc), 1);
+}
+
+#[test]
+fn test_mixed_kinds_shared_value_is_rejected() {
+    let value: SharedPointer<u64, RcK> = SharedPointer::new(1944);
+
+    // Both archived pointers point to the same value, so reading the second one as an `ArcK`
+    // pointer would make it share an `Rc` allocation.
+    let bytes = ::rkyv::to_bytes::<Error>(&(SharedPointer::clone(&value), value)).unwrap();
+    let result =
+        ::rkyv::from_bytes::<(SharedPointer<u64, RcK>, SharedPointer<u64, ArcK>), Error>(&bytes);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_static() {
+    static VALUE: u32 = 42;
+
+    let ptr: SharedPointer<u32, ArcK> = SharedPointer::from_static(&VALUE);
+
+    let bytes = ::rkyv::to_bytes::<Error>(&ptr).unwrap();
+    let deserialized: SharedPointer<u32, ArcK> = ::rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+
+    assert_eq!(*deserialized, 42);
+    assert!(!SharedPointer::is_static(&deserialized));
+}
+
+#[test]
+fn test_unsized() {
+    let name: SharedPointer<str, RcK> = SharedPointer::from("António Variações");
+    let years: SharedPointer<[u32], ArcK> = SharedPointer::from(vec![1944, 1984]);
+
+    let bytes = ::rkyv::to_bytes::<Error>(&(name, years)).unwrap();
+    let archived = ::rkyv::access::<
+        Archived<(SharedPointer<str, RcK>, SharedPointer<[u32], ArcK>)>,
+        Error,
+    >(&bytes)
+    .unwrap();
+
+    assert_eq!(archived.0.get(), "António Variações");
+    assert_eq!(archived.1.get(), [1944, 1984]);
+}
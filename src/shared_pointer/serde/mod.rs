@@ -0,0 +1,32 @@
+//! Serialization of [`SharedPointer`]s with [serde](https://crates.io/crates/serde).
+//!
+//! A `SharedPointer` is serialized as the value it points to, so values shared by several
+//! pointers are written once per pointer, and each pointer gets its own allocation when
+//! deserialized. The [`shared`] module offers a serialization that preserves the sharing.
+
+use super::*;
+use ::serde::de::{Deserialize, Deserializer};
+use ::serde::ser::{Serialize, Serializer};
+
+impl<T: ?Sized, P> Serialize for SharedPointer<T, P>
+where
+    T: Serialize,
+    P: SharedPointerKind,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de, T, P> Deserialize<'de> for SharedPointer<T, P>
+where
+    T: Deserialize<'de>,
+    P: SharedPointerKind,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<SharedPointer<T, P>, D::Error> {
+        T::deserialize(deserializer).map(SharedPointer::new)
+    }
+}
+
+#[cfg(feature = "std")]
+pub mod shared;
@@ -0,0 +1,339 @@
+//! Serialization of [`SharedPointer`]s that preserves sharing.
+//!
+//! Use it on `SharedPointer` fields with `#[serde(with = "archery::shared_pointer::serde::shared")]`. The first
+//! time an allocation is serialized its value is written along with an id, and every other
+//! pointer to that allocation is written as a back-reference to that id. When deserializing, each
+//! back-reference gets a clone of the pointer created for its id, so the deserialized data shares
+//! its values exactly like the serialized data did. This keeps the size of a DAG with heavy
+//! sharing proportional to the number of its nodes, rather than to the number of its paths.
+//!
+//! Ids are only meaningful within a session. To share values across the whole document,
+//! serialize and deserialize it wrapped in [`WithSharing`], which holds a session open while it
+//! is processed. Otherwise each pointer at the top of the document opens its own session, so only
+//! the values under it are shared.
+//!
+//! Sessions are kept in thread-local storage, so this requires the `std` feature. Deserialization
+//! requires `T` and `P` to be `'static`, so that back-references can be checked to point to
+//! values of the right type.
+//!
+//! # Example
+//!
+//! ```rust
+//! use archery::RcK;
+//! use archery::SharedPointer;
+//! use archery::shared_pointer::serde::shared::WithSharing;
+//! use serde::Deserialize;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Node {
+//!     value: i32,
+//!     #[serde(with = "archery::shared_pointer::serde::shared")]
+//!     next: SharedPointer<Option<Node>, RcK>,
+//! }
+//!
+//! let tail: SharedPointer<Option<Node>, RcK> = SharedPointer::new(None);
+//! let nodes = vec![
+//!     Node { value: 1, next: SharedPointer::clone(&tail) },
+//!     Node { value: 2, next: SharedPointer::clone(&tail) },
+//! ];
+//!
+//! let json = serde_json::to_string(&WithSharing(&nodes)).unwrap();
+//! let WithSharing(nodes): WithSharing<Vec<Node>> = serde_json::from_str(&json).unwrap();
+//!
+//! assert_eq!(nodes[1].value, 2);
+//! assert!(SharedPointer::ptr_eq(&nodes[0].next, &nodes[1].next));
+//! ```
+
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::SharedPointerKind;
+use ::serde::de;
+use ::serde::de::Deserialize;
+use ::serde::de::Deserializer;
+use ::serde::de::EnumAccess;
+use ::serde::de::SeqAccess;
+use ::serde::de::VariantAccess;
+use ::serde::de::Visitor;
+use ::serde::ser::Serialize;
+use ::serde::ser::SerializeTupleVariant;
+use ::serde::ser::Serializer;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::any::type_name;
+use core::cell::RefCell;
+use core::fmt;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::thread::LocalKey;
+
+const NAME: &str = "Shared";
+const VARIANTS: &[&str] = &["Value", "Ref"];
+
+/// A clone of a serialized pointer, which keeps its allocation alive until the end of the
+/// session, so that its address is not reused by another allocation.
+struct Retained {
+    ptr: *mut (),
+    drop: unsafe fn(*mut ()),
+}
+
+impl Retained {
+    fn new<T, P: SharedPointerKind>(ptr: SharedPointer<T, P>) -> Retained {
+        unsafe fn drop_boxed<T, P: SharedPointerKind>(ptr: *mut ()) {
+            // SAFETY: `ptr` was created by `Box::into_raw()` in `Retained::new::<T, P>()`.
+            drop(unsafe { Box::from_raw(ptr.cast::<SharedPointer<T, P>>()) });
+        }
+
+        Retained { ptr: Box::into_raw(Box::new(ptr)).cast::<()>(), drop: drop_boxed::<T, P> }
+    }
+}
+
+impl Drop for Retained {
+    fn drop(&mut self) {
+        // SAFETY: The session is closed before the serialization that opened it returns, so the
+        // values the pointer borrows are still alive.
+        unsafe { (self.drop)(self.ptr) }
+    }
+}
+
+#[derive(Default)]
+struct SerializeSession {
+    /// Ids of the serialized allocations, by address and type of the value.
+    ids: HashMap<(*const (), &'static str), u64>,
+    retained: Vec<Retained>,
+}
+
+#[derive(Default)]
+struct DeserializeSession {
+    /// The deserialized pointers, as `SharedPointer<T, P>`s, by id.
+    pointers: HashMap<u64, Box<dyn Any>>,
+}
+
+std::thread_local! {
+    static SERIALIZE_SESSION: RefCell<Option<SerializeSession>> = const { RefCell::new(None) };
+    static DESERIALIZE_SESSION: RefCell<Option<DeserializeSession>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` within a session, opening one if none is open. A session opened here is closed when
+/// `f` returns (or panics).
+fn with_session<S: Default + 'static, R>(
+    session: &'static LocalKey<RefCell<Option<S>>>,
+    f: impl FnOnce() -> R,
+) -> R {
+    struct Close<S: 'static>(&'static LocalKey<RefCell<Option<S>>>);
+
+    impl<S> Drop for Close<S> {
+        fn drop(&mut self) {
+            // The session is dropped after the borrow is released, since that drops pointers.
+            let session = self.0.with_borrow_mut(Option::take);
+
+            drop(session);
+        }
+    }
+
+    let opened = session.with_borrow_mut(|session| match session {
+        Some(_) => false,
+        None => {
+            *session = Some(S::default());
+            true
+        }
+    });
+    let _close = opened.then(|| Close(session));
+
+    f()
+}
+
+/// Serializes `ptr` as its value, the first time its allocation is serialized in the current
+/// session, or as a back-reference to it otherwise. Meant to be used with
+/// `#[serde(serialize_with = "archery::shared_pointer::serde::shared::serialize")]`, or `with` for both
+/// directions.
+pub fn serialize<T, P, S>(ptr: &SharedPointer<T, P>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    P: SharedPointerKind,
+    S: Serializer,
+{
+    with_session(&SERIALIZE_SESSION, || {
+        let key = (SharedPointer::as_ptr(ptr).cast::<()>(), type_name::<T>());
+        let (id, first) = SERIALIZE_SESSION.with_borrow_mut(|session| {
+            let session = session.get_or_insert_default();
+            let next_id = session.ids.len() as u64;
+
+            match session.ids.entry(key) {
+                Entry::Occupied(entry) => (*entry.get(), false),
+                Entry::Vacant(entry) => {
+                    entry.insert(next_id);
+                    session.retained.push(Retained::new(SharedPointer::clone(ptr)));
+
+                    (next_id, true)
+                }
+            }
+        });
+
+        if first {
+            let mut variant = serializer.serialize_tuple_variant(NAME, 0, "Value", 2)?;
+
+            variant.serialize_field(&id)?;
+            variant.serialize_field(&**ptr)?;
+            variant.end()
+        } else {
+            serializer.serialize_newtype_variant(NAME, 1, "Ref", &id)
+        }
+    })
+}
+
+/// Deserializes a pointer serialized by [`serialize()`]. A back-reference gives a clone of the
+/// pointer deserialized for its id in the current session. Meant to be used with
+/// `#[serde(deserialize_with = "archery::shared_pointer::serde::shared::deserialize")]`, or `with` for both
+/// directions.
+pub fn deserialize<'de, T, P, D>(deserializer: D) -> Result<SharedPointer<T, P>, D::Error>
+where
+    T: Deserialize<'de> + 'static,
+    P: SharedPointerKind + 'static,
+    D: Deserializer<'de>,
+{
+    with_session(&DESERIALIZE_SESSION, || {
+        deserializer.deserialize_enum(NAME, VARIANTS, SharedVisitor::<T, P>(PhantomData))
+    })
+}
+
+enum Variant {
+    Value,
+    Ref,
+}
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Variant, D::Error> {
+        struct VariantVisitor;
+
+        impl Visitor<'_> for VariantVisitor {
+            type Value = Variant;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                f.write_str("`Value` or `Ref`")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Variant, E> {
+                match v {
+                    0 => Ok(Variant::Value),
+                    1 => Ok(Variant::Ref),
+                    _ => Err(E::invalid_value(de::Unexpected::Unsigned(v), &self)),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Variant, E> {
+                match v {
+                    "Value" => Ok(Variant::Value),
+                    "Ref" => Ok(Variant::Ref),
+                    _ => Err(E::unknown_variant(v, VARIANTS)),
+                }
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Variant, E> {
+                match v {
+                    b"Value" => Ok(Variant::Value),
+                    b"Ref" => Ok(Variant::Ref),
+                    _ => Err(E::invalid_value(de::Unexpected::Bytes(v), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(VariantVisitor)
+    }
+}
+
+struct SharedVisitor<T, P>(PhantomData<fn() -> (T, P)>);
+
+impl<'de, T, P> Visitor<'de> for SharedVisitor<T, P>
+where
+    T: Deserialize<'de> + 'static,
+    P: SharedPointerKind + 'static,
+{
+    type Value = SharedPointer<T, P>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a shared value or a back-reference to one")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<SharedPointer<T, P>, A::Error> {
+        match data.variant()? {
+            (Variant::Value, variant) => {
+                variant.tuple_variant(2, ValueVisitor::<T, P>(PhantomData))
+            }
+            (Variant::Ref, variant) => {
+                let id: u64 = variant.newtype_variant()?;
+
+                DESERIALIZE_SESSION.with_borrow(|session| {
+                    let ptr =
+                        session.as_ref().and_then(|session| session.pointers.get(&id)).ok_or_else(
+                            || de::Error::custom(format_args!("unknown shared value id {id}")),
+                        )?;
+
+                    ptr.downcast_ref::<SharedPointer<T, P>>().cloned().ok_or_else(|| {
+                        de::Error::custom(format_args!(
+                            "shared value id {id} refers to a value of another type"
+                        ))
+                    })
+                })
+            }
+        }
+    }
+}
+
+struct ValueVisitor<T, P>(PhantomData<fn() -> (T, P)>);
+
+impl<'de, T, P> Visitor<'de> for ValueVisitor<T, P>
+where
+    T: Deserialize<'de> + 'static,
+    P: SharedPointerKind + 'static,
+{
+    type Value = SharedPointer<T, P>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("an id and a shared value")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<SharedPointer<T, P>, A::Error> {
+        let id: u64 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let value: T = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let ptr: SharedPointer<T, P> = SharedPointer::new(value);
+
+        DESERIALIZE_SESSION.with_borrow_mut(|session| {
+            match session.get_or_insert_default().pointers.entry(id) {
+                Entry::Occupied(_) => {
+                    Err(de::Error::custom(format_args!("duplicate shared value id {id}")))
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(Box::new(SharedPointer::clone(&ptr)));
+
+                    Ok(())
+                }
+            }
+        })?;
+
+        Ok(ptr)
+    }
+}
+
+/// Wrapper that holds a session open while its value is serialized or deserialized, so that
+/// all the pointers in it serialized with this module share values. See the
+/// [module documentation](self).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WithSharing<T>(pub T);
+
+impl<T: Serialize> Serialize for WithSharing<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        with_session(&SERIALIZE_SESSION, || self.0.serialize(serializer))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for WithSharing<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<WithSharing<T>, D::Error> {
+        with_session(&DESERIALIZE_SESSION, || T::deserialize(deserializer).map(WithSharing))
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,270 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+#[cfg(feature = "triomphe")]
+use crate::shared_pointer::kind::ArcTK;
+use crate::shared_pointer::kind::RcK;
+use pretty_assertions::assert_eq;
+use std::string::String;
+use std::string::ToString;
+use std::vec;
+
+/// A node of a DAG, whose children are serialized preserving sharing.
+#[derive(Debug)]
+struct Node<P: SharedPointerKind> {
+    value: i32,
+    children: Vec<Child<P>>,
+}
+
+#[derive(Debug)]
+struct Child<P: SharedPointerKind>(SharedPointer<Node<P>, P>);
+
+impl<P: SharedPointerKind> PartialEq for Node<P> {
+    fn eq(&self, other: &Node<P>) -> bool {
+        self.value == other.value && self.children == other.children
+    }
+}
+
+impl<P: SharedPointerKind> PartialEq for Child<P> {
+    fn eq(&self, other: &Child<P>) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<P: SharedPointerKind> Clone for Child<P> {
+    fn clone(&self) -> Child<P> {
+        Child(SharedPointer::clone(&self.0))
+    }
+}
+
+impl<P: SharedPointerKind> Serialize for Node<P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.value, &self.children).serialize(serializer)
+    }
+}
+
+impl<'de, P: SharedPointerKind + 'static> Deserialize<'de> for Node<P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Node<P>, D::Error> {
+        <(i32, Vec<Child<P>>)>::deserialize(deserializer)
+            .map(|(value, children)| Node { value, children })
+    }
+}
+
+impl<P: SharedPointerKind> Serialize for Child<P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, P: SharedPointerKind + 'static> Deserialize<'de> for Child<P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Child<P>, D::Error> {
+        deserialize(deserializer).map(Child)
+    }
+}
+
+fn leaf<P: SharedPointerKind>(value: i32) -> Child<P> {
+    Child(SharedPointer::new(Node { value, children: Vec::new() }))
+}
+
+/// Builds a DAG of `depth` levels where each node has two edges to the node of the level below,
+/// so it has `2^depth` paths.
+fn diamonds<P: SharedPointerKind>(depth: i32) -> Child<P> {
+    (1..=depth).fold(leaf(0), |child, value| {
+        Child(SharedPointer::new(Node { value, children: vec![child.clone(), child] }))
+    })
+}
+
+fn check_diamonds<P: SharedPointerKind + 'static>() {
+    let depth = 20;
+    let root: Child<P> = diamonds(depth);
+
+    let encoded = serde_json::to_string(&root).unwrap();
+
+    assert_eq!(encoded.matches("Value").count(), 21);
+    assert_eq!(encoded.matches("Ref").count(), 20);
+
+    let decoded: Child<P> = serde_json::from_str(&encoded).unwrap();
+    let mut node = &decoded;
+
+    for value in (1..=depth).rev() {
+        assert_eq!(node.0.value, value);
+        assert!(SharedPointer::ptr_eq(&node.0.children[0].0, &node.0.children[1].0));
+
+        node = &node.0.children[0];
+    }
+
+    assert_eq!(SharedPointer::strong_count(&node.0), 2);
+    assert!(node.0.children.is_empty());
+}
+
+#[test]
+fn test_diamonds() {
+    check_diamonds::<RcK>();
+    check_diamonds::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_diamonds::<ArcTK>();
+}
+
+#[test]
+fn test_format() {
+    let shared = leaf::<RcK>(7);
+    let root: Child<RcK> =
+        Child(SharedPointer::new(Node { value: 1, children: vec![shared.clone(), shared] }));
+
+    let encoded = serde_json::to_string(&root).unwrap();
+
+    assert_eq!(encoded, r#"{"Value":[0,[1,[{"Value":[1,[7,[]]]},{"Ref":1}]]]}"#);
+}
+
+#[test]
+fn test_with_sharing() {
+    let shared = leaf::<RcK>(7);
+    let children = vec![shared.clone(), leaf(8), shared];
+
+    let encoded = serde_json::to_string(&WithSharing(&children)).unwrap();
+
+    assert_eq!(encoded, r#"[{"Value":[0,[7,[]]]},{"Value":[1,[8,[]]]},{"Ref":0}]"#);
+
+    let WithSharing(decoded): WithSharing<Vec<Child<RcK>>> =
+        serde_json::from_str(&encoded).unwrap();
+
+    assert_eq!(decoded, children);
+    assert!(SharedPointer::ptr_eq(&decoded[0].0, &decoded[2].0));
+    assert!(!SharedPointer::ptr_eq(&decoded[0].0, &decoded[1].0));
+}
+
+#[test]
+fn test_without_with_sharing() {
+    let shared = leaf::<RcK>(7);
+    let children = vec![shared.clone(), shared];
+
+    let encoded = serde_json::to_string(&children).unwrap();
+
+    assert_eq!(encoded, r#"[{"Value":[0,[7,[]]]},{"Value":[0,[7,[]]]}]"#);
+
+    let decoded: Vec<Child<RcK>> = serde_json::from_str(&encoded).unwrap();
+
+    assert_eq!(decoded, children);
+    assert!(!SharedPointer::ptr_eq(&decoded[0].0, &decoded[1].0));
+}
+
+#[test]
+fn test_static() {
+    static VALUE: i32 = 42;
+
+    struct Ptrs<'a>(&'a [SharedPointer<i32, ArcK>]);
+
+    impl Serialize for Ptrs<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use ::serde::ser::SerializeSeq;
+
+            let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+
+            for ptr in self.0 {
+                seq.serialize_element(&Shared(ptr))?;
+            }
+
+            seq.end()
+        }
+    }
+
+    struct Shared<'a>(&'a SharedPointer<i32, ArcK>);
+
+    impl Serialize for Shared<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(self.0, serializer)
+        }
+    }
+
+    let ptrs: Vec<SharedPointer<i32, ArcK>> =
+        vec![SharedPointer::from_static(&VALUE), SharedPointer::from_static(&VALUE)];
+
+    let encoded = serde_json::to_string(&WithSharing(Ptrs(&ptrs))).unwrap();
+
+    assert_eq!(encoded, r#"[{"Value":[0,42]},{"Ref":0}]"#);
+}
+
+#[test]
+fn test_same_address_different_types() {
+    struct Outer {
+        inner: i32,
+    }
+
+    static OUTER: Outer = Outer { inner: 42 };
+
+    struct Both(SharedPointer<Outer, RcK>, SharedPointer<i32, RcK>);
+
+    struct Shared<'a, T>(&'a SharedPointer<T, RcK>);
+
+    impl<T: Serialize> Serialize for Shared<'_, T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(self.0, serializer)
+        }
+    }
+
+    impl Serialize for Outer {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.inner.serialize(serializer)
+        }
+    }
+
+    impl Serialize for Both {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            (Shared(&self.0), Shared(&self.1)).serialize(serializer)
+        }
+    }
+
+    let outer: SharedPointer<Outer, RcK> = SharedPointer::from_static(&OUTER);
+    let inner: SharedPointer<i32, RcK> = SharedPointer::from_static(&OUTER.inner);
+
+    let encoded = serde_json::to_string(&WithSharing(Both(outer, inner))).unwrap();
+
+    assert_eq!(encoded, r#"[{"Value":[0,42]},{"Value":[1,42]}]"#);
+}
+
+fn decode_error(json: &str) -> String {
+    serde_json::from_str::<WithSharing<Vec<Child<RcK>>>>(json).unwrap_err().to_string()
+}
+
+#[test]
+fn test_unknown_id() {
+    assert!(decode_error(r#"[{"Ref":0}]"#).starts_with("unknown shared value id 0"));
+}
+
+#[test]
+fn test_duplicate_id() {
+    assert!(
+        decode_error(r#"[{"Value":[0,[7,[]]]},{"Value":[0,[8,[]]]}]"#)
+            .starts_with("duplicate shared value id 0")
+    );
+}
+
+#[test]
+fn test_id_of_another_type() {
+    let error = serde_json::from_str::<WithSharing<(Child<RcK>, Child<ArcK>)>>(
+        r#"[{"Value":[0,[7,[]]]},{"Ref":0}]"#,
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert!(error.starts_with("shared value id 0 refers to a value of another type"));
+}
+
+#[test]
+fn test_session_closed() {
+    let _: WithSharing<Vec<Child<RcK>>> =
+        serde_json::from_str(r#"[{"Value":[0,[7,[]]]}]"#).unwrap();
+
+    assert!(decode_error(r#"[{"Ref":0}]"#).starts_with("unknown shared value id 0"));
+
+    let _ = serde_json::from_str::<WithSharing<Vec<Child<RcK>>>>(r#"[{"Value":[0,[7,[]]]}, 3]"#)
+        .unwrap_err();
+
+    assert!(decode_error(r#"[{"Ref":0}]"#).starts_with("unknown shared value id 0"));
+
+    let shared = leaf::<RcK>(7);
+
+    serde_json::to_string(&WithSharing(vec![shared.clone()])).unwrap();
+
+    assert_eq!(serde_json::to_string(&shared).unwrap(), r#"{"Value":[0,[7,[]]]}"#);
+    assert_eq!(SharedPointer::strong_count(&shared.0), 1);
+}
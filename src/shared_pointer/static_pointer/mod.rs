@@ -0,0 +1,121 @@
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::SharedPointerKind;
+use core::borrow::Borrow;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::ops::Deref;
+
+/// A value shared through [`SharedPointer`]s that never count references to it.
+///
+/// A `StaticSharedPointer` can be built in `const` contexts, so it can be declared in a `static`
+/// item. [`StaticSharedPointer::get()`] then gives a [`SharedPointer`] to its value, of any
+/// [`SharedPointerKind`], that can be cloned and dropped without touching any reference count.
+/// See [`SharedPointer::from_static()`].
+///
+/// Unlike [`SharedPointer::from_static()`] this works with values of any alignment, since the
+/// value is stored aligned to two bytes, leaving room for the tag that marks static pointers.
+/// Unsized values, such as byte slices, can be declared through a reference to a sized
+/// `StaticSharedPointer`, which coerces to the unsized one.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+///
+/// static EMPTY: StaticSharedPointer<Vec<i32>> = StaticSharedPointer::new(Vec::new());
+///
+/// fn empty<P: SharedPointerKind>() -> SharedPointer<Vec<i32>, P> {
+///     EMPTY.get()
+/// }
+///
+/// let ptr: SharedPointer<_, RcK> = empty();
+///
+/// assert!(SharedPointer::is_static(&ptr));
+/// assert!(ptr.is_empty());
+/// ```
+///
+/// ```rust
+/// use archery::*;
+///
+/// static BYTES: &StaticSharedPointer<[u8]> = &StaticSharedPointer::new(*b"archery");
+///
+/// let ptr: SharedPointer<[u8], ArcK> = BYTES.get();
+///
+/// assert!(SharedPointer::is_static(&ptr));
+/// assert_eq!(*ptr, *b"archery");
+/// ```
+#[repr(C, align(2))]
+pub struct StaticSharedPointer<T: ?Sized> {
+    value: T,
+}
+
+impl<T> StaticSharedPointer<T> {
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(value: T) -> StaticSharedPointer<T> {
+        StaticSharedPointer { value }
+    }
+}
+
+impl<T: ?Sized> StaticSharedPointer<T> {
+    /// Creates a [`SharedPointer`] to the value of this `StaticSharedPointer`. This does not
+    /// allocate memory nor copy the value.
+    #[inline(always)]
+    #[must_use]
+    pub fn get<P: SharedPointerKind>(&'static self) -> SharedPointer<T, P> {
+        // The value is aligned to two bytes, so its address is a multiple of two.
+        SharedPointer::new_static(&self.value)
+    }
+}
+
+impl<T: ?Sized> Deref for StaticSharedPointer<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: ?Sized> Borrow<T> for StaticSharedPointer<T> {
+    #[inline(always)]
+    fn borrow(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: ?Sized> AsRef<T> for StaticSharedPointer<T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: ?Sized, P> From<&'static StaticSharedPointer<T>> for SharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn from(v: &'static StaticSharedPointer<T>) -> SharedPointer<T, P> {
+        v.get()
+    }
+}
+
+impl<T: Debug + ?Sized> Debug for StaticSharedPointer<T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        Debug::fmt(&self.value, f)
+    }
+}
+
+impl<T: Display + ?Sized> Display for StaticSharedPointer<T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        Display::fmt(&self.value, f)
+    }
+}
+
+#[cfg(test)]
+mod test;
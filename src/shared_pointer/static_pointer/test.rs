@@ -0,0 +1,69 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+use crate::shared_pointer::kind::RcK;
+use pretty_assertions::assert_eq;
+use std::string::ToString;
+use std::vec::Vec;
+
+static EMPTY: StaticSharedPointer<Vec<i32>> = StaticSharedPointer::new(Vec::new());
+
+#[test]
+fn test_get() {
+    let ptr_rc: SharedPointer<Vec<i32>, RcK> = EMPTY.get();
+    let ptr_arc: SharedPointer<Vec<i32>, ArcK> = EMPTY.get();
+
+    assert!(SharedPointer::is_static(&ptr_rc));
+    assert!(SharedPointer::is_static(&ptr_arc));
+    assert!(SharedPointer::ptr_eq(&ptr_rc, &ptr_arc));
+    assert_eq!(SharedPointer::as_ptr(&ptr_rc), &raw const *EMPTY);
+}
+
+#[test]
+fn test_from() {
+    let ptr: SharedPointer<Vec<i32>, RcK> = SharedPointer::from(&EMPTY);
+
+    assert!(SharedPointer::is_static(&ptr));
+    assert!(ptr.is_empty());
+}
+
+#[test]
+fn test_deref() {
+    static VALUE: StaticSharedPointer<i32> = StaticSharedPointer::new(1944);
+
+    assert_eq!(*VALUE, 1944);
+    assert_eq!(*VALUE.as_ref(), 1944);
+}
+
+#[test]
+fn test_debug_display() {
+    static VALUE: StaticSharedPointer<i32> = StaticSharedPointer::new(1944);
+
+    assert_eq!(format!("{:?}", VALUE), "1944");
+    assert_eq!(VALUE.to_string(), "1944");
+}
+
+#[test]
+fn test_small_alignment() {
+    static VALUES: [StaticSharedPointer<u8>; 2] =
+        [StaticSharedPointer::new(19), StaticSharedPointer::new(44)];
+
+    let ptr_a: SharedPointer<u8, RcK> = VALUES[0].get();
+    let ptr_b: SharedPointer<u8, ArcK> = VALUES[1].get();
+    let ptr_b_clone = SharedPointer::clone(&ptr_b);
+
+    assert!(SharedPointer::is_static(&ptr_a));
+    assert!(SharedPointer::is_static(&ptr_b_clone));
+    assert_eq!(*ptr_a, 19);
+    assert_eq!(*ptr_b_clone, 44);
+}
+
+#[test]
+fn test_unsized() {
+    static BYTES: &StaticSharedPointer<[u8]> = &StaticSharedPointer::new([19, 44]);
+
+    let ptr: SharedPointer<[u8], RcK> = BYTES.get();
+
+    assert!(SharedPointer::is_static(&ptr));
+    assert_eq!(*ptr, [19, 44]);
+    assert_eq!(format!("{:?}", BYTES), "[19, 44]");
+}
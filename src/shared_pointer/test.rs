@@ -6,6 +6,7 @@ use kind::RcK;
 use static_assertions::assert_impl_all;
 use std::cell::Cell;
 use std::mem;
+use std::string::String;
 use std::string::ToString;
 
 assert_impl_all!(SharedPointer<i32, ArcK>: Send, Sync);
@@ -98,6 +99,55 @@ fn test_try_unwrap() {
     assert_eq!(*ptr_clone, 42);
 }
 
+#[test]
+fn test_try_convert_kind() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::try_convert_kind(ptr).unwrap();
+
+    assert_eq!(*ptr, 42);
+
+    let ptr_clone = SharedPointer::clone(&ptr);
+    let ptr = SharedPointer::try_convert_kind::<RcK>(ptr).unwrap_err();
+
+    assert_eq!(SharedPointer::strong_count(&ptr), 2);
+
+    drop(ptr_clone);
+
+    let ptr: SharedPointer<_, RcK> = SharedPointer::try_convert_kind(ptr).unwrap();
+
+    assert_eq!(*ptr, 42);
+}
+
+#[cfg(feature = "triomphe")]
+#[test]
+fn test_try_convert_kind_arct() {
+    use crate::shared_pointer::kind::ArcTK;
+
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::new(42);
+    let ptr: SharedPointer<_, ArcTK> = SharedPointer::try_convert_kind(ptr).unwrap();
+
+    assert_eq!(*ptr, 42);
+
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::try_convert_kind(ptr).unwrap();
+
+    assert_eq!(*ptr, 42);
+}
+
+#[test]
+fn test_convert_kind_cloning() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(vec![1, 2, 3]);
+    let ptr_clone = SharedPointer::clone(&ptr);
+
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::convert_kind_cloning(ptr);
+
+    assert_eq!(*ptr, [1, 2, 3]);
+    assert_eq!(SharedPointer::strong_count(&ptr_clone), 1);
+
+    let ptr_clone: SharedPointer<_, ArcK> = SharedPointer::convert_kind_cloning(ptr_clone);
+
+    assert_eq!(*ptr_clone, [1, 2, 3]);
+}
+
 #[test]
 fn test_get_mut() {
     let mut ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
@@ -177,6 +227,92 @@ fn test_make_mut() {
     assert_eq!(*ptr_clone, 44);
 }
 
+#[test]
+fn test_make_mut_with() {
+    let mut ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let addr: *const i32 = SharedPointer::as_ptr(&ptr);
+
+    *SharedPointer::make_mut_with(&mut ptr, |_| unreachable!()) += 1;
+
+    assert_eq!(*ptr, 43);
+    assert_eq!(SharedPointer::as_ptr(&ptr), addr);
+
+    let ptr_clone = SharedPointer::clone(&ptr);
+
+    *SharedPointer::make_mut_with(&mut ptr, |v| v * 10) += 1;
+
+    assert_eq!(*ptr, 431);
+    assert_eq!(*ptr_clone, 43);
+    assert_eq!(SharedPointer::strong_count(&ptr), 1);
+    assert_eq!(SharedPointer::strong_count(&ptr_clone), 1);
+}
+
+#[test]
+fn test_make_mut_with_weak() {
+    let mut ptr: SharedPointer<_, ArcK> = SharedPointer::new(42);
+    let weak = SharedPointer::downgrade(&ptr);
+
+    *SharedPointer::make_mut_with(&mut ptr, |v| *v) += 1;
+
+    assert_eq!(*ptr, 43);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_update() {
+    let mut ptr: SharedPointer<_, RcK> = SharedPointer::new(vec![1]);
+
+    SharedPointer::update(&mut ptr, |v| v.push(2));
+
+    let ptr_clone = SharedPointer::clone(&ptr);
+
+    SharedPointer::update(&mut ptr, |v| v.push(3));
+
+    assert_eq!(*ptr, [1, 2, 3]);
+    assert_eq!(*ptr_clone, [1, 2]);
+}
+
+#[test]
+fn test_unwrap_or_clone() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(vec![1, 2, 3]);
+    let ptr_clone = SharedPointer::clone(&ptr);
+
+    assert_eq!(SharedPointer::unwrap_or_clone(ptr), [1, 2, 3]);
+    assert_eq!(SharedPointer::strong_count(&ptr_clone), 1);
+    assert_eq!(SharedPointer::unwrap_or_clone(ptr_clone), [1, 2, 3]);
+}
+
+#[test]
+fn test_into_inner() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let ptr_clone = SharedPointer::clone(&ptr);
+
+    assert_eq!(SharedPointer::into_inner(ptr), None);
+    assert_eq!(SharedPointer::into_inner(ptr_clone), Some(42));
+}
+
+#[test]
+fn test_into_inner_threads() {
+    use std::thread;
+
+    for _ in 0..100 {
+        let ptr: SharedPointer<_, ArcK> = SharedPointer::new(42);
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let ptr = SharedPointer::clone(&ptr);
+
+                thread::spawn(move || SharedPointer::into_inner(ptr))
+            })
+            .collect();
+
+        let values: Vec<Option<i32>> = core::iter::once(SharedPointer::into_inner(ptr))
+            .chain(threads.into_iter().map(|t| t.join().unwrap()))
+            .collect();
+
+        assert_eq!(values.iter().flatten().collect::<Vec<_>>(), [&42]);
+    }
+}
+
 #[test]
 fn test_clone() {
     let ptr: SharedPointer<_, RcK> = SharedPointer::new(Cell::new(42));
@@ -195,7 +331,7 @@ fn test_clone() {
     assert_eq!(ptr_clone.get(), 3);
 }
 
-fn hash<T: Hash, P: SharedPointerKind>(pointer: &SharedPointer<T, P>) -> u64 {
+fn hash<T: Hash + ?Sized, P: SharedPointerKind>(pointer: &SharedPointer<T, P>) -> u64 {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
 
     pointer.hash(&mut hasher);
@@ -311,6 +447,573 @@ fn test_from_t() {
     assert_eq!(*ptr, 42);
 }
 
+mod static_check_fallible_kinds {
+    #[cfg(feature = "triomphe")]
+    use crate::ArcTK;
+    #[cfg(feature = "std")]
+    use crate::BiasedArcK;
+    use crate::{ArcK, RcK, SharedFallibleKind};
+    use static_assertions::*;
+
+    #[cfg(feature = "triomphe")]
+    assert_impl_all!(ArcTK: SharedFallibleKind);
+    #[cfg(feature = "std")]
+    assert_impl_all!(BiasedArcK: SharedFallibleKind);
+
+    // Without the `allocator_api` feature `RcK` and `ArcK` cannot report allocation failures.
+    #[cfg(not(feature = "allocator_api"))]
+    assert_not_impl_any!(RcK: SharedFallibleKind);
+    #[cfg(not(feature = "allocator_api"))]
+    assert_not_impl_any!(ArcK: SharedFallibleKind);
+    #[cfg(feature = "allocator_api")]
+    assert_impl_all!(RcK: SharedFallibleKind);
+    #[cfg(feature = "allocator_api")]
+    assert_impl_all!(ArcK: SharedFallibleKind);
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn test_try_new() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::try_new(42).unwrap();
+
+    assert_eq!(*ptr, 42);
+
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::try_new(String::from("hello")).unwrap();
+
+    assert_eq!(*ptr, "hello");
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn test_try_from_box() {
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::try_from_box(Box::new(42)).unwrap();
+
+    assert_eq!(*ptr, 42);
+
+    let ptr: SharedPointer<_, RcK> =
+        SharedPointer::try_from_box(Box::new(String::from("hello"))).unwrap();
+
+    assert_eq!(*ptr, "hello");
+}
+
+#[cfg(feature = "triomphe")]
+#[test]
+fn test_try_from_box_triomphe() {
+    let ptr: SharedPointer<_, crate::ArcTK> =
+        SharedPointer::try_from_box(Box::new([String::from("hello"), String::from("world")]))
+            .unwrap();
+
+    assert_eq!(*ptr, ["hello", "world"]);
+}
+
+#[test]
+fn test_alloc_error_display() {
+    assert_eq!(AllocError.to_string(), "memory allocation failed");
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn test_new_in() {
+    use crate::RcAllocK;
+    use alloc::alloc::Global;
+
+    let ptr: SharedPointer<_, RcAllocK<Global>> = SharedPointer::new_in(42, Global);
+    let ptr_clone = SharedPointer::clone(&ptr);
+
+    assert_eq!(*ptr_clone, 42);
+    assert_eq!(SharedPointer::strong_count(&ptr), 2);
+
+    let ptr: SharedPointer<_, RcK> = SharedPointer::try_new_in(42, Global).unwrap();
+
+    assert_eq!(*ptr, 42);
+    let _: &Global = SharedPointer::allocator(&ptr);
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn test_from_box_in() {
+    use crate::ArcAllocK;
+    use alloc::alloc::Global;
+
+    let boxed: Box<str, Global> = Box::from("hello");
+    let ptr: SharedPointer<str, ArcAllocK<Global>> = SharedPointer::from_box_in(boxed);
+
+    assert_eq!(&*ptr, "hello");
+
+    let ptr: SharedPointer<i32, ArcK> = SharedPointer::from_box_in(Box::new(42));
+
+    assert_eq!(*ptr, 42);
+}
+
+#[test]
+fn test_new_uninit() {
+    let mut ptr: SharedPointer<mem::MaybeUninit<String>, ArcK> = SharedPointer::new_uninit();
+
+    SharedPointer::get_mut(&mut ptr).unwrap().write(String::from("hello"));
+
+    let ptr: SharedPointer<String, ArcK> = unsafe { SharedPointer::assume_init(ptr) };
+
+    assert_eq!(*ptr, "hello");
+}
+
+#[test]
+fn test_new_zeroed() {
+    // Large enough to overflow the stack of the test thread if it was built on the stack.
+    const SIZE: usize = 4 * 1024 * 1024;
+
+    let ptr: SharedPointer<mem::MaybeUninit<[u8; SIZE]>, RcK> = SharedPointer::new_zeroed();
+    let ptr: SharedPointer<[u8; SIZE], RcK> = unsafe { SharedPointer::assume_init(ptr) };
+
+    assert!(ptr.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn test_from_slice() {
+    let ptr: SharedPointer<[i32], RcK> = SharedPointer::from(&[1, 2, 3][..]);
+
+    assert_eq!(&*ptr, &[1, 2, 3]);
+}
+
+#[test]
+fn test_from_vec() {
+    let ptr: SharedPointer<[i32], ArcK> = SharedPointer::from(vec![1, 2, 3]);
+
+    assert_eq!(&*ptr, &[1, 2, 3]);
+}
+
+#[test]
+fn test_from_iter() {
+    let ptr: SharedPointer<[i32], RcK> = (1..=3).collect();
+
+    assert_eq!(&*ptr, &[1, 2, 3]);
+}
+
+#[test]
+fn test_from_str() {
+    let ptr: SharedPointer<str, RcK> = SharedPointer::from("hello");
+
+    assert_eq!(&*ptr, "hello");
+    assert_eq!(ptr.len(), 5);
+}
+
+#[test]
+fn test_from_string() {
+    let ptr: SharedPointer<str, ArcK> = SharedPointer::from(String::from("hello"));
+
+    assert_eq!(&*ptr, "hello");
+}
+
+#[test]
+fn test_unsized() {
+    let mut ptr: SharedPointer<[i32], RcK> = SharedPointer::from(vec![1, 2, 3]);
+
+    SharedPointer::get_mut(&mut ptr).unwrap()[0] = 0;
+
+    let ptr_clone = SharedPointer::clone(&ptr);
+    let ptr_same_content: SharedPointer<[i32], RcK> = SharedPointer::from(vec![0, 2, 3]);
+
+    assert_eq!(SharedPointer::strong_count(&ptr), 2);
+    assert_eq!(SharedPointer::get_mut(&mut ptr), None);
+    assert!(SharedPointer::ptr_eq(&ptr, &ptr_clone));
+    assert!(!SharedPointer::ptr_eq(&ptr, &ptr_same_content));
+    assert_eq!(ptr, ptr_same_content);
+    assert_eq!(ptr.cmp(&ptr_same_content), Ordering::Equal);
+    assert_eq!(hash(&ptr), hash(&ptr_same_content));
+    assert_eq!(format!("{ptr:?}"), "[0, 2, 3]");
+}
+
+#[test]
+fn test_size() {
+    assert_eq!(mem::size_of::<SharedPointer<i32, RcK>>(), mem::size_of::<usize>());
+    assert_eq!(mem::size_of::<SharedPointer<i32, ArcK>>(), mem::size_of::<usize>());
+    assert_eq!(mem::size_of::<Option<SharedPointer<i32, RcK>>>(), mem::size_of::<usize>());
+    assert_eq!(mem::size_of::<SharedPointer<str, RcK>>(), 2 * mem::size_of::<usize>());
+    assert_eq!(mem::size_of::<SharedPointer<dyn ToString, ArcK>>(), 2 * mem::size_of::<usize>());
+}
+
+#[test]
+fn test_unsized_clone_drop() {
+    let ptr: SharedPointer<str, ArcK> = SharedPointer::from("hello");
+    let ptr_clone = SharedPointer::clone(&ptr);
+    let ptr_clone_clone = SharedPointer::clone(&ptr_clone);
+
+    drop(ptr);
+
+    assert_eq!(&*ptr_clone_clone, "hello");
+    assert_eq!(SharedPointer::strong_count(&ptr_clone), 2);
+
+    drop(ptr_clone);
+
+    assert_eq!(&*ptr_clone_clone, "hello");
+    assert_eq!(SharedPointer::strong_count(&ptr_clone_clone), 1);
+}
+
+#[test]
+fn test_unsized_str_display() {
+    let ptr: SharedPointer<str, RcK> = SharedPointer::from("hello");
+
+    assert_eq!(format!("{ptr}"), "hello");
+    assert_eq!(format!("{ptr:?}"), "\"hello\"");
+}
+
+#[test]
+fn test_unsize() {
+    let ptr: SharedPointer<i32, RcK> = SharedPointer::new(42);
+    let ptr_dyn: SharedPointer<dyn ToString, RcK> = unsafe { SharedPointer::unsize(ptr, |p| p) };
+
+    assert_eq!(ptr_dyn.to_string(), "42");
+}
+
+#[test]
+fn test_coerce_shared_pointer() {
+    let ptr: SharedPointer<i32, ArcK> = SharedPointer::new(42);
+    let ptr_clone = SharedPointer::clone(&ptr);
+    let ptr_dyn: SharedPointer<dyn ToString, ArcK> = coerce_shared_pointer!(ptr_clone);
+
+    assert_eq!(ptr_dyn.to_string(), "42");
+    assert_eq!(SharedPointer::strong_count(&ptr_dyn), 2);
+    assert!(ptr::addr_eq(SharedPointer::as_ptr(&ptr), SharedPointer::as_ptr(&ptr_dyn)));
+
+    let ptr: SharedPointer<[i32; 3], RcK> = SharedPointer::new([1, 2, 3]);
+    let ptr_slice: SharedPointer<[i32], RcK> = coerce_shared_pointer!(ptr);
+
+    assert_eq!(&*ptr_slice, &[1, 2, 3]);
+}
+
+#[test]
+fn test_coerce_shared_pointer_drop() {
+    struct SetOnDrop<'a>(&'a Cell<bool>);
+
+    impl Drop for SetOnDrop<'_> {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    trait Trait {}
+
+    impl Trait for SetOnDrop<'_> {}
+
+    let dropped = Cell::new(false);
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(SetOnDrop(&dropped));
+    let ptr_dyn: SharedPointer<dyn Trait, RcK> = coerce_shared_pointer!(ptr);
+    let weak = SharedPointer::downgrade(&ptr_dyn);
+
+    assert!(!dropped.get());
+
+    mem::drop(ptr_dyn);
+
+    assert!(dropped.get());
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_coerce_shared_pointer_static() {
+    static VALUE: i32 = 42;
+
+    let ptr: SharedPointer<i32, RcK> = SharedPointer::from_static(&VALUE);
+    let ptr: SharedPointer<dyn ToString, RcK> = coerce_shared_pointer!(ptr);
+
+    assert!(SharedPointer::is_static(&ptr));
+    assert_eq!(ptr.to_string(), "42");
+}
+
+#[cfg(feature = "coerce_unsized")]
+#[test]
+fn test_coerce_unsized() {
+    let ptr: SharedPointer<i32, ArcK> = SharedPointer::new(42);
+    let ptr_dyn: SharedPointer<dyn ToString, ArcK> = SharedPointer::<i32, ArcK>::clone(&ptr);
+    let weak: SharedWeak<dyn ToString, ArcK> = SharedPointer::<i32, ArcK>::downgrade(&ptr);
+    let slice: SharedPointer<[i32], RcK> = SharedPointer::new([1, 2, 3]);
+    let unique: UniqueSharedPointer<dyn ToString, RcK> = UniqueSharedPointer::new(42);
+
+    assert_eq!(ptr_dyn.to_string(), "42");
+    assert_eq!(SharedPointer::strong_count(&ptr_dyn), 2);
+    assert_eq!(weak.upgrade().unwrap().to_string(), "42");
+    assert_eq!(*slice, [1, 2, 3]);
+    assert_eq!(unique.to_string(), "42");
+
+    let weak_dangling: SharedWeak<dyn ToString, RcK> = SharedWeak::<i32, RcK>::new();
+
+    assert!(weak_dangling.upgrade().is_none());
+}
+
+#[cfg(feature = "coerce_unsized")]
+#[test]
+fn test_coerce_unsized_static() {
+    static VALUE: i32 = 42;
+
+    let ptr: SharedPointer<dyn ToString, RcK> = SharedPointer::<i32, RcK>::from_static(&VALUE);
+
+    assert!(SharedPointer::is_static(&ptr));
+    assert_eq!(ptr.to_string(), "42");
+}
+
+#[cfg(feature = "coerce_unsized")]
+#[test]
+fn test_dispatch_from_dyn() {
+    trait Name {
+        fn name(self: SharedPointer<Self, RcK>) -> String;
+    }
+
+    impl Name for i32 {
+        fn name(self: SharedPointer<Self, RcK>) -> String {
+            format!("i32 {}", *self)
+        }
+    }
+
+    let ptr: SharedPointer<dyn Name, RcK> = SharedPointer::new(42);
+
+    assert_eq!(ptr.name(), "i32 42");
+}
+
+#[test]
+fn test_from_static() {
+    static VALUE: String = String::new();
+
+    let ptr: SharedPointer<String, ArcK> = SharedPointer::from_static(&VALUE);
+    let mut ptr_clone = SharedPointer::clone(&ptr);
+
+    assert!(SharedPointer::is_static(&ptr));
+    assert!(SharedPointer::is_static(&ptr_clone));
+    assert_eq!(SharedPointer::as_ptr(&ptr), &raw const VALUE);
+    assert_eq!(SharedPointer::strong_count(&ptr), usize::MAX);
+    assert!(SharedPointer::get_mut(&mut ptr_clone).is_none());
+
+    SharedPointer::make_mut(&mut ptr_clone).push_str("hello");
+
+    assert!(!SharedPointer::is_static(&ptr_clone));
+    assert_eq!(*ptr, "");
+    assert_eq!(*ptr_clone, "hello");
+
+    let ptr = SharedPointer::try_unwrap(ptr).unwrap_err();
+
+    assert_eq!(SharedPointer::into_inner(SharedPointer::clone(&ptr)), None);
+    assert_eq!(SharedPointer::unwrap_or_clone(ptr), "");
+}
+
+#[test]
+fn test_from_static_weak() {
+    static VALUE: i32 = 42;
+
+    let ptr: SharedPointer<i32, RcK> = SharedPointer::from_static(&VALUE);
+    let weak = SharedPointer::downgrade(&ptr);
+
+    mem::drop(ptr);
+
+    let upgraded = weak.upgrade().unwrap();
+
+    assert!(SharedPointer::is_static(&upgraded));
+    assert_eq!(*upgraded, 42);
+}
+
+#[test]
+#[should_panic(expected = "this operation is not supported by pointers to static values")]
+fn test_from_static_into_raw() {
+    static VALUE: i32 = 42;
+
+    let ptr: SharedPointer<i32, RcK> = SharedPointer::from_static(&VALUE);
+    let _ = SharedPointer::into_raw(ptr);
+}
+
+#[test]
+fn test_from_static_unsized() {
+    static VALUES: [i32; 3] = [1, 2, 3];
+    static DISPLAY: i32 = 42;
+
+    let ptr: SharedPointer<[i32], RcK> = SharedPointer::from_static(&VALUES[..]);
+    let ptr_clone = SharedPointer::clone(&ptr);
+    let display: SharedPointer<dyn Display + Sync, ArcK> = SharedPointer::from_static(&DISPLAY);
+
+    assert!(SharedPointer::is_static(&ptr_clone));
+    assert_eq!(*ptr_clone, [1, 2, 3]);
+    assert_eq!(SharedPointer::as_ptr(&ptr).cast::<i32>(), VALUES.as_ptr());
+    assert_eq!(display.to_string(), "42");
+}
+
+#[test]
+fn test_from_static_zero_sized() {
+    static UNIT: () = ();
+    static EMPTY: [u8; 0] = [];
+
+    let unit: SharedPointer<(), RcK> = SharedPointer::from_static(&UNIT);
+    let empty: SharedPointer<[u8], ArcK> = SharedPointer::from_static(&EMPTY[..]);
+
+    assert!(SharedPointer::is_static(&unit));
+    assert!(SharedPointer::is_static(&SharedPointer::clone(&empty)));
+    assert_eq!(*unit, ());
+    assert!(empty.is_empty());
+    assert_eq!(SharedPointer::unwrap_or_clone(unit), ());
+}
+
+#[test]
+#[should_panic(expected = "static values must be aligned to at least two bytes")]
+fn test_from_static_unaligned() {
+    static VALUE: &str = "Variações";
+
+    let _: SharedPointer<str, RcK> = SharedPointer::from_static(VALUE);
+}
+
+#[test]
+fn test_from_static_threads() {
+    use std::thread;
+
+    static VALUE: i32 = 42;
+
+    let ptr: SharedPointer<i32, ArcK> = SharedPointer::from_static(&VALUE);
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            let ptr = SharedPointer::clone(&ptr);
+
+            s.spawn(move || {
+                for _ in 0..100 {
+                    let _ = SharedPointer::clone(&ptr);
+                }
+
+                assert_eq!(*ptr, 42);
+            });
+        }
+    });
+
+    assert!(SharedPointer::is_static(&ptr));
+}
+
+#[test]
+fn test_leak() {
+    static VALUE: i32 = 42;
+
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let weak = SharedPointer::downgrade(&ptr);
+    let value: &'static i32 = SharedPointer::leak(ptr);
+
+    assert_eq!(*value, 42);
+    assert_eq!(weak.strong_count(), 1);
+
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::from_static(&VALUE);
+
+    assert_eq!(ptr::from_ref(SharedPointer::leak(ptr)), &raw const VALUE);
+}
+
+#[test]
+fn test_from_inner_pointer() {
+    let rc = alloc::rc::Rc::new(42);
+    let ptr: SharedPointer<_, RcK> = SharedPointer::from_inner_pointer(alloc::rc::Rc::clone(&rc));
+
+    assert_eq!(SharedPointer::strong_count(&ptr), 2);
+    assert_eq!(SharedPointer::as_ptr(&ptr), alloc::rc::Rc::as_ptr(&rc));
+
+    let rc_back = SharedPointer::into_inner_pointer(ptr);
+
+    assert!(alloc::rc::Rc::ptr_eq(&rc, &rc_back));
+    assert_eq!(alloc::rc::Rc::strong_count(&rc), 2);
+}
+
+#[test]
+fn test_from_rc() {
+    let rc: alloc::rc::Rc<str> = alloc::rc::Rc::from("hello");
+    let ptr: SharedPointer<str, RcK> = SharedPointer::from(alloc::rc::Rc::clone(&rc));
+
+    assert!(ptr::addr_eq(SharedPointer::as_ptr(&ptr), alloc::rc::Rc::as_ptr(&rc)));
+
+    let rc_back: alloc::rc::Rc<str> = alloc::rc::Rc::from(ptr);
+
+    assert!(alloc::rc::Rc::ptr_eq(&rc, &rc_back));
+}
+
+#[test]
+fn test_from_arc() {
+    let arc = alloc::sync::Arc::new(42);
+    let ptr: SharedPointer<i32, ArcK> = SharedPointer::from(alloc::sync::Arc::clone(&arc));
+
+    assert_eq!(SharedPointer::as_ptr(&ptr), alloc::sync::Arc::as_ptr(&arc));
+
+    let arc_back: alloc::sync::Arc<i32> = alloc::sync::Arc::from(ptr);
+
+    assert!(alloc::sync::Arc::ptr_eq(&arc, &arc_back));
+    assert_eq!(alloc::sync::Arc::strong_count(&arc), 2);
+}
+
+#[cfg(feature = "triomphe")]
+#[test]
+fn test_from_triomphe_arc() {
+    use crate::ArcTK;
+
+    let arc = triomphe::Arc::new(42);
+    let ptr: SharedPointer<i32, ArcTK> = SharedPointer::from(triomphe::Arc::clone(&arc));
+
+    assert_eq!(SharedPointer::as_ptr(&ptr), triomphe::Arc::as_ptr(&arc));
+
+    let arc_back: triomphe::Arc<i32> = triomphe::Arc::from(ptr);
+
+    assert!(triomphe::Arc::ptr_eq(&arc, &arc_back));
+    assert_eq!(triomphe::Arc::count(&arc), 2);
+}
+
+#[test]
+fn test_into_raw_from_raw() {
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::new(String::from("hello"));
+    let ptr_clone = SharedPointer::clone(&ptr);
+    let raw = SharedPointer::into_raw(ptr);
+
+    assert_eq!(raw, SharedPointer::as_ptr(&ptr_clone));
+
+    let ptr: SharedPointer<String, ArcK> = unsafe { SharedPointer::from_raw(raw) };
+
+    assert_eq!(*ptr, "hello");
+    assert_eq!(SharedPointer::strong_count(&ptr), 2);
+}
+
+#[test]
+fn test_into_raw_from_raw_unsized() {
+    let ptr: SharedPointer<str, RcK> = SharedPointer::from("hello");
+    let raw: *const str = SharedPointer::into_raw(ptr);
+    let ptr: SharedPointer<str, RcK> = unsafe { SharedPointer::from_raw(raw) };
+
+    assert_eq!(&*ptr, "hello");
+}
+
+#[test]
+fn test_increment_decrement_strong_count() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let raw = SharedPointer::as_ptr(&ptr);
+
+    unsafe {
+        SharedPointer::<i32, RcK>::increment_strong_count(raw);
+    }
+
+    assert_eq!(SharedPointer::strong_count(&ptr), 2);
+
+    unsafe {
+        SharedPointer::<i32, RcK>::decrement_strong_count(raw);
+    }
+
+    assert_eq!(SharedPointer::strong_count(&ptr), 1);
+}
+
+#[test]
+fn test_decrement_strong_count_drops_value() {
+    struct SetOnDrop<'a>(&'a Cell<bool>);
+
+    impl Drop for SetOnDrop<'_> {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let dropped = Cell::new(false);
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::new(SetOnDrop(&dropped));
+    let raw = SharedPointer::into_raw(ptr);
+
+    assert!(!dropped.get());
+
+    unsafe {
+        SharedPointer::<SetOnDrop, ArcK>::decrement_strong_count(raw);
+    }
+
+    assert!(dropped.get());
+}
+
 #[test]
 fn test_debug() {
     let ptr: SharedPointer<_, RcK> = SharedPointer::new([1, 2, 3]);
@@ -0,0 +1,534 @@
+use crate::shared_pointer::STATIC_TAG;
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::SharedThinKind;
+use alloc::alloc::Layout;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::hash::Hash;
+use core::hash::Hasher;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Deref;
+use core::ptr;
+use core::ptr::NonNull;
+
+/// The header of a [`HeaderSlice`], along with the length of its slice.
+///
+/// This has the same layout as
+/// [`triomphe::HeaderWithLength`](https://docs.rs/triomphe/latest/triomphe/struct.HeaderWithLength.html),
+/// so that a `HeaderSlice` has the same layout as the value of a
+/// [`triomphe::ThinArc`](https://docs.rs/triomphe/latest/triomphe/struct.ThinArc.html).
+#[repr(C)]
+pub(crate) struct HeaderWithLength<H> {
+    pub(crate) header: H,
+    pub(crate) length: usize,
+}
+
+/// A header followed by a slice, stored together in a single allocation.
+///
+/// This is the value pointed to by a [`ThinSharedPointer`]. The length of the slice is stored
+/// after the header, so that a pointer to a `HeaderSlice` can be rebuilt from a thin pointer.
+#[repr(C)]
+pub struct HeaderSlice<H, T> {
+    header: HeaderWithLength<H>,
+    slice: [T],
+}
+
+impl<H, T> HeaderSlice<H, T> {
+    #[inline(always)]
+    pub fn header(&self) -> &H {
+        &self.header.header
+    }
+
+    #[inline(always)]
+    pub fn header_mut(&mut self) -> &mut H {
+        &mut self.header.header
+    }
+
+    #[inline(always)]
+    pub fn slice(&self) -> &[T] {
+        &self.slice
+    }
+
+    #[inline(always)]
+    pub fn slice_mut(&mut self) -> &mut [T] {
+        &mut self.slice
+    }
+}
+
+impl<H: Hash, T: Hash> Hash for HeaderSlice<H, T> {
+    #[inline(always)]
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        self.header().hash(state);
+        self.slice().hash(state);
+    }
+}
+
+impl<H: PartialEq, T: PartialEq> PartialEq for HeaderSlice<H, T> {
+    #[inline(always)]
+    fn eq(&self, other: &HeaderSlice<H, T>) -> bool {
+        self.header() == other.header() && self.slice() == other.slice()
+    }
+}
+
+impl<H: Eq, T: Eq> Eq for HeaderSlice<H, T> {}
+
+impl<H: PartialOrd, T: PartialOrd> PartialOrd for HeaderSlice<H, T> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &HeaderSlice<H, T>) -> Option<Ordering> {
+        match self.header().partial_cmp(other.header()) {
+            Some(Ordering::Equal) => self.slice().partial_cmp(other.slice()),
+            ordering => ordering,
+        }
+    }
+}
+
+impl<H: Ord, T: Ord> Ord for HeaderSlice<H, T> {
+    #[inline(always)]
+    fn cmp(&self, other: &HeaderSlice<H, T>) -> Ordering {
+        self.header().cmp(other.header()).then_with(|| self.slice().cmp(other.slice()))
+    }
+}
+
+impl<H: Debug, T: Debug> Debug for HeaderSlice<H, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("HeaderSlice")
+            .field("header", self.header())
+            .field("slice", &self.slice())
+            .finish()
+    }
+}
+
+/// A unit of memory of a [`HeaderSlice<H, T>`]: its size and alignment are the alignment of a
+/// `HeaderSlice<H, T>`, so the size of any `HeaderSlice<H, T>` is a multiple of it.
+///
+/// Smart pointers such as `Rc` cannot allocate a custom dynamically-sized type, but they can
+/// allocate a slice of units, which has the same layout as the `HeaderSlice<H, T>` it can hold.
+#[repr(C)]
+pub(crate) struct HeaderSliceUnit<H, T> {
+    _header: [HeaderWithLength<H>; 0],
+    _items: [T; 0],
+    _byte: u8,
+}
+
+/// Returns the number of [`HeaderSliceUnit`]s taken by a [`HeaderSlice`] of `length` items.
+///
+/// # Panics
+///
+/// Panics if the size of the `HeaderSlice` overflows.
+pub(crate) fn unit_count<H, T>(length: usize) -> usize {
+    let layout = Layout::array::<T>(length)
+        .and_then(|slice_layout| Layout::new::<HeaderWithLength<H>>().extend(slice_layout))
+        .expect("capacity overflow")
+        .0
+        .pad_to_align();
+
+    layout.size() / mem::size_of::<HeaderSliceUnit<H, T>>()
+}
+
+/// Writes a [`HeaderSlice`] with `header` and the items yielded by `items` to `mem`.
+///
+/// # Panics
+///
+/// Panics if `items` does not yield exactly as many items as its length. The items written so far
+/// are dropped, and the memory is left to the caller.
+///
+/// # Safety
+///
+/// `mem` must be valid for writes of [`unit_count::<H, T>(items.len())`](unit_count) units.
+pub(crate) unsafe fn write_header_and_iter<H, T, I>(
+    mem: *mut HeaderSliceUnit<H, T>,
+    header: H,
+    mut items: I,
+) where
+    I: ExactSizeIterator<Item = T>,
+{
+    /// Drops the items written so far if the iterator panics.
+    struct Guard<T> {
+        slice: *mut T,
+        initialized: usize,
+    }
+
+    impl<T> Drop for Guard<T> {
+        fn drop(&mut self) {
+            // SAFETY: The first `initialized` items of the slice were written.
+            unsafe {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.slice, self.initialized));
+            }
+        }
+    }
+
+    let length = items.len();
+    let ptr = header_slice_ptr::<H, T>(mem, length).cast_mut();
+    let mut guard = Guard {
+        // SAFETY: By the caller's guarantee `ptr` points to memory that can hold the value.
+        slice: unsafe { (&raw mut (*ptr).slice).cast::<T>() },
+        initialized: 0,
+    };
+
+    while guard.initialized < length {
+        let item = items.next().expect("ExactSizeIterator over-reported length");
+
+        // SAFETY: The slice has room for `length` items.
+        unsafe { guard.slice.add(guard.initialized).write(item) };
+
+        guard.initialized += 1;
+    }
+
+    assert!(items.next().is_none(), "ExactSizeIterator under-reported length");
+
+    mem::forget(guard);
+
+    // SAFETY: `ptr` points to memory that can hold the value.
+    unsafe { (&raw mut (*ptr).header).write(HeaderWithLength { header, length }) };
+}
+
+/// Returns a pointer to the [`HeaderSlice`] of `length` items that starts at `mem`.
+#[inline(always)]
+pub(crate) fn header_slice_ptr<H, T>(
+    mem: *const HeaderSliceUnit<H, T>,
+    length: usize,
+) -> *const HeaderSlice<H, T> {
+    // This is the layout of `HeaderSlice<H, T>`, which is `repr(C)`: the metadata of the pointer
+    // is the length of its slice.
+    ptr::slice_from_raw_parts(mem, length) as *const HeaderSlice<H, T>
+}
+
+/// Pointer to a [`HeaderSlice`] with reference-counting, which is a single machine word.
+///
+/// A [`SharedPointer`] to a `HeaderSlice` is a fat pointer, since it needs to store the length
+/// of the slice. A `ThinSharedPointer` reads that length from the allocation instead, so it is as
+/// small as a pointer to a sized type, and so is an `Option<ThinSharedPointer<H, T, P>>`. This is
+/// useful for recursive data structures with nodes of variable size, which can keep their
+/// children inline in the allocation of the node rather than in a separate [`Vec`].
+///
+/// The supported pointer kinds are the ones that implement [`SharedThinKind`]. With `ArcTK` a
+/// `ThinSharedPointer` can be converted from and to a
+/// [`triomphe::ThinArc`](https://docs.rs/triomphe/latest/triomphe/struct.ThinArc.html). This does
+/// not copy the value nor change the reference count, but converting a `ThinSharedPointer` to a
+/// [static value](SharedPointer::from_static) panics, since a `ThinArc` is always
+/// reference-counted.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+///
+/// struct Node<P: SharedThinKind> {
+///     children: ThinSharedPointer<char, Option<Node<P>>, P>,
+/// }
+///
+/// let leaf: Node<RcK> = Node {
+///     children: ThinSharedPointer::from_header_and_iter('a', [None, None].into_iter()),
+/// };
+/// let root: Node<RcK> = Node {
+///     children: ThinSharedPointer::from_header_and_iter('r', [Some(leaf)].into_iter()),
+/// };
+///
+/// assert_eq!(size_of::<Option<ThinSharedPointer<char, u8, RcK>>>(), size_of::<usize>());
+/// assert_eq!(*root.children.header(), 'r');
+/// assert_eq!(root.children.slice().len(), 1);
+/// ```
+///
+/// [`Vec`]: alloc::vec::Vec
+pub struct ThinSharedPointer<H, T, P>
+where
+    P: SharedThinKind,
+{
+    /// Points to the start of the `HeaderSlice<H, T>`, which holds the length of the slice.
+    /// This is the pointer of a [`SharedPointer`], without its metadata: either it owns a strong
+    /// reference, or it points to a static value and is tagged with [`STATIC_TAG`].
+    ptr: NonNull<HeaderWithLength<H>>,
+    _phantom: PhantomData<SharedPointer<HeaderSlice<H, T>, P>>,
+}
+
+unsafe impl<H: Sync + Send, T: Sync + Send, P: Send> Send for ThinSharedPointer<H, T, P> where
+    P: SharedThinKind
+{
+}
+unsafe impl<H: Sync + Send, T: Sync + Send, P: Sync> Sync for ThinSharedPointer<H, T, P> where
+    P: SharedThinKind
+{
+}
+
+impl<H, T, P> Unpin for ThinSharedPointer<H, T, P> where P: SharedThinKind {}
+
+impl<H, T, P> ThinSharedPointer<H, T, P>
+where
+    P: SharedThinKind,
+{
+    /// Creates a `ThinSharedPointer` to a [`HeaderSlice`] with `header` and the items yielded by
+    /// `items`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` does not yield exactly as many items as its length.
+    #[inline(always)]
+    pub fn from_header_and_iter<I>(header: H, items: I) -> ThinSharedPointer<H, T, P>
+    where
+        I: ExactSizeIterator<Item = T>,
+    {
+        ThinSharedPointer::from(SharedPointer::from_inner_pointer(P::from_header_and_iter(
+            header, items,
+        )))
+    }
+
+    /// Creates a `ThinSharedPointer` to a [`HeaderSlice`] with `header` and clones of the items
+    /// of `items`.
+    #[inline(always)]
+    pub fn from_header_and_slice(header: H, items: &[T]) -> ThinSharedPointer<H, T, P>
+    where
+        T: Clone,
+    {
+        ThinSharedPointer::from_header_and_iter(header, items.iter().cloned())
+    }
+
+    /// Rebuilds the (fat) pointer of the [`SharedPointer`], which keeps the [`STATIC_TAG`].
+    #[inline(always)]
+    fn tagged_fat_ptr(&self) -> NonNull<HeaderSlice<H, T>> {
+        let header = self.ptr.as_ptr().map_addr(|addr| addr & !STATIC_TAG);
+        // SAFETY: `header` points to a live `HeaderSlice<H, T>`, which starts with a
+        // `HeaderWithLength<H>` since it is `repr(C)`.
+        let length: usize = unsafe { (*header).length };
+        let ptr = ptr::slice_from_raw_parts_mut(self.ptr.as_ptr().cast::<T>(), length)
+            as *mut HeaderSlice<H, T>;
+
+        // SAFETY: `ptr` has the address of `self.ptr`, which is not null.
+        unsafe { NonNull::new_unchecked(ptr) }
+    }
+
+    /// Returns `true` if the pointer points to a static value, which is not reference-counted.
+    #[inline(always)]
+    fn is_static(&self) -> bool {
+        self.ptr.as_ptr().addr() & STATIC_TAG != 0
+    }
+
+    /// Converts this pointer into a [`SharedPointer`] to the same allocation. This does not change
+    /// the reference count.
+    #[inline(always)]
+    #[must_use]
+    pub fn into_shared(this: ThinSharedPointer<H, T, P>) -> SharedPointer<HeaderSlice<H, T>, P> {
+        let ptr = this.tagged_fat_ptr();
+
+        mem::forget(this);
+
+        // The kind instance of a pointer built by `SharedThinKind` is the default one.
+        SharedPointer::from_parts(ptr, P::default())
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn as_ptr(this: &Self) -> *const HeaderSlice<H, T> {
+        this.tagged_fat_ptr().as_ptr().map_addr(|addr| addr & !STATIC_TAG).cast_const()
+    }
+
+    #[inline(always)]
+    pub fn get_mut(this: &mut ThinSharedPointer<H, T, P>) -> Option<&mut HeaderSlice<H, T>> {
+        if this.is_static() {
+            return None;
+        }
+
+        // SAFETY: The pointer is not static, so it owns a strong reference, which is kept alive by
+        // `this` and not used by anything else while `this` is borrowed mutably.
+        unsafe { P::thin_get_mut(this.tagged_fat_ptr()) }
+    }
+
+    /// Returns the number of strong references to the value.
+    ///
+    /// Static values are not reference-counted, and never dropped, so this returns `usize::MAX`
+    /// for them.
+    #[inline(always)]
+    #[must_use]
+    pub fn strong_count(this: &Self) -> usize {
+        if this.is_static() {
+            return usize::MAX;
+        }
+
+        // SAFETY: The pointer is not static, so it owns a strong reference.
+        unsafe { P::thin_strong_count(this.tagged_fat_ptr()) }
+    }
+
+    /// Returns `true` if both pointers point to the same allocation.
+    #[inline(always)]
+    #[must_use]
+    pub fn ptr_eq<PO: SharedThinKind>(
+        this: &ThinSharedPointer<H, T, P>,
+        other: &ThinSharedPointer<H, T, PO>,
+    ) -> bool {
+        ptr::addr_eq(this.ptr.as_ptr(), other.ptr.as_ptr())
+    }
+}
+
+impl<H, T, P> Deref for ThinSharedPointer<H, T, P>
+where
+    P: SharedThinKind,
+{
+    type Target = HeaderSlice<H, T>;
+
+    #[inline(always)]
+    fn deref(&self) -> &HeaderSlice<H, T> {
+        // SAFETY: The value is kept alive by `self`.
+        unsafe { &*ThinSharedPointer::as_ptr(self) }
+    }
+}
+
+impl<H, T, P> Borrow<HeaderSlice<H, T>> for ThinSharedPointer<H, T, P>
+where
+    P: SharedThinKind,
+{
+    #[inline(always)]
+    fn borrow(&self) -> &HeaderSlice<H, T> {
+        self.deref()
+    }
+}
+
+impl<H, T, P> AsRef<HeaderSlice<H, T>> for ThinSharedPointer<H, T, P>
+where
+    P: SharedThinKind,
+{
+    #[inline(always)]
+    fn as_ref(&self) -> &HeaderSlice<H, T> {
+        self.deref()
+    }
+}
+
+impl<H, T, P> Clone for ThinSharedPointer<H, T, P>
+where
+    P: SharedThinKind,
+{
+    #[inline(always)]
+    fn clone(&self) -> ThinSharedPointer<H, T, P> {
+        if !self.is_static() {
+            // SAFETY: The pointer is not static, so it owns a strong reference. The new reference
+            // is owned by the new `ThinSharedPointer`.
+            unsafe { P::thin_clone(self.tagged_fat_ptr()) };
+        }
+
+        ThinSharedPointer { ptr: self.ptr, _phantom: PhantomData }
+    }
+}
+
+impl<H, T, P> Hash for ThinSharedPointer<H, T, P>
+where
+    H: Hash,
+    T: Hash,
+    P: SharedThinKind,
+{
+    #[inline(always)]
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        self.deref().hash(state);
+    }
+}
+
+impl<H, T, P, PO> PartialEq<ThinSharedPointer<H, T, PO>> for ThinSharedPointer<H, T, P>
+where
+    H: PartialEq,
+    T: PartialEq,
+    P: SharedThinKind,
+    PO: SharedThinKind,
+{
+    #[inline(always)]
+    fn eq(&self, other: &ThinSharedPointer<H, T, PO>) -> bool {
+        self.deref().eq(other.deref())
+    }
+}
+
+impl<H, T, P> Eq for ThinSharedPointer<H, T, P>
+where
+    H: Eq,
+    T: Eq,
+    P: SharedThinKind,
+{
+}
+
+impl<H, T, P, PO> PartialOrd<ThinSharedPointer<H, T, PO>> for ThinSharedPointer<H, T, P>
+where
+    H: PartialOrd,
+    T: PartialOrd,
+    P: SharedThinKind,
+    PO: SharedThinKind,
+{
+    #[inline(always)]
+    fn partial_cmp(&self, other: &ThinSharedPointer<H, T, PO>) -> Option<Ordering> {
+        self.deref().partial_cmp(other.deref())
+    }
+}
+
+impl<H, T, P> Ord for ThinSharedPointer<H, T, P>
+where
+    H: Ord,
+    T: Ord,
+    P: SharedThinKind,
+{
+    #[inline(always)]
+    fn cmp(&self, other: &ThinSharedPointer<H, T, P>) -> Ordering {
+        self.deref().cmp(other.deref())
+    }
+}
+
+impl<H, T, P> From<SharedPointer<HeaderSlice<H, T>, P>> for ThinSharedPointer<H, T, P>
+where
+    P: SharedThinKind,
+{
+    #[inline(always)]
+    fn from(other: SharedPointer<HeaderSlice<H, T>, P>) -> ThinSharedPointer<H, T, P> {
+        let (ptr, kind) = SharedPointer::into_parts(other);
+
+        // The kind instance is the default one, which is rebuilt when needed.
+        mem::forget(kind);
+
+        ThinSharedPointer { ptr: ptr.cast(), _phantom: PhantomData }
+    }
+}
+
+impl<H, T, P> From<ThinSharedPointer<H, T, P>> for SharedPointer<HeaderSlice<H, T>, P>
+where
+    P: SharedThinKind,
+{
+    #[inline(always)]
+    fn from(other: ThinSharedPointer<H, T, P>) -> SharedPointer<HeaderSlice<H, T>, P> {
+        ThinSharedPointer::into_shared(other)
+    }
+}
+
+impl<H, T, P> Debug for ThinSharedPointer<H, T, P>
+where
+    H: Debug,
+    T: Debug,
+    P: SharedThinKind,
+{
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        Debug::fmt(self.deref(), f)
+    }
+}
+
+impl<H, T, P> fmt::Pointer for ThinSharedPointer<H, T, P>
+where
+    P: SharedThinKind,
+{
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Pointer::fmt(&ThinSharedPointer::as_ptr(self).cast::<HeaderWithLength<H>>(), f)
+    }
+}
+
+impl<H, T, P> Drop for ThinSharedPointer<H, T, P>
+where
+    P: SharedThinKind,
+{
+    #[inline(always)]
+    fn drop(&mut self) {
+        if !self.is_static() {
+            // SAFETY: The pointer is not static, so it owns a strong reference, which is given
+            // away.
+            unsafe { P::thin_drop(self.tagged_fat_ptr()) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
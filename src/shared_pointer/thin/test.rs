@@ -0,0 +1,342 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+#[cfg(feature = "triomphe")]
+use crate::shared_pointer::kind::ArcTK;
+use crate::shared_pointer::kind::RcK;
+use pretty_assertions::assert_eq;
+use static_assertions::assert_impl_all;
+use static_assertions::assert_not_impl_any;
+use std::cell::Cell;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::string::String;
+use std::string::ToString;
+use std::vec::Vec;
+
+assert_impl_all!(ThinSharedPointer<i32, i32, ArcK>: Send, Sync);
+assert_not_impl_any!(ThinSharedPointer<i32, i32, RcK>: Send, Sync);
+assert_not_impl_any!(ThinSharedPointer<Cell<i32>, i32, ArcK>: Send, Sync);
+
+#[derive(Debug)]
+struct DropCounter<'a>(&'a Cell<usize>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+/// Iterator that reports `len` as its length regardless of the number of items it yields.
+struct LyingIter<I> {
+    iter: I,
+    len: usize,
+}
+
+impl<I: Iterator> Iterator for LyingIter<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<I: Iterator> ExactSizeIterator for LyingIter<I> {}
+
+#[repr(align(64))]
+#[derive(Debug, PartialEq)]
+struct Aligned(u8);
+
+fn check_from_header_and_iter<P: SharedThinKind>() {
+    let ptr: ThinSharedPointer<String, i32, P> =
+        ThinSharedPointer::from_header_and_iter("header".to_string(), [1, 2, 3].into_iter());
+
+    assert_eq!(ptr.header(), "header");
+    assert_eq!(ptr.slice(), [1, 2, 3]);
+    assert_eq!(ThinSharedPointer::strong_count(&ptr), 1);
+
+    let ptr: ThinSharedPointer<Aligned, u8, P> =
+        ThinSharedPointer::from_header_and_iter(Aligned(7), (0..5).map(|i| i * 2));
+
+    assert_eq!(ptr.header(), &Aligned(7));
+    assert_eq!(ptr.slice(), [0, 2, 4, 6, 8]);
+
+    let ptr: ThinSharedPointer<u8, Aligned, P> =
+        ThinSharedPointer::from_header_and_iter(1, (0..3).map(Aligned));
+
+    assert_eq!(*ptr.header(), 1);
+    assert_eq!(ptr.slice(), [Aligned(0), Aligned(1), Aligned(2)]);
+
+    let ptr: ThinSharedPointer<(), (), P> =
+        ThinSharedPointer::from_header_and_iter((), [(); 10].into_iter());
+
+    assert_eq!(ptr.slice().len(), 10);
+
+    let ptr: ThinSharedPointer<i32, String, P> =
+        ThinSharedPointer::from_header_and_iter(0, core::iter::empty());
+
+    assert!(ptr.slice().is_empty());
+}
+
+#[test]
+fn test_from_header_and_iter() {
+    check_from_header_and_iter::<RcK>();
+    check_from_header_and_iter::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_from_header_and_iter::<ArcTK>();
+}
+
+#[test]
+fn test_from_header_and_slice() {
+    let ptr: ThinSharedPointer<i32, String, RcK> =
+        ThinSharedPointer::from_header_and_slice(42, &["a".to_string(), "b".to_string()]);
+
+    assert_eq!(*ptr.header(), 42);
+    assert_eq!(ptr.slice(), ["a", "b"]);
+}
+
+#[test]
+fn test_size() {
+    assert_eq!(size_of::<ThinSharedPointer<i32, i32, RcK>>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<ThinSharedPointer<i32, i32, RcK>>>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<ThinSharedPointer<i32, i32, ArcK>>>(), size_of::<usize>());
+    #[cfg(feature = "triomphe")]
+    assert_eq!(size_of::<Option<ThinSharedPointer<i32, i32, ArcTK>>>(), size_of::<usize>());
+}
+
+fn check_clone_drop<P: SharedThinKind>() {
+    let dropped = Cell::new(0);
+    let ptr: ThinSharedPointer<DropCounter, DropCounter, P> =
+        ThinSharedPointer::from_header_and_iter(
+            DropCounter(&dropped),
+            (0..3).map(|_| DropCounter(&dropped)),
+        );
+    let ptr_clone = ThinSharedPointer::clone(&ptr);
+
+    assert!(ThinSharedPointer::ptr_eq(&ptr, &ptr_clone));
+    assert_eq!(ThinSharedPointer::strong_count(&ptr), 2);
+
+    drop(ptr);
+
+    assert_eq!(dropped.get(), 0);
+    assert_eq!(ThinSharedPointer::strong_count(&ptr_clone), 1);
+
+    drop(ptr_clone);
+
+    assert_eq!(dropped.get(), 4);
+}
+
+#[test]
+fn test_clone_drop() {
+    check_clone_drop::<RcK>();
+    check_clone_drop::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_clone_drop::<ArcTK>();
+}
+
+fn check_get_mut<P: SharedThinKind>() {
+    let mut ptr: ThinSharedPointer<i32, i32, P> =
+        ThinSharedPointer::from_header_and_iter(0, [1, 2].into_iter());
+
+    {
+        let header_slice = ThinSharedPointer::get_mut(&mut ptr).unwrap();
+
+        *header_slice.header_mut() += 10;
+        header_slice.slice_mut()[1] = 20;
+    }
+
+    assert_eq!(*ptr.header(), 10);
+    assert_eq!(ptr.slice(), [1, 20]);
+
+    let ptr_clone = ThinSharedPointer::clone(&ptr);
+
+    assert!(ThinSharedPointer::get_mut(&mut ptr).is_none());
+
+    drop(ptr_clone);
+
+    assert!(ThinSharedPointer::get_mut(&mut ptr).is_some());
+}
+
+#[test]
+fn test_get_mut() {
+    check_get_mut::<RcK>();
+    check_get_mut::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_get_mut::<ArcTK>();
+}
+
+fn check_into_shared<P: SharedThinKind>() {
+    let ptr: ThinSharedPointer<i32, i32, P> =
+        ThinSharedPointer::from_header_and_iter(42, [1, 2, 3].into_iter());
+    let raw = ThinSharedPointer::as_ptr(&ptr);
+    let shared: SharedPointer<HeaderSlice<i32, i32>, P> = ThinSharedPointer::into_shared(ptr);
+
+    assert_eq!(SharedPointer::as_ptr(&shared), raw);
+    assert_eq!(SharedPointer::strong_count(&shared), 1);
+    assert_eq!(shared.slice(), [1, 2, 3]);
+
+    let ptr: ThinSharedPointer<i32, i32, P> = ThinSharedPointer::from(shared);
+
+    assert_eq!(ThinSharedPointer::as_ptr(&ptr), raw);
+    assert_eq!(*ptr.header(), 42);
+    assert_eq!(ptr.slice(), [1, 2, 3]);
+}
+
+#[test]
+fn test_into_shared() {
+    check_into_shared::<RcK>();
+    check_into_shared::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_into_shared::<ArcTK>();
+}
+
+fn check_iter_length_mismatch<P: SharedThinKind>() {
+    let dropped = Cell::new(0);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let iter = LyingIter { iter: (0..2).map(|_| DropCounter(&dropped)), len: 3 };
+
+        ThinSharedPointer::<(), DropCounter, P>::from_header_and_iter((), iter)
+    }));
+
+    assert!(result.is_err());
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let iter = LyingIter { iter: (0..3).map(|_| DropCounter(&dropped)), len: 2 };
+
+        ThinSharedPointer::<(), DropCounter, P>::from_header_and_iter((), iter)
+    }));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_iter_length_mismatch() {
+    check_iter_length_mismatch::<RcK>();
+    check_iter_length_mismatch::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_iter_length_mismatch::<ArcTK>();
+}
+
+#[test]
+fn test_iter_length_mismatch_drops_items() {
+    let dropped = Cell::new(0);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let iter = LyingIter { iter: (0..2).map(|_| DropCounter(&dropped)), len: 3 };
+
+        ThinSharedPointer::<(), DropCounter, RcK>::from_header_and_iter((), iter)
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(dropped.get(), 2);
+
+    dropped.set(0);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let iter = LyingIter { iter: (0..3).map(|_| DropCounter(&dropped)), len: 2 };
+
+        ThinSharedPointer::<(), DropCounter, ArcK>::from_header_and_iter((), iter)
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(dropped.get(), 3);
+}
+
+#[test]
+fn test_eq_ord_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::BuildHasher;
+    use std::hash::BuildHasherDefault;
+
+    let a: ThinSharedPointer<i32, i32, RcK> =
+        ThinSharedPointer::from_header_and_iter(1, [2].into_iter());
+    let b: ThinSharedPointer<i32, i32, ArcK> =
+        ThinSharedPointer::from_header_and_iter(1, [2].into_iter());
+    let c: ThinSharedPointer<i32, i32, RcK> =
+        ThinSharedPointer::from_header_and_iter(1, [3].into_iter());
+    let d: ThinSharedPointer<i32, i32, RcK> =
+        ThinSharedPointer::from_header_and_iter(0, [4].into_iter());
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert!(a < c);
+    assert!(d < a);
+    assert_eq!(a.cmp(&c), Ordering::Less);
+
+    let hasher = BuildHasherDefault::<DefaultHasher>::default();
+
+    assert_eq!(hasher.hash_one(&a), hasher.hash_one(&b));
+}
+
+#[test]
+fn test_debug() {
+    let ptr: ThinSharedPointer<&str, i32, RcK> =
+        ThinSharedPointer::from_header_and_iter("header", [1, 2].into_iter());
+
+    assert_eq!(format!("{:?}", ptr), r#"HeaderSlice { header: "header", slice: [1, 2] }"#);
+}
+
+#[test]
+fn test_fmt_pointer() {
+    let ptr: ThinSharedPointer<i32, i32, ArcK> =
+        ThinSharedPointer::from_header_and_iter(0, [1].into_iter());
+
+    assert_eq!(format!("{:p}", ptr), format!("{:p}", ThinSharedPointer::as_ptr(&ptr).cast::<()>()));
+}
+
+fn check_static<P: SharedThinKind>() {
+    let ptr: ThinSharedPointer<&str, i32, P> =
+        ThinSharedPointer::from_header_and_iter("header", [1, 2, 3].into_iter());
+    let value: &'static HeaderSlice<&str, i32> =
+        SharedPointer::leak(ThinSharedPointer::into_shared(ptr));
+    let mut ptr: ThinSharedPointer<&str, i32, P> =
+        ThinSharedPointer::from(SharedPointer::from_static(value));
+    let ptr_clone = ThinSharedPointer::clone(&ptr);
+
+    assert_eq!(*ptr.header(), "header");
+    assert_eq!(ptr.slice(), [1, 2, 3]);
+    assert_eq!(ThinSharedPointer::as_ptr(&ptr), ptr::from_ref(value));
+    assert_eq!(ThinSharedPointer::strong_count(&ptr), usize::MAX);
+    assert!(ThinSharedPointer::get_mut(&mut ptr).is_none());
+    assert!(ThinSharedPointer::ptr_eq(&ptr, &ptr_clone));
+
+    let shared = ThinSharedPointer::into_shared(ptr_clone);
+
+    assert!(SharedPointer::is_static(&shared));
+    assert_eq!(SharedPointer::as_ptr(&shared), ptr::from_ref(value));
+}
+
+#[test]
+fn test_static() {
+    check_static::<RcK>();
+    check_static::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_static::<ArcTK>();
+}
+
+#[test]
+fn test_recursive() {
+    struct Node {
+        children: ThinSharedPointer<u32, Option<Node>, ArcK>,
+    }
+
+    fn build(depth: u32) -> Node {
+        let children: Vec<Option<Node>> =
+            (0..depth).map(|d| if d == 0 { None } else { Some(build(d - 1)) }).collect();
+
+        Node { children: ThinSharedPointer::from_header_and_iter(depth, children.into_iter()) }
+    }
+
+    fn count(node: &Node) -> usize {
+        1 + node.children.slice().iter().flatten().map(count).sum::<usize>()
+    }
+
+    let root = build(5);
+
+    assert_eq!(*root.children.header(), 5);
+    assert_eq!(count(&root), 8);
+}
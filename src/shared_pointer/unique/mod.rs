@@ -0,0 +1,269 @@
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::SharedPointerKind;
+use core::borrow::Borrow;
+use core::borrow::BorrowMut;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+#[cfg(feature = "coerce_unsized")]
+use core::marker::Unsize;
+use core::mem::ManuallyDrop;
+#[cfg(feature = "coerce_unsized")]
+use core::ops::CoerceUnsized;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::ptr::NonNull;
+
+/// Pointer to data with reference-counting that is not (yet) shared.
+///
+/// A `UniqueSharedPointer` is the only reference to its value: its strong count is one and there
+/// are no weak references to it. This means it can give mutable access to the value, through
+/// [`DerefMut`], without any check. This is useful to build a value in several mutation steps
+/// before publishing it with [`UniqueSharedPointer::into_shared()`], which is free.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+///
+/// let mut unique: UniqueSharedPointer<Vec<i32>, RcK> = UniqueSharedPointer::new(Vec::new());
+///
+/// unique.push(1944);
+/// unique.push(1984);
+///
+/// let shared: SharedPointer<Vec<i32>, RcK> = UniqueSharedPointer::into_shared(unique);
+///
+/// assert_eq!(*shared, [1944, 1984]);
+/// ```
+pub struct UniqueSharedPointer<T: ?Sized, P>
+where
+    P: SharedPointerKind,
+{
+    /// The pointer of the [raw parts](SharedPointerKind#raw-parts) of a `P::Pointer<T>` that is
+    /// the only reference to its value.
+    ptr: NonNull<T>,
+    kind: ManuallyDrop<P>,
+    _phantom_t: PhantomData<T>,
+}
+
+unsafe impl<T: Sync + Send + ?Sized, P: Send> Send for UniqueSharedPointer<T, P> where
+    P: SharedPointerKind
+{
+}
+unsafe impl<T: Sync + Send + ?Sized, P: Sync> Sync for UniqueSharedPointer<T, P> where
+    P: SharedPointerKind
+{
+}
+
+impl<T: ?Sized, P> Unpin for UniqueSharedPointer<T, P> where P: SharedPointerKind {}
+
+#[cfg(feature = "coerce_unsized")]
+impl<T, U, P> CoerceUnsized<UniqueSharedPointer<U, P>> for UniqueSharedPointer<T, P>
+where
+    T: Unsize<U> + ?Sized,
+    U: ?Sized,
+    P: SharedPointerKind,
+{
+}
+
+impl<T, P> UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    pub fn new(v: T) -> UniqueSharedPointer<T, P> {
+        // SAFETY: The pointer was just created, so it is the only reference to its value.
+        unsafe { UniqueSharedPointer::new_from_inner(P::new::<T>(v)) }
+    }
+}
+
+impl<T: ?Sized, P> UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    /// Creates a `UniqueSharedPointer` from its raw parts.
+    ///
+    /// # Safety
+    ///
+    /// The raw parts must have been returned by [`SharedPointerKind::into_raw()`] for a pointer
+    /// that is the only reference to its allocation.
+    #[inline(always)]
+    pub(super) unsafe fn from_parts(ptr: NonNull<T>, kind: P) -> UniqueSharedPointer<T, P> {
+        UniqueSharedPointer { ptr, kind: ManuallyDrop::new(kind), _phantom_t: PhantomData }
+    }
+
+    /// Creates a `UniqueSharedPointer` from `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be the only reference to its allocation.
+    #[inline(always)]
+    pub(super) unsafe fn new_from_inner(ptr: P::Pointer<T>) -> UniqueSharedPointer<T, P> {
+        let (ptr, kind) = P::into_raw::<T>(ptr);
+
+        // SAFETY: The caller guarantees that the pointer is the only reference to its allocation.
+        unsafe { UniqueSharedPointer::from_parts(ptr, kind) }
+    }
+
+    /// Converts this pointer into a [`SharedPointer`]. This does not copy `T` nor change the
+    /// reference count.
+    #[inline(always)]
+    pub fn into_shared(this: UniqueSharedPointer<T, P>) -> SharedPointer<T, P> {
+        let mut this = ManuallyDrop::new(this);
+
+        // SAFETY: `this` is never used again.
+        SharedPointer::from_parts(this.ptr, unsafe { ManuallyDrop::take(&mut this.kind) })
+    }
+
+    #[inline(always)]
+    pub fn as_ptr(this: &Self) -> *const T {
+        this.ptr.as_ptr().cast_const()
+    }
+}
+
+impl<T, P> Default for UniqueSharedPointer<T, P>
+where
+    T: Default,
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn default() -> UniqueSharedPointer<T, P> {
+        UniqueSharedPointer::new(Default::default())
+    }
+}
+
+impl<T: ?Sized, P> Deref for UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // SAFETY: The value is kept alive by `self`.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized, P> DerefMut for UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: The invariant of `UniqueSharedPointer` guarantees there is no other reference
+        // to the allocation.
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: ?Sized, P> Borrow<T> for UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn borrow(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<T: ?Sized, P> BorrowMut<T> for UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn borrow_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<T: ?Sized, P> AsRef<T> for UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<T: ?Sized, P> AsMut<T> for UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<T, P> From<T> for UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn from(other: T) -> UniqueSharedPointer<T, P> {
+        UniqueSharedPointer::new(other)
+    }
+}
+
+impl<T: ?Sized, P> From<UniqueSharedPointer<T, P>> for SharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn from(other: UniqueSharedPointer<T, P>) -> SharedPointer<T, P> {
+        UniqueSharedPointer::into_shared(other)
+    }
+}
+
+impl<T: ?Sized, P> Debug for UniqueSharedPointer<T, P>
+where
+    T: Debug,
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        Debug::fmt(self.deref(), f)
+    }
+}
+
+impl<T: ?Sized, P> fmt::Pointer for UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Pointer::fmt(&core::ptr::addr_of!(**self), f)
+    }
+}
+
+impl<T: ?Sized, P> Display for UniqueSharedPointer<T, P>
+where
+    T: Display,
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        Display::fmt(self.deref(), f)
+    }
+}
+
+impl<T: ?Sized, P> Drop for UniqueSharedPointer<T, P>
+where
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn drop(&mut self) {
+        // SAFETY: `self` is not used again.
+        let kind: P = unsafe { ManuallyDrop::take(&mut self.kind) };
+
+        // SAFETY: The raw parts come from `P::into_raw()`.
+        drop(unsafe { P::from_raw::<T>(self.ptr, kind) });
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,146 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+use crate::shared_pointer::kind::RcK;
+use static_assertions::assert_impl_all;
+use std::cell::Cell;
+use std::mem;
+use std::string::String;
+use std::string::ToString;
+use std::vec::Vec;
+
+assert_impl_all!(UniqueSharedPointer<i32, ArcK>: Send, Sync);
+
+mod static_check_rc_is_not_send_nor_sync {
+    use crate::{RcK, UniqueSharedPointer};
+    use static_assertions::*;
+
+    assert_not_impl_any!(UniqueSharedPointer<i32, RcK>: Send);
+    assert_not_impl_any!(UniqueSharedPointer<i32, RcK>: Sync);
+}
+
+mod static_check_arc_of_non_sync_is_not_send_nor_sync {
+    use crate::{ArcK, UniqueSharedPointer};
+    use static_assertions::*;
+    use std::cell::Cell;
+
+    assert_not_impl_any!(UniqueSharedPointer<Cell<()>, ArcK>: Send);
+    assert_not_impl_any!(UniqueSharedPointer<Cell<()>, ArcK>: Sync);
+}
+
+#[test]
+fn test_deref_mut() {
+    let mut unique: UniqueSharedPointer<_, RcK> = UniqueSharedPointer::new(Vec::new());
+
+    unique.push(1);
+    unique.push(2);
+
+    assert_eq!(*unique, [1, 2]);
+}
+
+#[test]
+fn test_into_shared() {
+    let mut unique: UniqueSharedPointer<_, ArcK> = UniqueSharedPointer::new(42);
+    let addr: *const i32 = UniqueSharedPointer::as_ptr(&unique);
+
+    *unique += 1;
+
+    let shared: SharedPointer<_, ArcK> = UniqueSharedPointer::into_shared(unique);
+
+    assert_eq!(*shared, 43);
+    assert_eq!(SharedPointer::strong_count(&shared), 1);
+    assert_eq!(SharedPointer::as_ptr(&shared), addr);
+}
+
+#[test]
+fn test_try_into_unique() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let addr: *const i32 = SharedPointer::as_ptr(&ptr);
+
+    let mut unique = SharedPointer::try_into_unique(ptr).unwrap();
+
+    *unique += 1;
+
+    assert_eq!(*unique, 43);
+    assert_eq!(UniqueSharedPointer::as_ptr(&unique), addr);
+}
+
+#[test]
+fn test_try_into_unique_shared() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let ptr_clone = SharedPointer::clone(&ptr);
+
+    let ptr = SharedPointer::try_into_unique(ptr).unwrap_err();
+
+    assert_eq!(SharedPointer::strong_count(&ptr), 2);
+
+    mem::drop(ptr_clone);
+
+    assert!(SharedPointer::try_into_unique(ptr).is_ok());
+}
+
+#[test]
+fn test_try_into_unique_with_weak() {
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::new(42);
+    let weak = SharedPointer::downgrade(&ptr);
+
+    let ptr = SharedPointer::try_into_unique(ptr).unwrap_err();
+
+    mem::drop(weak);
+
+    assert!(SharedPointer::try_into_unique(ptr).is_ok());
+}
+
+#[test]
+fn test_drop() {
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let unique: UniqueSharedPointer<_, RcK> = UniqueSharedPointer::new(DropCounter(&drops));
+
+    mem::drop(unique);
+
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn test_from() {
+    let unique: UniqueSharedPointer<String, ArcK> = UniqueSharedPointer::from("hello".to_string());
+    let shared: SharedPointer<String, ArcK> = SharedPointer::from(unique);
+
+    assert_eq!(*shared, "hello");
+}
+
+#[cfg(feature = "triomphe")]
+#[test]
+fn test_triomphe_unique_arc() {
+    use crate::shared_pointer::kind::ArcTK;
+    use triomphe::UniqueArc;
+
+    let mut unique: UniqueSharedPointer<i32, ArcTK> = UniqueSharedPointer::from(UniqueArc::new(42));
+
+    *unique += 1;
+
+    let unique_arc: UniqueArc<i32> = UniqueArc::from(unique);
+
+    assert_eq!(*unique_arc, 43);
+}
+
+#[test]
+fn test_debug() {
+    let unique: UniqueSharedPointer<_, RcK> = UniqueSharedPointer::new([1, 2, 3]);
+
+    assert_eq!(format!("{unique:?}"), "[1, 2, 3]");
+}
+
+#[test]
+fn test_display() {
+    let unique: UniqueSharedPointer<_, RcK> = UniqueSharedPointer::new("hello");
+
+    assert_eq!(format!("{unique}"), "hello");
+}
@@ -0,0 +1,259 @@
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::SharedAtomicKind;
+use crate::shared_pointer::kind::SharedPointerKind;
+use crate::shared_pointer::kind::SharedStatelessKind;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+#[cfg(feature = "local_waker")]
+use core::task::LocalWaker;
+use core::task::RawWaker;
+use core::task::RawWakerVTable;
+use core::task::Waker;
+
+/// Implementation of waking a task, to build a [`Waker`] from a `SharedPointer` of an atomic
+/// pointer kind. This is the equivalent of [`alloc::task::Wake`] for `SharedPointer`.
+///
+/// The kind is a type parameter, so an executor can implement this only for the kind it stores
+/// its tasks with, or for any atomic kind.
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+/// use std::sync::atomic::AtomicBool;
+/// use std::sync::atomic::Ordering;
+/// use std::task::Waker;
+///
+/// struct Task {
+///     woken: AtomicBool,
+/// }
+///
+/// impl SharedWake<ArcK> for Task {
+///     fn wake(this: SharedPointer<Task, ArcK>) {
+///         Task::wake_by_ref(&this);
+///     }
+///
+///     fn wake_by_ref(this: &SharedPointer<Task, ArcK>) {
+///         this.woken.store(true, Ordering::Release);
+///     }
+/// }
+///
+/// let task: SharedPointer<Task, ArcK> = SharedPointer::new(Task { woken: AtomicBool::new(false) });
+/// let waker: Waker = SharedPointer::into_waker(SharedPointer::clone(&task));
+///
+/// waker.wake();
+///
+/// assert!(task.woken.load(Ordering::Acquire));
+/// ```
+pub trait SharedWake<P: SharedAtomicKind> {
+    /// Wakes the task.
+    fn wake(this: SharedPointer<Self, P>);
+
+    /// Wakes the task without consuming the pointer. By default this clones the pointer and calls
+    /// [`Self::wake()`].
+    #[inline(always)]
+    fn wake_by_ref(this: &SharedPointer<Self, P>) {
+        Self::wake(SharedPointer::clone(this));
+    }
+}
+
+/// Implementation of waking a task, to build a [`LocalWaker`] from a `SharedPointer` of any
+/// pointer kind, typically [`RcK`](crate::RcK), for single-threaded executors. This is the
+/// equivalent of `alloc::task::LocalWake` for `SharedPointer`.
+///
+/// This requires the `local_waker` feature, which requires a nightly compiler.
+#[cfg(feature = "local_waker")]
+pub trait SharedLocalWake<P: SharedPointerKind> {
+    /// Wakes the task.
+    fn wake(this: SharedPointer<Self, P>);
+
+    /// Wakes the task without consuming the pointer. By default this clones the pointer and calls
+    /// [`Self::wake()`].
+    #[inline(always)]
+    fn wake_by_ref(this: &SharedPointer<Self, P>) {
+        Self::wake(SharedPointer::clone(this));
+    }
+}
+
+impl<W, P> SharedPointer<W, P>
+where
+    W: SharedWake<P> + Send + Sync + 'static,
+    P: SharedAtomicKind + SharedStatelessKind,
+{
+    /// Converts this pointer into a [`Waker`] that wakes the task with [`SharedWake`]. Cloning
+    /// and dropping the waker clones and drops the pointer.
+    #[inline(always)]
+    #[must_use]
+    pub fn into_waker(this: SharedPointer<W, P>) -> Waker {
+        // SAFETY: The functions of the vtable implement the `RawWaker` contract, and the bounds on
+        //         `W` and `P` make the pointer, and therefore the waker, `Send` and `Sync`.
+        unsafe { Waker::from_raw(VTables::<W, P, SyncWake>::raw_waker(this)) }
+    }
+}
+
+#[cfg(feature = "local_waker")]
+impl<W, P> SharedPointer<W, P>
+where
+    W: SharedLocalWake<P> + 'static,
+    P: SharedStatelessKind,
+{
+    /// Converts this pointer into a [`LocalWaker`] that wakes the task with [`SharedLocalWake`].
+    /// Cloning and dropping the waker clones and drops the pointer.
+    #[inline(always)]
+    #[must_use]
+    pub fn into_local_waker(this: SharedPointer<W, P>) -> LocalWaker {
+        // SAFETY: The functions of the vtable implement the `RawWaker` contract.
+        unsafe { LocalWaker::from_raw(VTables::<W, P, LocalWake>::raw_waker(this)) }
+    }
+}
+
+impl<W, P> From<SharedPointer<W, P>> for Waker
+where
+    W: SharedWake<P> + Send + Sync + 'static,
+    P: SharedAtomicKind + SharedStatelessKind,
+{
+    #[inline(always)]
+    fn from(ptr: SharedPointer<W, P>) -> Waker {
+        SharedPointer::into_waker(ptr)
+    }
+}
+
+#[cfg(feature = "local_waker")]
+impl<W, P> From<SharedPointer<W, P>> for LocalWaker
+where
+    W: SharedLocalWake<P> + 'static,
+    P: SharedStatelessKind,
+{
+    #[inline(always)]
+    fn from(ptr: SharedPointer<W, P>) -> LocalWaker {
+        SharedPointer::into_local_waker(ptr)
+    }
+}
+
+/// Selects which trait the raw waker functions wake the task with.
+trait WakeWith<W, P: SharedPointerKind> {
+    fn wake(ptr: SharedPointer<W, P>);
+    fn wake_by_ref(ptr: &SharedPointer<W, P>);
+}
+
+struct SyncWake;
+
+impl<W, P> WakeWith<W, P> for SyncWake
+where
+    W: SharedWake<P>,
+    P: SharedAtomicKind,
+{
+    #[inline(always)]
+    fn wake(ptr: SharedPointer<W, P>) {
+        W::wake(ptr);
+    }
+
+    #[inline(always)]
+    fn wake_by_ref(ptr: &SharedPointer<W, P>) {
+        W::wake_by_ref(ptr);
+    }
+}
+
+#[cfg(feature = "local_waker")]
+struct LocalWake;
+
+#[cfg(feature = "local_waker")]
+impl<W, P> WakeWith<W, P> for LocalWake
+where
+    W: SharedLocalWake<P>,
+    P: SharedPointerKind,
+{
+    #[inline(always)]
+    fn wake(ptr: SharedPointer<W, P>) {
+        W::wake(ptr);
+    }
+
+    #[inline(always)]
+    fn wake_by_ref(ptr: &SharedPointer<W, P>) {
+        W::wake_by_ref(ptr);
+    }
+}
+
+/// Raw waker vtables for `SharedPointer<W, P>`. The data pointer of the raw waker is the pointer
+/// returned by [`SharedPointer::into_raw()`], or the address of the value for
+/// [static pointers](SharedPointer::from_static), which have their own vtable since they cannot
+/// be converted into a raw pointer and have no reference count to update.
+struct VTables<W, P, K> {
+    _phantom: PhantomData<(W, P, K)>,
+}
+
+impl<W, P, K> VTables<W, P, K>
+where
+    W: 'static,
+    P: SharedStatelessKind,
+    K: WakeWith<W, P>,
+{
+    const COUNTED: RawWakerVTable = RawWakerVTable::new(
+        Self::clone_counted,
+        Self::wake_counted,
+        Self::wake_by_ref_counted,
+        Self::drop_counted,
+    );
+
+    const STATIC: RawWakerVTable = RawWakerVTable::new(
+        Self::clone_static,
+        Self::wake_static,
+        Self::wake_by_ref_static,
+        Self::drop_static,
+    );
+
+    fn raw_waker(ptr: SharedPointer<W, P>) -> RawWaker {
+        if SharedPointer::is_static(&ptr) {
+            RawWaker::new(SharedPointer::as_ptr(&ptr).cast(), &Self::STATIC)
+        } else {
+            RawWaker::new(SharedPointer::into_raw(ptr).cast(), &Self::COUNTED)
+        }
+    }
+
+    unsafe fn clone_counted(data: *const ()) -> RawWaker {
+        // SAFETY: `data` was returned by `SharedPointer::into_raw()` and the waker being cloned
+        //         owns a strong reference, so the value is alive.
+        unsafe { SharedPointer::<W, P>::increment_strong_count(data.cast()) };
+
+        RawWaker::new(data, &Self::COUNTED)
+    }
+
+    unsafe fn wake_counted(data: *const ()) {
+        // SAFETY: The waker owns a strong reference, which is transferred to the pointer.
+        K::wake(unsafe { SharedPointer::from_raw(data.cast()) });
+    }
+
+    unsafe fn wake_by_ref_counted(data: *const ()) {
+        // SAFETY: The waker owns a strong reference, which is borrowed by the pointer and never
+        //         released since the pointer is not dropped.
+        let ptr = ManuallyDrop::new(unsafe { SharedPointer::from_raw(data.cast()) });
+
+        K::wake_by_ref(&ptr);
+    }
+
+    unsafe fn drop_counted(data: *const ()) {
+        // SAFETY: The waker owns a strong reference, which is released.
+        unsafe { SharedPointer::<W, P>::decrement_strong_count(data.cast()) };
+    }
+
+    unsafe fn clone_static(data: *const ()) -> RawWaker {
+        RawWaker::new(data, &Self::STATIC)
+    }
+
+    unsafe fn wake_static(data: *const ()) {
+        // SAFETY: `data` is the address of a `W` that lives forever, which was the address of a
+        //         static pointer before.
+        K::wake(SharedPointer::new_static(unsafe { &*data.cast::<W>() }));
+    }
+
+    unsafe fn wake_by_ref_static(data: *const ()) {
+        // SAFETY: `data` is the address of a `W` that lives forever, which was the address of a
+        //         static pointer before.
+        K::wake_by_ref(&SharedPointer::new_static(unsafe { &*data.cast::<W>() }));
+    }
+
+    unsafe fn drop_static(_: *const ()) {}
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,176 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+#[cfg(feature = "triomphe")]
+use crate::shared_pointer::kind::ArcTK;
+#[cfg(feature = "local_waker")]
+use crate::shared_pointer::kind::RcK;
+use pretty_assertions::assert_eq;
+#[cfg(feature = "local_waker")]
+use std::cell::Cell;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+
+#[derive(Default)]
+struct Task {
+    wakes: AtomicUsize,
+    wakes_by_ref: AtomicUsize,
+}
+
+impl<P: SharedAtomicKind> SharedWake<P> for Task {
+    fn wake(this: SharedPointer<Task, P>) {
+        this.wakes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn wake_by_ref(this: &SharedPointer<Task, P>) {
+        this.wakes_by_ref.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Task that only implements [`SharedWake::wake()`].
+#[derive(Default)]
+struct WakeOnlyTask {
+    wakes: AtomicUsize,
+}
+
+impl SharedWake<ArcK> for WakeOnlyTask {
+    fn wake(this: SharedPointer<WakeOnlyTask, ArcK>) {
+        this.wakes.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn check_waker<P: SharedAtomicKind + SharedStatelessKind>() {
+    let task: SharedPointer<Task, P> = SharedPointer::default();
+    let waker = SharedPointer::into_waker(SharedPointer::clone(&task));
+
+    assert_eq!(SharedPointer::strong_count(&task), 2);
+
+    let waker_clone = waker.clone();
+
+    assert!(waker.will_wake(&waker_clone));
+    assert_eq!(SharedPointer::strong_count(&task), 3);
+
+    waker.wake_by_ref();
+
+    assert_eq!(task.wakes_by_ref.load(Ordering::Relaxed), 1);
+    assert_eq!(SharedPointer::strong_count(&task), 3);
+
+    waker.wake();
+
+    assert_eq!(task.wakes.load(Ordering::Relaxed), 1);
+    assert_eq!(SharedPointer::strong_count(&task), 2);
+
+    drop(waker_clone);
+
+    assert_eq!(SharedPointer::strong_count(&task), 1);
+}
+
+#[test]
+fn test_waker() {
+    check_waker::<ArcK>();
+    #[cfg(feature = "triomphe")]
+    check_waker::<ArcTK>();
+}
+
+#[test]
+fn test_waker_from() {
+    let task: SharedPointer<Task, ArcK> = SharedPointer::default();
+    let waker = Waker::from(SharedPointer::clone(&task));
+
+    waker.wake();
+
+    assert_eq!(task.wakes.load(Ordering::Relaxed), 1);
+    assert_eq!(SharedPointer::strong_count(&task), 1);
+}
+
+#[test]
+fn test_default_wake_by_ref() {
+    let task: SharedPointer<WakeOnlyTask, ArcK> = SharedPointer::default();
+    let waker = SharedPointer::into_waker(SharedPointer::clone(&task));
+
+    waker.wake_by_ref();
+    waker.wake_by_ref();
+
+    assert_eq!(task.wakes.load(Ordering::Relaxed), 2);
+    assert_eq!(SharedPointer::strong_count(&task), 2);
+}
+
+#[test]
+fn test_static() {
+    static TASK: Task = Task { wakes: AtomicUsize::new(0), wakes_by_ref: AtomicUsize::new(0) };
+
+    let waker = SharedPointer::<Task, ArcK>::into_waker(SharedPointer::from_static(&TASK));
+    let waker_clone = waker.clone();
+
+    assert!(waker.will_wake(&waker_clone));
+
+    waker.wake_by_ref();
+    waker.wake();
+    drop(waker_clone);
+
+    assert_eq!(TASK.wakes.load(Ordering::Relaxed), 1);
+    assert_eq!(TASK.wakes_by_ref.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_wake_from_other_threads() {
+    let task: SharedPointer<Task, ArcK> = SharedPointer::default();
+    let waker = SharedPointer::into_waker(SharedPointer::clone(&task));
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            let waker = waker.clone();
+
+            s.spawn(move || {
+                for _ in 0..100 {
+                    waker.wake_by_ref();
+                }
+
+                waker.wake();
+            });
+        }
+    });
+
+    assert_eq!(task.wakes.load(Ordering::Relaxed), 4);
+    assert_eq!(task.wakes_by_ref.load(Ordering::Relaxed), 400);
+    assert_eq!(SharedPointer::strong_count(&task), 2);
+}
+
+#[cfg(feature = "local_waker")]
+#[derive(Default)]
+struct LocalTask {
+    wakes: Cell<usize>,
+}
+
+#[cfg(feature = "local_waker")]
+impl SharedLocalWake<RcK> for LocalTask {
+    fn wake(this: SharedPointer<LocalTask, RcK>) {
+        this.wakes.set(this.wakes.get() + 1);
+    }
+}
+
+#[cfg(feature = "local_waker")]
+#[test]
+fn test_local_waker() {
+    let task: SharedPointer<LocalTask, RcK> = SharedPointer::default();
+    let waker = SharedPointer::into_local_waker(SharedPointer::clone(&task));
+    let waker_clone = waker.clone();
+
+    assert!(waker.will_wake(&waker_clone));
+    assert_eq!(SharedPointer::strong_count(&task), 3);
+
+    waker.wake_by_ref();
+
+    assert_eq!(task.wakes.get(), 1);
+    assert_eq!(SharedPointer::strong_count(&task), 3);
+
+    waker.wake();
+    drop(waker_clone);
+
+    assert_eq!(task.wakes.get(), 2);
+    assert_eq!(SharedPointer::strong_count(&task), 1);
+
+    LocalWaker::from(SharedPointer::clone(&task)).wake();
+
+    assert_eq!(task.wakes.get(), 3);
+}
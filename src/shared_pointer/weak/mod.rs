@@ -0,0 +1,257 @@
+use crate::shared_pointer::STATIC_TAG;
+use crate::shared_pointer::SharedPointer;
+use crate::shared_pointer::kind::SharedWeakKind;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+#[cfg(feature = "coerce_unsized")]
+use core::marker::Unsize;
+use core::mem::ManuallyDrop;
+#[cfg(feature = "coerce_unsized")]
+use core::ops::CoerceUnsized;
+use core::ptr;
+use core::ptr::NonNull;
+
+/// Weak version of [`SharedPointer`].
+///
+/// A `SharedWeak` does not keep the pointed value alive: the value is dropped as soon as the last
+/// [`SharedPointer`] to it is dropped. It can be [upgraded](SharedWeak::upgrade) back to a
+/// [`SharedPointer`] while the value is still alive. This makes it suitable for back-edges, such
+/// as parent links in trees, which would otherwise form reference cycles.
+///
+/// The type parameter `P` is the [kind](crate::shared_pointer::kind::SharedPointerKind) of the
+/// strong pointer, and it must support weak references (see [`SharedWeakKind`]).
+///
+/// # Example
+///
+/// ```rust
+/// use archery::*;
+///
+/// let strong: SharedPointer<_, RcK> = SharedPointer::new("António Variações");
+/// let weak: SharedWeak<_, RcK> = SharedPointer::downgrade(&strong);
+///
+/// assert_eq!(weak.upgrade().as_deref(), Some(&"António Variações"));
+///
+/// drop(strong);
+///
+/// assert!(weak.upgrade().is_none());
+/// ```
+pub struct SharedWeak<T: ?Sized, P>
+where
+    P: SharedWeakKind,
+{
+    /// The pointer of the [raw parts](crate::shared_pointer::kind::SharedPointerKind#raw-parts)
+    /// of a `P::Weak<T>`, or a weak pointer that is not counted: either one downgraded from a
+    /// static value, tagged with [`STATIC_TAG`], or [`DANGLING`].
+    ptr: NonNull<T>,
+    /// The kind instance of the raw parts, which is the [`Default`] one for weak pointers that are
+    /// not counted.
+    kind: ManuallyDrop<P>,
+    _phantom_t: PhantomData<T>,
+}
+
+unsafe impl<T: Sync + Send + ?Sized, P: Send> Send for SharedWeak<T, P> where P: SharedWeakKind {}
+unsafe impl<T: Sync + Send + ?Sized, P: Sync> Sync for SharedWeak<T, P> where P: SharedWeakKind {}
+
+#[cfg(feature = "coerce_unsized")]
+impl<T, U, P> CoerceUnsized<SharedWeak<U, P>> for SharedWeak<T, P>
+where
+    T: Unsize<U> + ?Sized,
+    U: ?Sized,
+    P: SharedWeakKind,
+{
+}
+
+/// Address of the weak pointers created by [`SharedWeak::new()`], as with
+/// [`Weak::new()`](alloc::rc::Weak::new). No value can be there, since it would end past the
+/// address space.
+const DANGLING: usize = usize::MAX;
+
+impl<T, P> SharedWeak<T, P>
+where
+    P: SharedWeakKind,
+{
+    /// Creates a `SharedWeak` that is not associated with any value. Calling
+    /// [`upgrade()`](SharedWeak::upgrade) on it always returns `None`.
+    #[inline(always)]
+    #[must_use]
+    pub fn new() -> SharedWeak<T, P> {
+        let ptr: *mut T = ptr::without_provenance_mut(DANGLING);
+
+        // SAFETY: `DANGLING` is not zero.
+        SharedWeak::from_parts(unsafe { NonNull::new_unchecked(ptr) }, P::default())
+    }
+}
+
+impl<T: ?Sized, P> SharedWeak<T, P>
+where
+    P: SharedWeakKind,
+{
+    #[inline(always)]
+    fn from_parts(ptr: NonNull<T>, kind: P) -> SharedWeak<T, P> {
+        SharedWeak { ptr, kind: ManuallyDrop::new(kind), _phantom_t: PhantomData }
+    }
+
+    #[inline(always)]
+    pub(super) fn from_weak(weak: P::Weak<T>) -> SharedWeak<T, P> {
+        let (ptr, kind) = P::weak_into_raw::<T>(weak);
+
+        SharedWeak::from_parts(ptr, kind)
+    }
+
+    /// Creates a weak pointer to a static value, from the tagged pointer of a [`SharedPointer`].
+    #[inline(always)]
+    pub(super) fn from_static(ptr: NonNull<T>) -> SharedWeak<T, P> {
+        SharedWeak::from_parts(ptr, P::default())
+    }
+
+    /// Returns `true` if this has the raw parts of a `P::Weak<T>`.
+    #[inline(always)]
+    fn is_counted(&self) -> bool {
+        !self.is_dangling() && self.ptr.as_ptr().addr() & STATIC_TAG == 0
+    }
+
+    #[inline(always)]
+    fn is_dangling(&self) -> bool {
+        self.ptr.as_ptr().addr() == DANGLING
+    }
+
+    /// Returns a view of the weak pointer `self` holds the raw parts of, which must not be
+    /// dropped.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be counted.
+    #[inline(always)]
+    unsafe fn weak(&self) -> ManuallyDrop<P::Weak<T>> {
+        // SAFETY: The raw parts of a counted pointer come from `P::weak_into_raw()`. The kind
+        // instance is a bitwise copy, which is fine since the view is never dropped.
+        ManuallyDrop::new(unsafe {
+            P::weak_from_raw::<T>(self.ptr, ptr::read(&raw const *self.kind))
+        })
+    }
+
+    #[inline(always)]
+    pub fn upgrade(&self) -> Option<SharedPointer<T, P>> {
+        if !self.is_counted() {
+            return match self.is_dangling() {
+                true => None,
+                false => Some(SharedPointer::from_parts(self.ptr, P::default())),
+            };
+        }
+
+        // SAFETY: The pointer is counted.
+        let weak = unsafe { self.weak() };
+
+        P::upgrade::<T>(&weak).map(SharedPointer::from_pointer)
+    }
+
+    /// Returns a pointer to the value. The pointer is only valid while there are strong
+    /// pointers to the value, and it is dangling if this was created with
+    /// [`SharedWeak::new()`].
+    #[inline(always)]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const T {
+        match self.is_dangling() {
+            true => self.ptr.as_ptr().cast_const(),
+            false => self.ptr.as_ptr().map_addr(|addr| addr & !STATIC_TAG).cast_const(),
+        }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn strong_count(&self) -> usize {
+        if !self.is_counted() {
+            return match self.is_dangling() {
+                true => 0,
+                false => usize::MAX,
+            };
+        }
+
+        // SAFETY: The pointer is counted.
+        let weak = unsafe { self.weak() };
+
+        P::weak_strong_count::<T>(&weak)
+    }
+
+    /// Returns the number of `SharedWeak` pointers to the value, or `0` if there are no strong
+    /// pointers left.
+    #[inline(always)]
+    #[must_use]
+    pub fn weak_count(&self) -> usize {
+        if !self.is_counted() {
+            return 0;
+        }
+
+        // SAFETY: The pointer is counted.
+        let weak = unsafe { self.weak() };
+
+        P::weak_weak_count::<T>(&weak)
+    }
+
+    /// Returns `true` if both `SharedWeak`s point to the same allocation or if both were created
+    /// with [`SharedWeak::new()`]. Like [`ptr::addr_eq()`], this ignores the metadata of pointers
+    /// to unsized types.
+    #[inline(always)]
+    #[must_use]
+    pub fn ptr_eq<PO: SharedWeakKind>(&self, other: &SharedWeak<T, PO>) -> bool {
+        ptr::addr_eq(self.as_ptr(), other.as_ptr())
+    }
+}
+
+impl<T, P> Default for SharedWeak<T, P>
+where
+    P: SharedWeakKind,
+{
+    #[inline(always)]
+    fn default() -> SharedWeak<T, P> {
+        SharedWeak::new()
+    }
+}
+
+impl<T: ?Sized, P> Clone for SharedWeak<T, P>
+where
+    P: SharedWeakKind,
+{
+    #[inline(always)]
+    fn clone(&self) -> SharedWeak<T, P> {
+        if !self.is_counted() {
+            return SharedWeak::from_parts(self.ptr, P::default());
+        }
+
+        // SAFETY: The pointer is counted.
+        let weak = unsafe { self.weak() };
+
+        SharedWeak::from_weak(P::weak_clone::<T>(&weak))
+    }
+}
+
+impl<T: ?Sized, P> Debug for SharedWeak<T, P>
+where
+    P: SharedWeakKind,
+{
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("(Weak)")
+    }
+}
+
+impl<T: ?Sized, P> Drop for SharedWeak<T, P>
+where
+    P: SharedWeakKind,
+{
+    #[inline(always)]
+    fn drop(&mut self) {
+        // SAFETY: `self` is not used again.
+        let kind: P = unsafe { ManuallyDrop::take(&mut self.kind) };
+
+        if self.is_counted() {
+            // SAFETY: The raw parts of a counted pointer come from `P::weak_into_raw()`.
+            drop(unsafe { P::weak_from_raw::<T>(self.ptr, kind) });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
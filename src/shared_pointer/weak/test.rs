@@ -0,0 +1,210 @@
+use super::*;
+use crate::shared_pointer::kind::ArcK;
+use crate::shared_pointer::kind::RcK;
+use static_assertions::assert_impl_all;
+use std::cell::RefCell;
+use std::mem;
+use std::vec::Vec;
+
+assert_impl_all!(SharedWeak<i32, ArcK>: Send, Sync);
+
+mod static_check_rc_is_not_send_nor_sync {
+    use crate::{RcK, SharedWeak};
+    use static_assertions::*;
+
+    assert_not_impl_any!(SharedWeak<i32, RcK>: Send);
+    assert_not_impl_any!(SharedWeak<i32, RcK>: Sync);
+}
+
+mod static_check_arc_of_non_sync_is_not_send_nor_sync {
+    use crate::{ArcK, SharedWeak};
+    use static_assertions::*;
+    use std::cell::Cell;
+
+    assert_not_impl_any!(SharedWeak<Cell<()>, ArcK>: Send);
+    assert_not_impl_any!(SharedWeak<Cell<()>, ArcK>: Sync);
+}
+
+#[test]
+fn test_new() {
+    let weak: SharedWeak<i32, RcK> = SharedWeak::new();
+
+    assert!(weak.upgrade().is_none());
+    assert_eq!(weak.strong_count(), 0);
+    assert_eq!(weak.weak_count(), 0);
+}
+
+#[test]
+fn test_upgrade() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let weak = SharedPointer::downgrade(&ptr);
+
+    let upgraded = weak.upgrade().unwrap();
+
+    assert_eq!(*upgraded, 42);
+    assert!(SharedPointer::ptr_eq(&ptr, &upgraded));
+
+    mem::drop(ptr);
+    mem::drop(upgraded);
+
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_upgrade_arc() {
+    let ptr: SharedPointer<_, ArcK> = SharedPointer::new(42);
+    let weak = SharedPointer::downgrade(&ptr);
+
+    assert_eq!(weak.upgrade().as_deref(), Some(&42));
+
+    mem::drop(ptr);
+
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_counts() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+
+    assert_eq!(SharedPointer::weak_count(&ptr), 0);
+
+    let weak = SharedPointer::downgrade(&ptr);
+    let weak_clone = SharedWeak::clone(&weak);
+
+    assert_eq!(SharedPointer::strong_count(&ptr), 1);
+    assert_eq!(SharedPointer::weak_count(&ptr), 2);
+    assert_eq!(weak.strong_count(), 1);
+    assert_eq!(weak.weak_count(), 2);
+
+    mem::drop(weak_clone);
+
+    assert_eq!(SharedPointer::weak_count(&ptr), 1);
+
+    mem::drop(ptr);
+
+    assert_eq!(weak.strong_count(), 0);
+    assert_eq!(weak.weak_count(), 0);
+}
+
+#[test]
+fn test_weak_does_not_keep_value_alive() {
+    struct SetOnDrop<'a>(&'a RefCell<bool>);
+
+    impl Drop for SetOnDrop<'_> {
+        fn drop(&mut self) {
+            *self.0.borrow_mut() = true;
+        }
+    }
+
+    let dropped = RefCell::new(false);
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(SetOnDrop(&dropped));
+    let weak = SharedPointer::downgrade(&ptr);
+
+    mem::drop(ptr);
+
+    assert!(*dropped.borrow());
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_get_mut_with_weak() {
+    let mut ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let weak = SharedPointer::downgrade(&ptr);
+
+    assert_eq!(SharedPointer::get_mut(&mut ptr), None);
+
+    mem::drop(weak);
+
+    assert_eq!(SharedPointer::get_mut(&mut ptr), Some(&mut 42));
+}
+
+#[test]
+fn test_ptr_eq() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let ptr_same_content: SharedPointer<_, RcK> = SharedPointer::new(42);
+    let weak = SharedPointer::downgrade(&ptr);
+
+    assert!(weak.ptr_eq(&SharedPointer::downgrade(&ptr)));
+    assert!(!weak.ptr_eq(&SharedPointer::downgrade(&ptr_same_content)));
+    assert!(ptr::eq(weak.as_ptr(), SharedPointer::as_ptr(&ptr)));
+    assert!(SharedWeak::<i32, RcK>::new().ptr_eq(&SharedWeak::<i32, RcK>::new()));
+}
+
+#[test]
+fn test_parent_links() {
+    struct Node {
+        parent: SharedWeak<RefCell<Node>, RcK>,
+        children: Vec<SharedPointer<RefCell<Node>, RcK>>,
+        value: i32,
+    }
+
+    let root: SharedPointer<_, RcK> = SharedPointer::new(RefCell::new(Node {
+        parent: SharedWeak::new(),
+        children: Vec::new(),
+        value: 1,
+    }));
+    let child: SharedPointer<_, RcK> = SharedPointer::new(RefCell::new(Node {
+        parent: SharedPointer::downgrade(&root),
+        children: Vec::new(),
+        value: 2,
+    }));
+
+    root.borrow_mut().children.push(SharedPointer::clone(&child));
+
+    assert_eq!(child.borrow().parent.upgrade().unwrap().borrow().value, 1);
+    assert_eq!(root.borrow().children[0].borrow().value, 2);
+    assert_eq!(SharedPointer::strong_count(&root), 1);
+}
+
+#[test]
+fn test_new_cyclic() {
+    struct Node {
+        this: SharedWeak<Node, ArcK>,
+        value: i32,
+    }
+
+    let node: SharedPointer<Node, ArcK> = SharedPointer::new_cyclic(|this| {
+        assert!(this.upgrade().is_none());
+
+        Node { this: SharedWeak::clone(this), value: 42 }
+    });
+
+    assert_eq!(SharedPointer::strong_count(&node), 1);
+    assert_eq!(SharedPointer::weak_count(&node), 1);
+    assert_eq!(node.this.upgrade().unwrap().value, 42);
+    assert!(SharedPointer::ptr_eq(&node.this.upgrade().unwrap(), &node));
+
+    let weak = SharedWeak::clone(&node.this);
+
+    mem::drop(node);
+
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_new_cyclic_without_storing_weak() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new_cyclic(|_| 42);
+
+    assert_eq!(*ptr, 42);
+    assert_eq!(SharedPointer::weak_count(&ptr), 0);
+}
+
+#[test]
+fn test_unsized() {
+    let ptr: SharedPointer<str, RcK> = SharedPointer::from("hello");
+    let weak: SharedWeak<str, RcK> = SharedPointer::downgrade(&ptr);
+
+    assert_eq!(weak.upgrade().as_deref(), Some("hello"));
+    assert!(weak.ptr_eq(&SharedPointer::downgrade(&ptr)));
+
+    mem::drop(ptr);
+
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_debug() {
+    let ptr: SharedPointer<_, RcK> = SharedPointer::new(42);
+
+    assert_eq!(format!("{:?}", SharedPointer::downgrade(&ptr)), "(Weak)");
+}
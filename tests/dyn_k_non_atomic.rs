@@ -0,0 +1,48 @@
+//! The mode of `DynK` is process-global, so the non-atomic mode is tested in its own process.
+
+use archery::shared_pointer::kind::DynPointer;
+use archery::*;
+use pretty_assertions::assert_eq;
+use std::thread;
+
+#[test]
+fn test_non_atomic_mode() {
+    assert_eq!(DynK::set_mode(DynMode::NonAtomic), Ok(()));
+    assert_eq!(DynK::set_mode(DynMode::NonAtomic), Ok(()));
+    assert_eq!(DynK::set_mode(DynMode::Atomic), Err(DynMode::NonAtomic));
+    assert_eq!(DynK::mode(), DynMode::NonAtomic);
+    assert!(DynK::atomic().is_none());
+
+    let mut ptr: SharedPointer<i32, DynK> = SharedPointer::new(42);
+    let ptr_clone = SharedPointer::clone(&ptr);
+
+    assert_eq!(SharedPointer::strong_count(&ptr), 2);
+    assert_eq!(SharedPointer::get_mut(&mut ptr), None);
+
+    *SharedPointer::make_mut(&mut ptr) += 1;
+
+    assert_eq!(*ptr, 43);
+    assert_eq!(*ptr_clone, 42);
+
+    match DynPointer::from(ptr_clone) {
+        DynPointer::Rc(rc) => assert_eq!(*rc, 42),
+        DynPointer::Arc(_) => panic!("expected an `Rc`"),
+    }
+
+    thread::scope(|s| {
+        // Other threads share the mode, and can use their own pointers.
+        s.spawn(|| {
+            assert_eq!(DynK::set_mode(DynMode::NonAtomic), Ok(()));
+            assert!(DynK::atomic().is_none());
+
+            let ptr: SharedPointer<i32, DynK> = SharedPointer::new(0);
+            let ptr_clone = SharedPointer::clone(&ptr);
+
+            assert!(matches!(DynPointer::from(ptr_clone), DynPointer::Rc(_)));
+            assert_eq!(SharedPointer::into_inner(ptr), Some(0));
+        });
+    });
+
+    assert_eq!(SharedPointer::strong_count(&ptr), 1);
+    assert_eq!(SharedPointer::into_inner(ptr), Some(43));
+}
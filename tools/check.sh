@@ -22,22 +22,25 @@ trap on_failure ERR
 
 export RUSTFLAGS="-Dwarnings"
 
+# Features that work with a stable compiler. Features that require nightly are checked by `check_nightly`.
+STABLE_FEATURES="triomphe,serde,rkyv,std"
+
 function check_basic {
     assert_installed "cargo-hack"
 
     echo 'Building:'
-    cargo build --all-targets --all-features
+    cargo build --all-targets --features "$STABLE_FEATURES"
     echo 'Testing:'
-    cargo hack test --each-feature --all-targets
+    cargo hack test --each-feature --exclude-features allocator_api,local_waker,coerce_unsized --all-targets
     # Weirdly, the `cargo test ... --all-targets ...` above does not run the tests in the documentation, so we run the
     # doc tests like this.
     # See https://github.com/rust-lang/cargo/issues/6669.
     echo 'Testing doc:'
-    cargo test --doc --all-features
+    cargo test --doc --features "$STABLE_FEATURES"
     echo 'Checking the benchmarks:'
-    cargo bench --all-features -- --test
+    cargo bench --features "$STABLE_FEATURES" -- --test
     echo 'Checking documentation:'
-    cargo doc --no-deps --all-features
+    cargo doc --no-deps --features "$STABLE_FEATURES"
 
 	# Tests for memory safety and memory leaks with miri.
 	if [ -z "$MIRI_TOOLCHAIN" ]; then
@@ -47,6 +50,12 @@ function check_basic {
 	cargo +$MIRI_TOOLCHAIN miri test --all-features
 }
 
+function check_nightly {
+    echo 'Testing with nightly features:'
+    cargo +nightly test --all-targets --all-features
+    cargo +nightly test --doc --all-features
+}
+
 function check_doc_url_links {
     assert_installed "cargo-deadlinks"
 
@@ -101,7 +110,7 @@ function check_clippy {
     cargo clippy --all-targets -- -D warnings
 }
 
-to_run=(basic doc_url_links unused_deps packaging fmt toml_fmt readme msrv clippy)
+to_run=(basic nightly doc_url_links unused_deps packaging fmt toml_fmt readme msrv clippy)
 
 if [ $# -ge 1 ]; then
     to_run=("$@")